@@ -1 +0,0 @@
-pub mod todos;
\ No newline at end of file
@@ -0,0 +1,115 @@
+//! Command line of the `todo-cli` binary: operational tasks run straight
+//! against the database, through the same services the `todo-server`
+//! handlers use.
+
+use std::io::BufRead;
+use std::path::PathBuf;
+
+use clap::builder::PossibleValuesParser;
+use clap::{Parser, Subcommand};
+use todo_core::item_service;
+use todo_server::db_trace;
+use todo_server::error::AppError;
+use todo_server::extract::Actor;
+use todo_server::modules::audit::audit_service::AuditContext;
+use todo_server::modules::exports::exporter::ExporterRegistry;
+use todo_server::modules::imports::import_dto::ImportReport;
+use todo_server::modules::imports::import_service;
+use todo_server::modules::imports::importer::ImporterRegistry;
+use todo_server::modules::todos::todo_dto::CreateItemDto;
+use todo_server::modules::todos::todo_entity::{Item, Priority};
+use todo_server::modules::todos::todo_service::SqliteItemRepository;
+use todo_server::state::AppState;
+
+/// Actor recorded in the audit log for changes made from the command line.
+pub const CLI_ACTOR: &str = "cli";
+
+#[derive(Parser, Debug)]
+#[command(version, about = "Todo administration tasks")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand, Debug, PartialEq, Eq)]
+pub enum Command {
+    /// Apply pending database migrations.
+    Migrate,
+    /// Create sample items.
+    Seed {
+        #[arg(long, default_value_t = 10)]
+        count: u32,
+    },
+    /// Report dangling references and attachments without files.
+    Check {
+        /// Repair what is found instead of only reporting it.
+        #[arg(long)]
+        repair: bool,
+    },
+    /// Create items from a file. Nothing is created if any row is invalid.
+    Import {
+        #[arg(long, value_parser = PossibleValuesParser::new(ImporterRegistry::default().names()))]
+        format: String,
+        /// Validate and report without creating anything.
+        #[arg(long)]
+        dry_run: bool,
+        /// Read from standard input when omitted.
+        file: Option<PathBuf>,
+    },
+    /// Walk the core item journey through the HTTP API and exit non-zero at
+    /// the first unexpected response.
+    Smoke {
+        /// Base URL of a running deployment. When omitted the app is booted
+        /// on a random local port over a scratch in-memory database.
+        #[arg(long)]
+        url: Option<String>,
+    },
+    /// Write every item to standard output.
+    Export {
+        /// One of the built-in export formats.
+        #[arg(long, default_value = "json", value_parser = PossibleValuesParser::new(ExporterRegistry::default().names()))]
+        format: String,
+    },
+}
+
+/// Creates `count` sample items, numbered after the items already there and
+/// cycling through the priorities.
+pub async fn seed(state: &AppState, count: u32) -> Result<Vec<Item>, sqlx::Error> {
+    let audit = AuditContext::new(state, Actor::named(CLI_ACTOR));
+    let repo = SqliteItemRepository::new(&state.writer, &audit);
+    let (existing,): (i64,) = db_trace::query_as("SELECT COUNT(*) FROM items").fetch_one(&state.pool).await?;
+
+    let mut items = Vec::with_capacity(count as usize);
+    for n in 1..=i64::from(count) {
+        let dto = CreateItemDto {
+            name: format!("Sample item {}", existing + n),
+            description: None,
+            priority: [Priority::Low, Priority::Medium, Priority::High][(n % 3) as usize],
+            parent_id: None,
+        };
+        items.push(item_service::create_item(&repo, audit.ids, audit.now, dto).await?);
+    }
+
+    Ok(items)
+}
+
+/// Imports `input` in the named format of the app's registry, reporting
+/// progress on standard error.
+pub async fn import(
+    state: &AppState,
+    format: &str,
+    input: impl BufRead + Send + 'static,
+    dry_run: bool,
+) -> Result<ImportReport, AppError> {
+    let importer = state
+        .importers
+        .get(format)
+        .ok_or_else(|| AppError::BadRequest(format!("Unknown import format {format:?}")))?;
+
+    import_service::import(state, Actor::named(CLI_ACTOR), importer, Box::new(input), dry_run, &mut |created, total| {
+        if created.is_multiple_of(100) || created == total {
+            eprintln!("Imported {created}/{total}");
+        }
+    })
+    .await
+}
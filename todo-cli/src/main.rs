@@ -0,0 +1,107 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::sync::Arc;
+
+use clap::Parser;
+use todo_cli::{Cli, Command};
+use todo_server::config::AppConfig;
+use todo_server::db::{init_db, init_scratch_db, run_migrations};
+use todo_server::modules::admin::admin_service;
+use todo_server::modules::attachments::attachment_storage::InMemoryStorage;
+use todo_server::modules::exports::export_service;
+use todo_server::modules::exports::exporter::ExporterRegistry;
+use todo_server::smoke;
+use todo_server::telemetry;
+use todo_server::AppBuilder;
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    let config = AppConfig::from_env();
+    telemetry::init(&config);
+
+    // The smoke runs without opening DATABASE_URL
+    match cli.command {
+        Command::Smoke { url } => smoke(config, url).await,
+        Command::Migrate => {
+            let pool = open_db(&config).await;
+            run_migrations(&pool).await.expect("Failed to run migrations");
+            println!("Database is up to date");
+        }
+        Command::Seed { count } => {
+            let state = AppBuilder::new(open_db(&config).await)
+                .with_config(config)
+                .build_state()
+                .await
+                .expect("Failed to build the app");
+            let items = todo_cli::seed(&state, count).await.expect("Failed to seed items");
+            println!("Created {} items", items.len());
+        }
+        Command::Check { repair } => {
+            let state = AppBuilder::new(open_db(&config).await)
+                .with_config(config)
+                .build_state()
+                .await
+                .expect("Failed to build the app");
+            let report = admin_service::check_consistency(&state.writer, state.storage.as_ref(), repair)
+                .await
+                .expect("Failed to check consistency");
+            println!("{}", serde_json::to_string_pretty(&report).expect("Failed to write the report"));
+            if !report.is_consistent() && !repair {
+                std::process::exit(1);
+            }
+        }
+        Command::Import { format, dry_run, file } => {
+            let input: Box<dyn BufRead + Send> = match file {
+                Some(path) => Box::new(BufReader::new(File::open(path).expect("Failed to open the import file"))),
+                None => Box::new(BufReader::new(std::io::stdin())),
+            };
+            let state = AppBuilder::new(open_db(&config).await)
+                .with_config(config)
+                .build_state()
+                .await
+                .expect("Failed to build the app");
+            let report = todo_cli::import(&state, &format, input, dry_run).await.expect("Failed to import items");
+            println!("{}", serde_json::to_string_pretty(&report).expect("Failed to write the report"));
+            if !report.errors.is_empty() {
+                std::process::exit(1);
+            }
+        }
+        Command::Export { format } => {
+            // Clap only accepts registered names
+            let exporter = ExporterRegistry::default().get(&format).expect("Unknown export format");
+            let pool = open_db(&config).await;
+            let items = export_service::all_items(&pool).await.expect("Failed to export items");
+            exporter.write(&items, &mut std::io::stdout().lock()).expect("Failed to write the export");
+        }
+    }
+}
+
+async fn open_db(config: &AppConfig) -> sqlx::SqlitePool {
+    init_db(config).await.expect("Failed to initialize the database")
+}
+
+async fn smoke(config: AppConfig, url: Option<String>) {
+    // Without a deployment to check, boot one that touches no real data
+    let url = match url {
+        Some(url) => url,
+        None => {
+            let pool = init_scratch_db().await.expect("Failed to set up a scratch database");
+            let app = AppBuilder::new(pool)
+                .with_config(config)
+                .with_storage(Arc::new(InMemoryStorage::default()))
+                .build()
+                .await
+                .expect("Failed to build the app");
+            smoke::boot(app).await.expect("Failed to start the app")
+        }
+    };
+
+    match smoke::run(&url, &mut |step| println!("ok {step}")).await {
+        Ok(()) => println!("Smoke test passed against {url}"),
+        Err(failure) => {
+            eprintln!("{failure}");
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,104 @@
+mod common;
+
+use todo_cli::{Cli, Command};
+use todo_server::modules::exports::export_service;
+use todo_server::modules::exports::exporter::{Exporter, JsonExporter};
+use clap::Parser;
+use serde_json::Value;
+
+use common::setup_state;
+
+#[test]
+fn cli_needs_a_task() {
+    assert!(Cli::try_parse_from(["todo-cli"]).is_err());
+    assert!(Cli::try_parse_from(["todo-cli", "serve"]).is_err());
+}
+
+#[test]
+fn cli_parses_task_arguments() {
+    let cli = Cli::try_parse_from(["todo-cli", "seed", "--count", "3"]).unwrap();
+    assert_eq!(cli.command, Command::Seed { count: 3 });
+
+    let cli = Cli::try_parse_from(["todo-cli", "seed"]).unwrap();
+    assert_eq!(cli.command, Command::Seed { count: 10 });
+
+    let cli = Cli::try_parse_from(["todo-cli", "export", "--format", "csv"]).unwrap();
+    assert_eq!(cli.command, Command::Export { format: "csv".to_string() });
+
+    let cli = Cli::try_parse_from(["todo-cli", "export"]).unwrap();
+    assert_eq!(cli.command, Command::Export { format: "json".to_string() });
+
+    assert!(Cli::try_parse_from(["todo-cli", "export", "--format", "xml"]).is_err());
+
+    let cli = Cli::try_parse_from(["todo-cli", "smoke"]).unwrap();
+    assert_eq!(cli.command, Command::Smoke { url: None });
+
+    let cli = Cli::try_parse_from(["todo-cli", "smoke", "--url", "http://todo.internal"]).unwrap();
+    assert_eq!(cli.command, Command::Smoke { url: Some("http://todo.internal".to_string()) });
+
+    let cli = Cli::try_parse_from(["todo-cli", "import", "--format", "csv", "--dry-run", "items.csv"]).unwrap();
+    assert_eq!(
+        cli.command,
+        Command::Import { format: "csv".to_string(), dry_run: true, file: Some("items.csv".into()) }
+    );
+    assert!(Cli::try_parse_from(["todo-cli", "import", "--format", "xlsx"]).is_err());
+    assert!(Cli::try_parse_from(["todo-cli", "seed", "--count", "-1"]).is_err());
+}
+
+#[test]
+fn check_subcommand_takes_a_repair_flag() {
+    assert_eq!(Cli::try_parse_from(["todo-cli", "check"]).unwrap().command, Command::Check { repair: false });
+    assert_eq!(Cli::try_parse_from(["todo-cli", "check", "--repair"]).unwrap().command, Command::Check { repair: true });
+}
+
+#[tokio::test]
+async fn seed_numbers_items_after_existing_ones_and_records_the_cli_actor() {
+    let state = setup_state().await;
+
+    let first = todo_cli::seed(&state, 2).await.unwrap();
+    let second = todo_cli::seed(&state, 1).await.unwrap();
+    let names: Vec<_> = first.iter().chain(&second).map(|item| item.name.as_str()).collect();
+    assert_eq!(names, ["Sample item 1", "Sample item 2", "Sample item 3"]);
+
+    let (actor, request_id): (String, Option<String>) = sqlx::query_as("SELECT actor, request_id FROM audit_log LIMIT 1")
+        .fetch_one(&state.pool)
+        .await
+        .unwrap();
+    assert_eq!(actor, todo_cli::CLI_ACTOR);
+    assert_eq!(request_id, None);
+}
+
+#[tokio::test]
+async fn export_writes_every_item_as_json() {
+    let state = setup_state().await;
+    // More than one page, to check the export follows the cursor.
+    todo_cli::seed(&state, 205).await.unwrap();
+
+    let items = export_service::all_items(&state.pool).await.unwrap();
+    assert_eq!(items.len(), 205);
+
+    let mut out = Vec::new();
+    JsonExporter.write(&items, &mut out).unwrap();
+    let exported: Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(exported.as_array().unwrap().len(), 205);
+    assert_eq!(exported[0]["name"], "Sample item 1");
+    assert_eq!(exported[204]["name"], "Sample item 205");
+}
+
+#[tokio::test]
+async fn import_creates_items_as_the_cli_actor() {
+    let state = setup_state().await;
+
+    let report = todo_cli::import(&state, "todotxt", &b"(A) First\nSecond\n"[..], false).await.unwrap();
+    assert_eq!(report.created, 2);
+
+    let items = export_service::all_items(&state.pool).await.unwrap();
+    let names: Vec<_> = items.iter().map(|item| item.name.as_str()).collect();
+    assert_eq!(names, ["First", "Second"]);
+
+    let (actor,): (String,) = sqlx::query_as("SELECT actor FROM audit_log LIMIT 1")
+        .fetch_one(&state.pool)
+        .await
+        .unwrap();
+    assert_eq!(actor, todo_cli::CLI_ACTOR);
+}
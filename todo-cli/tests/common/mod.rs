@@ -0,0 +1,19 @@
+use std::sync::Arc;
+
+use chrono::{TimeZone, Utc};
+use todo_server::clock::FixedClock;
+use todo_server::db::init_scratch_db;
+use todo_server::id_generator::SequentialIdGenerator;
+use todo_server::modules::attachments::attachment_storage::InMemoryStorage;
+use todo_server::state::AppState;
+
+/// App state over a fresh in-memory database, with a fixed clock, sequential
+/// ids and in-memory attachment storage.
+pub async fn setup_state() -> AppState {
+    let pool = init_scratch_db().await.expect("Failed to open in-memory database");
+
+    AppState::new(pool)
+        .with_clock(Arc::new(FixedClock::new(Utc.with_ymd_and_hms(2024, 6, 24, 8, 0, 0).unwrap())))
+        .with_ids(Arc::new(SequentialIdGenerator::default()))
+        .with_storage(Arc::new(InMemoryStorage::default()))
+}
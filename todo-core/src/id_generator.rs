@@ -0,0 +1,40 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use uuid::Uuid;
+
+/// Source of new entity ids.
+pub trait IdGenerator: Send + Sync {
+    fn next_id(&self) -> String;
+}
+
+/// Random v4 UUIDs.
+pub struct UuidV4Generator;
+
+impl IdGenerator for UuidV4Generator {
+    fn next_id(&self) -> String {
+        Uuid::new_v4().to_string()
+    }
+}
+
+/// Time-ordered v7 UUIDs. Ids sort by creation time, so new rows land at the
+/// end of primary key indexes instead of at random places in them.
+pub struct UuidV7Generator;
+
+impl IdGenerator for UuidV7Generator {
+    fn next_id(&self) -> String {
+        Uuid::now_v7().to_string()
+    }
+}
+
+/// UUID-shaped ids counting up from 1: `00000000-0000-0000-0000-000000000001`, ...
+#[derive(Default)]
+pub struct SequentialIdGenerator {
+    last: AtomicU64,
+}
+
+impl IdGenerator for SequentialIdGenerator {
+    fn next_id(&self) -> String {
+        let next = self.last.fetch_add(1, Ordering::Relaxed) + 1;
+        Uuid::from_u128(u128::from(next)).to_string()
+    }
+}
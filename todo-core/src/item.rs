@@ -0,0 +1,159 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct Item {
+    pub id: String,
+    pub name: String,
+    /// `None` when the item has no description. Stored as `''` because the
+    /// column predates optional descriptions and is `NOT NULL`.
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// When the item itself last changed, including its place in the manual
+    /// ordering. Changes to its reminders, attachments or comments don't count.
+    pub updated_at: DateTime<Utc>,
+    pub priority: Priority,
+    /// Place in the manual ordering, starting at 0. Deleted items leave gaps
+    /// behind. Changed through `PATCH /items/:id/move`.
+    pub position: i64,
+    /// The item this one is a subtask of.
+    pub parent_id: Option<String>,
+}
+
+impl Item {
+    /// A new item made from `dto`, created at `now`. Its position is given
+    /// when it is stored.
+    pub fn new(id: String, now: DateTime<Utc>, dto: CreateItemDto) -> Self {
+        Self {
+            id,
+            name: dto.name,
+            description: dto.description.filter(|description| !description.is_empty()),
+            created_at: now,
+            updated_at: now,
+            priority: dto.priority,
+            position: 0,
+            parent_id: dto.parent_id,
+        }
+    }
+
+    /// This item with the fields `dto` sets changed at `now`. An empty
+    /// description clears it.
+    pub fn updated(&self, now: DateTime<Utc>, dto: UpdateItemDto) -> Self {
+        Self {
+            name: dto.name.unwrap_or_else(|| self.name.clone()),
+            description: dto.description
+                .apply(self.description.clone())
+                .filter(|description| !description.is_empty()),
+            priority: dto.priority.unwrap_or(self.priority),
+            parent_id: dto.parent_id.apply(self.parent_id.clone()),
+            updated_at: now,
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "sqlx", sqlx(rename_all = "lowercase"))]
+pub enum Priority {
+    Low,
+    #[default]
+    Medium,
+    High,
+}
+
+impl Priority {
+    /// Sort key putting the most urgent items first. Matches the `CASE`
+    /// expression used when listing by priority.
+    pub fn rank(self) -> i64 {
+        match self {
+            Priority::High => 0,
+            Priority::Medium => 1,
+            Priority::Low => 2,
+        }
+    }
+
+    /// Name of the priority, as it is serialized and stored.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Priority::Low => "low",
+            Priority::Medium => "medium",
+            Priority::High => "high",
+        }
+    }
+}
+
+impl FromStr for Priority {
+    type Err = String;
+
+    /// Parses a name given by [`Priority::as_str`].
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "low" => Ok(Priority::Low),
+            "medium" => Ok(Priority::Medium),
+            "high" => Ok(Priority::High),
+            _ => Err(format!("Unknown priority {value:?}")),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CreateItemDto {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub priority: Priority,
+    /// Makes the new item a subtask of this one.
+    #[serde(default)]
+    pub parent_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct UpdateItemDto {
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Patch<String>,
+    pub priority: Option<Priority>,
+    /// `null` turns a subtask back into a top-level item.
+    #[serde(default)]
+    pub parent_id: Patch<String>,
+}
+
+/// A field of a partial update that can also be cleared.
+///
+/// Tells an absent field (leave it alone) apart from an explicit `null`
+/// (clear it), which a plain `Option` cannot. Fields of this type need
+/// `#[serde(default)]` so absence deserializes to [`Patch::Missing`].
+#[derive(Serialize, Default, Clone, Debug, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum Patch<T> {
+    #[default]
+    Missing,
+    Null,
+    Value(T),
+}
+
+impl<T> Patch<T> {
+    /// The field's new value, given its current one.
+    pub fn apply(self, current: Option<T>) -> Option<T> {
+        match self {
+            Patch::Missing => current,
+            Patch::Null => None,
+            Patch::Value(value) => Some(value),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Patch<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match Option::<T>::deserialize(deserializer)? {
+            Some(value) => Patch::Value(value),
+            None => Patch::Null,
+        })
+    }
+}
@@ -0,0 +1,90 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use chrono::{DateTime, Utc};
+
+use crate::id_generator::IdGenerator;
+use crate::item::{CreateItemDto, Item, UpdateItemDto};
+use crate::repository::ItemRepository;
+
+/// Why an item operation failed.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ItemError<E> {
+    NotFound,
+    Repository(E),
+}
+
+impl<E: fmt::Display> fmt::Display for ItemError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ItemError::NotFound => write!(f, "Item not found"),
+            ItemError::Repository(err) => err.fmt(f),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for ItemError<E> {}
+
+pub async fn get_item<R: ItemRepository>(repo: &R, id: &str) -> Result<Item, ItemError<R::Error>> {
+    repo.get(id).await.map_err(ItemError::Repository)?.ok_or(ItemError::NotFound)
+}
+
+/// New items go to the end of the manual ordering.
+pub async fn create_item<R: ItemRepository>(repo: &R, ids: &dyn IdGenerator, now: DateTime<Utc>, dto: CreateItemDto) -> Result<Item, R::Error> {
+    repo.insert(Item::new(ids.next_id(), now, dto)).await
+}
+
+/// Creates every item of `dtos`, in order, so that either all of them are
+/// created or none are. `progress` is told how many have been created after
+/// each one.
+pub async fn create_items<R: ItemRepository>(
+    repo: &R,
+    ids: &dyn IdGenerator,
+    now: DateTime<Utc>,
+    dtos: Vec<CreateItemDto>,
+    progress: &mut (dyn FnMut(usize) + Send),
+) -> Result<Vec<Item>, R::Error> {
+    let items = dtos.into_iter().map(|dto| Item::new(ids.next_id(), now, dto)).collect();
+
+    repo.insert_all(items, progress).await
+}
+
+pub async fn update_item<R: ItemRepository>(repo: &R, now: DateTime<Utc>, id: &str, dto: UpdateItemDto) -> Result<Item, ItemError<R::Error>> {
+    repo.update(id, Box::new(move |item| item.updated(now, dto)))
+        .await
+        .map_err(ItemError::Repository)?
+        .ok_or(ItemError::NotFound)
+}
+
+/// Moves an item to index `position` of the manual ordering, or to the end
+/// when `position` is past it. The item, and the items it shifts, count as
+/// updated at `now` unless it stays where it is.
+pub async fn move_item<R: ItemRepository>(repo: &R, now: DateTime<Utc>, id: &str, position: i64) -> Result<Item, ItemError<R::Error>> {
+    let place = move |item: &Item, position: i64| match position == item.position {
+        true => item.clone(),
+        false => Item { position, updated_at: now, ..item.clone() },
+    };
+
+    repo.move_to(id, position.max(0), Box::new(place))
+        .await
+        .map_err(ItemError::Repository)?
+        .ok_or(ItemError::NotFound)
+}
+
+/// Deleting an item that does not exist is a no-op. Its subtasks go with it.
+pub async fn delete_item<R: ItemRepository>(repo: &R, id: &str) -> Result<(), R::Error> {
+    repo.delete(id).await?;
+
+    Ok(())
+}
+
+/// The positions of the items shifted by a move from position `from` to
+/// `to`, and by how much; `None` when the item stays put.
+pub fn shift(from: i64, to: i64) -> Option<(RangeInclusive<i64>, i64)> {
+    match from.cmp(&to) {
+        Ordering::Less => Some((from + 1..=to, -1)),
+        Ordering::Greater => Some((to..=from - 1, 1)),
+        Ordering::Equal => None,
+    }
+}
@@ -0,0 +1,13 @@
+//! The to-do domain: items, the rules for changing them and the
+//! [`ItemRepository`](repository::ItemRepository) they are kept in.
+//!
+//! Nothing here knows about HTTP or SQL. The server keeps items in SQLite;
+//! other binaries and tests can run the same rules over
+//! [`InMemoryItemRepository`](memory::InMemoryItemRepository).
+
+pub mod clock;
+pub mod id_generator;
+pub mod item;
+pub mod item_service;
+pub mod memory;
+pub mod repository;
@@ -0,0 +1,111 @@
+use std::convert::Infallible;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+
+use crate::item::Item;
+use crate::item_service;
+use crate::repository::{Change, ItemRepository, Placement};
+
+/// Keeps items in memory, for tests and throwaway runs. Keeps no history.
+#[derive(Default)]
+pub struct InMemoryItemRepository {
+    items: Mutex<Vec<Item>>,
+}
+
+impl InMemoryItemRepository {
+    /// Every item, in manual order.
+    pub fn items(&self) -> Vec<Item> {
+        manual_order(&self.items.lock().unwrap())
+    }
+}
+
+#[async_trait]
+impl ItemRepository for InMemoryItemRepository {
+    type Error = Infallible;
+
+    async fn get(&self, id: &str) -> Result<Option<Item>, Infallible> {
+        Ok(self.items.lock().unwrap().iter().find(|item| item.id == id).cloned())
+    }
+
+    async fn insert(&self, mut item: Item) -> Result<Item, Infallible> {
+        let mut items = self.items.lock().unwrap();
+        item.position = items.iter().map(|item| item.position + 1).max().unwrap_or(0);
+        items.push(item.clone());
+
+        Ok(item)
+    }
+
+    async fn insert_all(&self, items: Vec<Item>, progress: &mut (dyn FnMut(usize) + Send)) -> Result<Vec<Item>, Infallible> {
+        let mut stored = Vec::with_capacity(items.len());
+        for item in items {
+            stored.push(self.insert(item).await?);
+            progress(stored.len());
+        }
+
+        Ok(stored)
+    }
+
+    async fn update(&self, id: &str, change: Change<'_>) -> Result<Option<Item>, Infallible> {
+        let mut items = self.items.lock().unwrap();
+        let Some(item) = items.iter_mut().find(|item| item.id == id) else {
+            return Ok(None);
+        };
+        *item = change(item);
+
+        Ok(Some(item.clone()))
+    }
+
+    async fn move_to(&self, id: &str, to: i64, place: Placement<'_>) -> Result<Option<Item>, Infallible> {
+        let mut items = self.items.lock().unwrap();
+        let order = manual_order(&items);
+        let Some(item) = order.iter().find(|item| item.id == id) else {
+            return Ok(None);
+        };
+        let target = usize::try_from(to).ok().and_then(|to| order.get(to)).or(order.last()).map_or(0, |item| item.position);
+        let moved = place(item, target);
+
+        let shift = item_service::shift(item.position, target);
+        for other in items.iter_mut() {
+            match &shift {
+                _ if other.id == id => *other = moved.clone(),
+                Some((range, by)) if range.contains(&other.position) => {
+                    other.position += by;
+                    other.updated_at = moved.updated_at;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Some(moved))
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, Infallible> {
+        let mut items = self.items.lock().unwrap();
+        if !items.iter().any(|item| item.id == id) {
+            return Ok(false);
+        }
+
+        let mut doomed = vec![id.to_string()];
+        let mut next = 0;
+        while next < doomed.len() {
+            let parent_id = doomed[next].clone();
+            doomed.extend(
+                items
+                    .iter()
+                    .filter(|item| item.parent_id.as_ref() == Some(&parent_id))
+                    .map(|item| item.id.clone()),
+            );
+            next += 1;
+        }
+        items.retain(|item| !doomed.contains(&item.id));
+
+        Ok(true)
+    }
+}
+
+fn manual_order(items: &[Item]) -> Vec<Item> {
+    let mut items = items.to_vec();
+    items.sort_by(|a, b| (a.position, &a.id).cmp(&(b.position, &b.id)));
+    items
+}
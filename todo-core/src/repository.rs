@@ -0,0 +1,48 @@
+use async_trait::async_trait;
+
+use crate::item::Item;
+
+/// Turns an item into its changed version.
+pub type Change<'a> = Box<dyn FnOnce(&Item) -> Item + Send + 'a>;
+
+/// Turns an item into its moved version, given the position it moves to.
+pub type Placement<'a> = Box<dyn FnOnce(&Item, i64) -> Item + Send + 'a>;
+
+/// Where items are kept.
+///
+/// The [`item_service`](crate::item_service) decides what a change does; a
+/// repository stores it, each call atomically. A repository that keeps a
+/// history of changes records them here too, with its own idea of who made
+/// them.
+#[async_trait]
+pub trait ItemRepository: Send + Sync {
+    type Error: Send;
+
+    /// The item with `id`, if there is one.
+    async fn get(&self, id: &str) -> Result<Option<Item>, Self::Error>;
+
+    /// Stores a new item at the end of the manual ordering and returns it
+    /// with the position it was given.
+    async fn insert(&self, item: Item) -> Result<Item, Self::Error>;
+
+    /// Stores new items in order, like [`insert`](Self::insert) each, but
+    /// either all of them or none. `progress` is told how many have been
+    /// stored after each one.
+    async fn insert_all(&self, items: Vec<Item>, progress: &mut (dyn FnMut(usize) + Send)) -> Result<Vec<Item>, Self::Error>;
+
+    /// Replaces the item with `id` by `change` applied to it and returns the
+    /// result, or `None` when there is no such item.
+    async fn update(&self, id: &str, change: Change<'_>) -> Result<Option<Item>, Self::Error>;
+
+    /// Moves the item with `id` to index `to` of the manual ordering, or to
+    /// the end when `to` is past it. The item takes the position of the item
+    /// at that index, is stored as `place` makes it, and the items in between
+    /// shift as [`shift`](crate::item_service::shift) says, taking the moved
+    /// item's `updated_at`. Returns the moved item, or `None` when there is
+    /// no such item.
+    async fn move_to(&self, id: &str, to: i64, place: Placement<'_>) -> Result<Option<Item>, Self::Error>;
+
+    /// Deletes the item with `id` and its subtasks at any depth, along with
+    /// anything attached to them. Returns whether there was such an item.
+    async fn delete(&self, id: &str) -> Result<bool, Self::Error>;
+}
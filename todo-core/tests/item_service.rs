@@ -0,0 +1,139 @@
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+use todo_core::id_generator::SequentialIdGenerator;
+use todo_core::item::{CreateItemDto, Patch, Priority, UpdateItemDto};
+use todo_core::item_service::{self, ItemError};
+use todo_core::memory::InMemoryItemRepository;
+
+fn start_time() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 6, 24, 8, 0, 0).unwrap()
+}
+
+fn new_item(name: &str, parent_id: Option<&str>) -> CreateItemDto {
+    CreateItemDto {
+        name: name.to_string(),
+        description: Some(String::new()),
+        priority: Priority::default(),
+        parent_id: parent_id.map(str::to_string),
+    }
+}
+
+fn names(repo: &InMemoryItemRepository) -> Vec<(String, i64)> {
+    repo.items().into_iter().map(|item| (item.name, item.position)).collect()
+}
+
+#[tokio::test]
+async fn new_items_go_to_the_end_without_an_empty_description() {
+    let repo = InMemoryItemRepository::default();
+    let ids = SequentialIdGenerator::default();
+
+    let first = item_service::create_item(&repo, &ids, start_time(), new_item("First", None)).await.unwrap();
+    let second = item_service::create_item(&repo, &ids, start_time(), new_item("Second", None)).await.unwrap();
+
+    assert_eq!((first.position, second.position), (0, 1));
+    assert_eq!(first.description, None);
+    assert_eq!(first.created_at, start_time());
+    assert_eq!(item_service::get_item(&repo, &second.id).await.unwrap().name, "Second");
+    assert_eq!(item_service::get_item(&repo, "missing").await.unwrap_err(), ItemError::NotFound);
+}
+
+#[tokio::test]
+async fn items_created_together_keep_their_order() {
+    let repo = InMemoryItemRepository::default();
+    let ids = SequentialIdGenerator::default();
+    let mut created = Vec::new();
+
+    let items = item_service::create_items(&repo, &ids, start_time(), vec![new_item("First", None), new_item("Second", None)], &mut |count| created.push(count))
+        .await
+        .unwrap();
+
+    assert_eq!(items.iter().map(|item| item.position).collect::<Vec<_>>(), [0, 1]);
+    assert_eq!(names(&repo), [("First".to_string(), 0), ("Second".to_string(), 1)]);
+    assert_eq!(created, [1, 2]);
+}
+
+#[tokio::test]
+async fn updates_change_only_the_given_fields() {
+    let repo = InMemoryItemRepository::default();
+    let ids = SequentialIdGenerator::default();
+    let parent = item_service::create_item(&repo, &ids, start_time(), new_item("Parent", None)).await.unwrap();
+    let item = item_service::create_item(&repo, &ids, start_time(), CreateItemDto {
+        description: Some("Keep me".to_string()),
+        ..new_item("Child", Some(&parent.id))
+    })
+    .await
+    .unwrap();
+
+    let later = start_time() + TimeDelta::minutes(5);
+    let updated = item_service::update_item(&repo, later, &item.id, UpdateItemDto {
+        name: None,
+        description: Patch::Missing,
+        priority: Some(Priority::High),
+        parent_id: Patch::Null,
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(updated.name, "Child");
+    assert_eq!(updated.description.as_deref(), Some("Keep me"));
+    assert_eq!(updated.priority, Priority::High);
+    assert_eq!(updated.parent_id, None);
+    assert_eq!((updated.created_at, updated.updated_at), (start_time(), later));
+
+    let missing = UpdateItemDto { name: None, description: Patch::Missing, priority: None, parent_id: Patch::Missing };
+    assert_eq!(item_service::update_item(&repo, later, "missing", missing).await.unwrap_err(), ItemError::NotFound);
+}
+
+#[tokio::test]
+async fn moves_renumber_only_the_shifted_items() {
+    let repo = InMemoryItemRepository::default();
+    let ids = SequentialIdGenerator::default();
+    let mut created = Vec::new();
+    for name in ["A", "B", "C", "D"] {
+        created.push(item_service::create_item(&repo, &ids, start_time(), new_item(name, None)).await.unwrap());
+    }
+
+    let later = start_time() + TimeDelta::minutes(5);
+    let moved = item_service::move_item(&repo, later, &created[3].id, 1).await.unwrap();
+    assert_eq!(moved.position, 1);
+    assert_eq!(names(&repo), [("A".to_string(), 0), ("D".to_string(), 1), ("B".to_string(), 2), ("C".to_string(), 3)]);
+
+    let unchanged: Vec<_> = repo.items().into_iter().filter(|item| item.updated_at == start_time()).map(|item| item.name).collect();
+    assert_eq!(unchanged, ["A"]);
+
+    // Past the end means the end.
+    item_service::move_item(&repo, later, &created[0].id, 99).await.unwrap();
+    assert_eq!(names(&repo).last().unwrap(), &("A".to_string(), 3));
+    assert_eq!(item_service::move_item(&repo, later, "missing", 0).await.unwrap_err(), ItemError::NotFound);
+}
+
+#[tokio::test]
+async fn moves_skip_the_gaps_left_by_deletes() {
+    let repo = InMemoryItemRepository::default();
+    let ids = SequentialIdGenerator::default();
+    let mut created = Vec::new();
+    for name in ["A", "B", "C", "D"] {
+        created.push(item_service::create_item(&repo, &ids, start_time(), new_item(name, None)).await.unwrap());
+    }
+    item_service::delete_item(&repo, &created[1].id).await.unwrap();
+
+    // Index 1 is C, at position 2.
+    let moved = item_service::move_item(&repo, start_time(), &created[3].id, 1).await.unwrap();
+    assert_eq!(moved.position, 2);
+    assert_eq!(names(&repo), [("A".to_string(), 0), ("D".to_string(), 2), ("C".to_string(), 3)]);
+}
+
+#[tokio::test]
+async fn deleting_an_item_takes_its_subtasks_along() {
+    let repo = InMemoryItemRepository::default();
+    let ids = SequentialIdGenerator::default();
+    let parent = item_service::create_item(&repo, &ids, start_time(), new_item("Parent", None)).await.unwrap();
+    let child = item_service::create_item(&repo, &ids, start_time(), new_item("Child", Some(&parent.id))).await.unwrap();
+    item_service::create_item(&repo, &ids, start_time(), new_item("Grandchild", Some(&child.id))).await.unwrap();
+    item_service::create_item(&repo, &ids, start_time(), new_item("Other", None)).await.unwrap();
+
+    item_service::delete_item(&repo, &parent.id).await.unwrap();
+    assert_eq!(names(&repo), [("Other".to_string(), 3)]);
+
+    // Deleting again is a no-op.
+    item_service::delete_item(&repo, &parent.id).await.unwrap();
+}
@@ -0,0 +1,13 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS reminders (
+     id TEXT PRIMARY KEY,
+     item_id TEXT NOT NULL REFERENCES items (id) ON DELETE CASCADE,
+     remind_at TEXT NOT NULL,
+     webhook_url TEXT NOT NULL,
+     attempts INTEGER NOT NULL DEFAULT 0,
+     next_attempt_at TEXT NOT NULL,
+     last_error TEXT,
+     delivered_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_reminders_pending ON reminders (next_attempt_at) WHERE delivered_at IS NULL;
@@ -0,0 +1,12 @@
+-- Add migration script here
+ALTER TABLE items ADD COLUMN created_at TEXT NOT NULL DEFAULT '1970-01-01T00:00:00+00:00';
+
+-- Existing items get made-up times one second apart in insertion (rowid)
+-- order, the newest now, so keyset pagination keeps them in that order
+UPDATE items SET created_at = strftime(
+     '%Y-%m-%dT%H:%M:%S+00:00', 'now',
+     '-' || ((SELECT MAX(rowid) FROM items) - rowid) || ' seconds'
+);
+
+-- Keyset pagination walks items in (created_at, id) order
+CREATE INDEX IF NOT EXISTS idx_items_created_at_id ON items (created_at, id);
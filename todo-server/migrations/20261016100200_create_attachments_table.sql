@@ -0,0 +1,11 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS attachments (
+     id TEXT PRIMARY KEY,
+     item_id TEXT NOT NULL REFERENCES items (id) ON DELETE CASCADE,
+     file_name TEXT NOT NULL,
+     content_type TEXT NOT NULL,
+     size_bytes INTEGER NOT NULL,
+     created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_attachments_item_id ON attachments (item_id);
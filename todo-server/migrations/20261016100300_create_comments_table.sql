@@ -0,0 +1,10 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS comments (
+     id TEXT PRIMARY KEY,
+     item_id TEXT NOT NULL REFERENCES items (id) ON DELETE CASCADE,
+     author TEXT NOT NULL,
+     body TEXT NOT NULL,
+     created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_comments_item_id_created_at_id ON comments (item_id, created_at, id);
@@ -0,0 +1,11 @@
+-- Add migration script here
+ALTER TABLE items ADD COLUMN priority TEXT NOT NULL DEFAULT 'medium' CHECK (priority IN ('low', 'medium', 'high'));
+
+ALTER TABLE items ADD COLUMN position INTEGER NOT NULL DEFAULT 0;
+
+-- Existing items keep their insertion order
+UPDATE items SET position = (
+     SELECT COUNT(*) FROM items AS other WHERE other.rowid < items.rowid
+);
+
+CREATE INDEX IF NOT EXISTS idx_items_position_id ON items (position, id);
@@ -0,0 +1,14 @@
+-- Add migration script here
+-- No foreign key to items: history outlives the item it describes.
+CREATE TABLE IF NOT EXISTS audit_log (
+     id TEXT PRIMARY KEY,
+     entity_type TEXT NOT NULL,
+     entity_id TEXT NOT NULL,
+     action TEXT NOT NULL CHECK (action IN ('create', 'update', 'move', 'delete')),
+     actor TEXT NOT NULL,
+     before TEXT,
+     after TEXT,
+     created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_entity_created_at_id ON audit_log (entity_type, entity_id, created_at, id);
@@ -0,0 +1,20 @@
+-- Add migration script here
+ALTER TABLE items ADD COLUMN parent_id TEXT REFERENCES items (id) ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS idx_items_parent_id_position_id ON items (parent_id, position, id);
+
+-- An item cannot become a subtask of itself or of one of its own subtasks
+CREATE TRIGGER IF NOT EXISTS items_no_parent_cycle
+BEFORE UPDATE OF parent_id ON items
+WHEN NEW.parent_id IS NOT NULL AND NEW.id IN (
+     WITH RECURSIVE ancestors (id) AS (
+          SELECT NEW.parent_id
+          UNION
+          SELECT items.parent_id FROM items JOIN ancestors ON items.id = ancestors.id
+          WHERE items.parent_id IS NOT NULL
+     )
+     SELECT id FROM ancestors
+)
+BEGIN
+     SELECT RAISE(ABORT, 'An item cannot be moved under one of its own subtasks');
+END;
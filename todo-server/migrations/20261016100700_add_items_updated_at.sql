@@ -0,0 +1,6 @@
+-- Add migration script here
+ALTER TABLE items ADD COLUMN updated_at TEXT NOT NULL DEFAULT '1970-01-01T00:00:00+00:00';
+
+-- Existing items were last changed no later than when they were created, as
+-- far as anyone can tell
+UPDATE items SET updated_at = created_at;
@@ -0,0 +1,3 @@
+-- Add migration script here
+-- Entries written before this column existed can't be tied to a request
+ALTER TABLE audit_log ADD COLUMN request_id TEXT;
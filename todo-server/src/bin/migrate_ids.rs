@@ -0,0 +1,14 @@
+//! Rewrites existing item ids to time-ordered v7 UUIDs. See
+//! [`todo_server::id_migration`].
+
+use todo_server::config::AppConfig;
+use todo_server::db::init_db;
+use todo_server::id_migration::migrate_item_ids;
+
+#[tokio::main]
+async fn main() {
+    let pool = init_db(&AppConfig::from_env()).await.expect("Failed to initialize the database");
+    let migrated = migrate_item_ids(&pool).await.expect("Failed to migrate item ids");
+
+    println!("Migrated {migrated} item ids to UUIDv7");
+}
@@ -0,0 +1,189 @@
+use std::sync::Arc;
+
+use axum::Router;
+use sqlx::migrate::MigrateError;
+use sqlx::SqlitePool;
+use crate::clock::Clock;
+use crate::config::AppConfig;
+use crate::db;
+use crate::db_maintenance::{self, MaintenanceConfig};
+use crate::id_generator::IdGenerator;
+use crate::modules::actions::action_registry::{Action, ActionRegistry};
+use crate::modules::attachments::attachment_storage::{AttachmentStorage, LocalDiskStorage};
+use crate::modules::exports::exporter::{Exporter, ExporterRegistry};
+use crate::modules::imports::importer::{Importer, ImporterRegistry};
+use crate::modules::reminders::reminder_worker::{self, ReminderWorkerConfig};
+use crate::modules::todos::todo_batcher::{ItemBatcher, ItemBatcherConfig};
+use crate::modules::todos::todo_hooks::ItemHooks;
+use crate::state::AppState;
+use crate::create_app_with_state;
+
+/// Wires the app up for use as a library.
+///
+/// ```no_run
+/// # async fn embed(pool: sqlx::SqlitePool) {
+/// use todo_server::AppBuilder;
+///
+/// let todos = AppBuilder::new(pool).with_migrations().build().await.unwrap();
+/// let app = axum::Router::new().nest("/todos", todos);
+/// # }
+/// ```
+///
+/// Unless overridden, ids follow `config.id_scheme`, attachments are written
+/// to `config.attachments_dir` and item inserts are batched when
+/// `config.ingest_batching` is set. Background workers only run when asked
+/// for, so an embedding app stays in control of what it spawns.
+pub struct AppBuilder {
+    pool: SqlitePool,
+    writer: Option<SqlitePool>,
+    config: AppConfig,
+    clock: Option<Arc<dyn Clock>>,
+    ids: Option<Arc<dyn IdGenerator>>,
+    storage: Option<Arc<dyn AttachmentStorage>>,
+    exporters: ExporterRegistry,
+    importers: ImporterRegistry,
+    actions: ActionRegistry,
+    item_hooks: Option<ItemHooks>,
+    run_migrations: bool,
+    reminder_worker: Option<ReminderWorkerConfig>,
+    maintenance: Option<MaintenanceConfig>,
+}
+
+impl AppBuilder {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            pool,
+            writer: None,
+            config: AppConfig::default(),
+            clock: None,
+            ids: None,
+            storage: None,
+            exporters: ExporterRegistry::default(),
+            importers: ImporterRegistry::default(),
+            actions: ActionRegistry::default(),
+            item_hooks: None,
+            run_migrations: false,
+            reminder_worker: None,
+            maintenance: None,
+        }
+    }
+
+    pub fn with_config(mut self, config: AppConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Sends writes through `writer` instead of the main pool.
+    pub fn with_writer_pool(mut self, writer: SqlitePool) -> Self {
+        self.writer = Some(writer);
+        self
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    pub fn with_ids(mut self, ids: Arc<dyn IdGenerator>) -> Self {
+        self.ids = Some(ids);
+        self
+    }
+
+    pub fn with_storage(mut self, storage: Arc<dyn AttachmentStorage>) -> Self {
+        self.storage = Some(storage);
+        self
+    }
+
+    /// Offers another export format, or replaces the built-in one of the
+    /// same name.
+    pub fn with_exporter(mut self, exporter: Arc<dyn Exporter>) -> Self {
+        self.exporters.register(exporter);
+        self
+    }
+
+    /// Accepts another import format, or replaces the built-in one of the
+    /// same name.
+    pub fn with_importer(mut self, importer: Arc<dyn Importer>) -> Self {
+        self.importers.register(importer);
+        self
+    }
+
+    /// Offers another action through `/actions`, or replaces the built-in
+    /// one of the same name.
+    pub fn with_action(mut self, action: Arc<dyn Action>) -> Self {
+        self.actions.register(action);
+        self
+    }
+
+    /// Runs `hooks` on item lifecycle events.
+    pub fn with_item_hooks(mut self, hooks: ItemHooks) -> Self {
+        self.item_hooks = Some(hooks);
+        self
+    }
+
+    /// Applies pending migrations to the pool before building.
+    pub fn with_migrations(mut self) -> Self {
+        self.run_migrations = true;
+        self
+    }
+
+    /// Spawns the reminder delivery worker when the app is built.
+    pub fn with_reminder_worker(mut self, config: ReminderWorkerConfig) -> Self {
+        self.reminder_worker = Some(config);
+        self
+    }
+
+    /// Spawns the periodic database maintenance job when the app is built.
+    pub fn with_maintenance(mut self, config: MaintenanceConfig) -> Self {
+        self.maintenance = Some(config);
+        self
+    }
+
+    /// Builds the shared state and starts whatever tasks were asked for. Must
+    /// be called inside a Tokio runtime.
+    pub async fn build_state(self) -> Result<AppState, MigrateError> {
+        if self.run_migrations {
+            db::run_migrations(&self.pool).await?;
+        }
+
+        let storage = self
+            .storage
+            .unwrap_or_else(|| Arc::new(LocalDiskStorage::new(self.config.attachments_dir.clone())));
+        let ids = self.ids.unwrap_or_else(|| self.config.id_scheme.generator());
+        let ingest_batching = self.config.ingest_batching;
+
+        let mut state = AppState::new(self.pool)
+            .with_config(self.config)
+            .with_storage(storage)
+            .with_ids(ids)
+            .with_exporters(self.exporters)
+            .with_importers(self.importers)
+            .with_actions(self.actions);
+        if let Some(hooks) = self.item_hooks {
+            state = state.with_item_hooks(hooks);
+        }
+        if let Some(writer) = self.writer {
+            state = state.with_writer(writer);
+        }
+        if let Some(clock) = self.clock {
+            state = state.with_clock(clock);
+        }
+        if ingest_batching {
+            let batcher = ItemBatcher::spawn(&state, ItemBatcherConfig::default());
+            state = state.with_item_batcher(batcher);
+        }
+        if let Some(config) = self.reminder_worker {
+            tokio::spawn(reminder_worker::run(state.clone(), config));
+        }
+        if let Some(config) = self.maintenance {
+            tokio::spawn(db_maintenance::run(state.writer.clone(), config));
+        }
+
+        Ok(state)
+    }
+
+    /// The app's routes, ready to serve or to nest into another router.
+    pub async fn build(self) -> Result<Router, MigrateError> {
+        Ok(create_app_with_state(self.build_state().await?))
+    }
+}
@@ -0,0 +1,241 @@
+use std::env;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::id_generator::{IdGenerator, UuidV4Generator, UuidV7Generator};
+
+/// Runtime settings that shape the router.
+#[derive(Clone)]
+pub struct AppConfig {
+    /// SQLite database to open.
+    pub database_url: String,
+    /// Most connections in the main pool.
+    pub db_max_connections: u32,
+    /// How long a connection waits for a lock held by another writer.
+    pub db_busy_timeout: Duration,
+    /// Send writes through a separate single-connection pool.
+    pub db_writer_pool: bool,
+    /// Time between database maintenance runs.
+    pub db_maintenance_interval: Duration,
+    /// Run a full `ANALYZE` every this many maintenance runs; 0 never does.
+    pub db_analyze_every: u32,
+    /// Most free pages a maintenance run releases.
+    pub db_vacuum_pages: i64,
+    /// Largest request body accepted, in bytes.
+    pub max_body_bytes: usize,
+    /// Log every SQL statement with its parameters and timing.
+    pub sql_debug: bool,
+    /// Directory attachment files are written to.
+    pub attachments_dir: PathBuf,
+    /// Largest accepted attachment, in bytes.
+    pub max_attachment_bytes: usize,
+    /// MIME types attachments may have.
+    pub allowed_attachment_types: Vec<String>,
+    /// Kind of id given to new rows.
+    pub id_scheme: IdScheme,
+    /// Coalesce item inserts into batched transactions.
+    pub ingest_batching: bool,
+    /// How long item reads are served from the cache. Zero disables it.
+    pub item_cache_ttl: Duration,
+    /// Most items, and separately most list pages, kept in the cache.
+    pub item_cache_capacity: usize,
+    /// Serve gRPC on this port instead of alongside REST on the main one.
+    pub grpc_port: Option<u16>,
+    /// Directory of `*.rhai` item hook scripts loaded at startup.
+    pub item_hooks_dir: Option<PathBuf>,
+    /// How long one item hook call may run.
+    pub item_hook_timeout: Duration,
+    /// Hosts reminder webhooks may reach even though they are, or resolve
+    /// to, loopback, private or link-local addresses.
+    pub webhook_private_hosts: Vec<String>,
+    /// `todo-server --demo`: sessions idle for longer are dropped with their data.
+    pub demo_session_ttl: Duration,
+    /// `todo-server --demo`: most sessions alive at once.
+    pub demo_max_sessions: usize,
+    /// `todo-server --demo`: most sessions one client address may start per hour.
+    pub demo_sessions_per_client_per_hour: u32,
+    /// `todo-server --demo`: most requests one session may make per minute.
+    pub demo_requests_per_session_per_minute: u32,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IdScheme {
+    /// Random v4 UUIDs.
+    #[default]
+    UuidV4,
+    /// Time-ordered v7 UUIDs.
+    UuidV7,
+}
+
+impl IdScheme {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "uuidv4" | "v4" => Some(IdScheme::UuidV4),
+            "uuidv7" | "v7" => Some(IdScheme::UuidV7),
+            _ => None,
+        }
+    }
+
+    pub fn generator(self) -> Arc<dyn IdGenerator> {
+        match self {
+            IdScheme::UuidV4 => Arc::new(UuidV4Generator),
+            IdScheme::UuidV7 => Arc::new(UuidV7Generator),
+        }
+    }
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            database_url: "sqlite:./database.db".to_string(),
+            db_max_connections: 5,
+            db_busy_timeout: Duration::from_secs(5),
+            db_writer_pool: false,
+            db_maintenance_interval: Duration::from_secs(6 * 60 * 60),
+            db_analyze_every: 4,
+            db_vacuum_pages: 1000,
+            max_body_bytes: 1024 * 1024,
+            sql_debug: false,
+            attachments_dir: PathBuf::from("./attachments"),
+            max_attachment_bytes: 10 * 1024 * 1024,
+            allowed_attachment_types: ["application/pdf", "image/gif", "image/jpeg", "image/png", "text/plain"]
+                .map(String::from)
+                .to_vec(),
+            id_scheme: IdScheme::default(),
+            ingest_batching: false,
+            item_cache_ttl: Duration::from_secs(5),
+            item_cache_capacity: 1000,
+            grpc_port: None,
+            item_hooks_dir: None,
+            item_hook_timeout: Duration::from_millis(50),
+            webhook_private_hosts: Vec::new(),
+            demo_session_ttl: Duration::from_secs(30 * 60),
+            demo_max_sessions: 500,
+            demo_sessions_per_client_per_hour: 10,
+            demo_requests_per_session_per_minute: 120,
+        }
+    }
+}
+
+impl AppConfig {
+    /// Defaults overridden by whichever of `DATABASE_URL`, `DB_MAX_CONNECTIONS`,
+    /// `DB_BUSY_TIMEOUT_MS`, `DB_WRITER_POOL`, `DB_MAINTENANCE_INTERVAL_SECS`,
+    /// `DB_ANALYZE_EVERY`, `DB_VACUUM_PAGES`, `MAX_BODY_BYTES`, `SQL_DEBUG`,
+    /// `ATTACHMENTS_DIR`, `MAX_ATTACHMENT_BYTES`, `ALLOWED_ATTACHMENT_TYPES`
+    /// (comma separated), `ID_SCHEME` (`uuidv4` or `uuidv7`),
+    /// `INGEST_BATCHING`, `ITEM_CACHE_TTL_MS`, `ITEM_CACHE_CAPACITY`,
+    /// `GRPC_PORT`, `ITEM_HOOKS_DIR`, `ITEM_HOOK_TIMEOUT_MS`,
+    /// `WEBHOOK_PRIVATE_HOSTS` (comma separated), `DEMO_SESSION_TTL_SECS`,
+    /// `DEMO_MAX_SESSIONS`, `DEMO_SESSIONS_PER_CLIENT_PER_HOUR` and
+    /// `DEMO_REQUESTS_PER_SESSION_PER_MINUTE` are set.
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(database_url) = env::var("DATABASE_URL") {
+            config.database_url = database_url;
+        }
+
+        if let Some(max_connections) = env::var("DB_MAX_CONNECTIONS").ok().and_then(|value| value.parse().ok()) {
+            config.db_max_connections = max_connections;
+        }
+
+        if let Some(timeout_ms) = env::var("DB_BUSY_TIMEOUT_MS").ok().and_then(|value| value.parse().ok()) {
+            config.db_busy_timeout = Duration::from_millis(timeout_ms);
+        }
+
+        if let Ok(writer_pool) = env::var("DB_WRITER_POOL") {
+            config.db_writer_pool = matches!(writer_pool.as_str(), "1" | "true");
+        }
+
+        if let Some(interval_secs) = env::var("DB_MAINTENANCE_INTERVAL_SECS").ok().and_then(|value| value.parse().ok()).filter(|secs| *secs > 0) {
+            config.db_maintenance_interval = Duration::from_secs(interval_secs);
+        }
+
+        if let Some(analyze_every) = env::var("DB_ANALYZE_EVERY").ok().and_then(|value| value.parse().ok()) {
+            config.db_analyze_every = analyze_every;
+        }
+
+        if let Some(vacuum_pages) = env::var("DB_VACUUM_PAGES").ok().and_then(|value| value.parse().ok()) {
+            config.db_vacuum_pages = vacuum_pages;
+        }
+
+        if let Some(max_body_bytes) = env::var("MAX_BODY_BYTES").ok().and_then(|value| value.parse().ok()) {
+            config.max_body_bytes = max_body_bytes;
+        }
+
+        if let Ok(sql_debug) = env::var("SQL_DEBUG") {
+            config.sql_debug = matches!(sql_debug.as_str(), "1" | "true");
+        }
+
+        if let Ok(attachments_dir) = env::var("ATTACHMENTS_DIR") {
+            config.attachments_dir = PathBuf::from(attachments_dir);
+        }
+
+        if let Some(max_attachment_bytes) = env::var("MAX_ATTACHMENT_BYTES").ok().and_then(|value| value.parse().ok()) {
+            config.max_attachment_bytes = max_attachment_bytes;
+        }
+
+        if let Ok(types) = env::var("ALLOWED_ATTACHMENT_TYPES") {
+            config.allowed_attachment_types = types
+                .split(',')
+                .map(|content_type| content_type.trim().to_ascii_lowercase())
+                .filter(|content_type| !content_type.is_empty())
+                .collect();
+        }
+
+        if let Some(id_scheme) = env::var("ID_SCHEME").ok().and_then(|value| IdScheme::parse(&value)) {
+            config.id_scheme = id_scheme;
+        }
+
+        if let Ok(ingest_batching) = env::var("INGEST_BATCHING") {
+            config.ingest_batching = matches!(ingest_batching.as_str(), "1" | "true");
+        }
+
+        if let Some(ttl_ms) = env::var("ITEM_CACHE_TTL_MS").ok().and_then(|value| value.parse().ok()) {
+            config.item_cache_ttl = Duration::from_millis(ttl_ms);
+        }
+
+        if let Some(capacity) = env::var("ITEM_CACHE_CAPACITY").ok().and_then(|value| value.parse().ok()) {
+            config.item_cache_capacity = capacity;
+        }
+
+        if let Some(grpc_port) = env::var("GRPC_PORT").ok().and_then(|value| value.parse().ok()) {
+            config.grpc_port = Some(grpc_port);
+        }
+
+        if let Ok(item_hooks_dir) = env::var("ITEM_HOOKS_DIR") {
+            config.item_hooks_dir = Some(PathBuf::from(item_hooks_dir));
+        }
+
+        if let Some(timeout_ms) = env::var("ITEM_HOOK_TIMEOUT_MS").ok().and_then(|value| value.parse().ok()) {
+            config.item_hook_timeout = Duration::from_millis(timeout_ms);
+        }
+
+        if let Ok(hosts) = env::var("WEBHOOK_PRIVATE_HOSTS") {
+            config.webhook_private_hosts = hosts
+                .split(',')
+                .map(|host| host.trim().to_ascii_lowercase())
+                .filter(|host| !host.is_empty())
+                .collect();
+        }
+
+        if let Some(ttl_secs) = env::var("DEMO_SESSION_TTL_SECS").ok().and_then(|value| value.parse().ok()).filter(|secs| *secs > 0) {
+            config.demo_session_ttl = Duration::from_secs(ttl_secs);
+        }
+
+        if let Some(max_sessions) = env::var("DEMO_MAX_SESSIONS").ok().and_then(|value| value.parse().ok()) {
+            config.demo_max_sessions = max_sessions;
+        }
+
+        if let Some(sessions) = env::var("DEMO_SESSIONS_PER_CLIENT_PER_HOUR").ok().and_then(|value| value.parse().ok()) {
+            config.demo_sessions_per_client_per_hour = sessions;
+        }
+
+        if let Some(requests) = env::var("DEMO_REQUESTS_PER_SESSION_PER_MINUTE").ok().and_then(|value| value.parse().ok()) {
+            config.demo_requests_per_session_per_minute = requests;
+        }
+
+        config
+    }
+}
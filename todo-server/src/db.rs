@@ -0,0 +1,82 @@
+use std::str::FromStr;
+
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous};
+use sqlx::SqlitePool;
+use crate::config::AppConfig;
+
+/// Connection settings for `config.database_url`.
+///
+/// WAL lets readers carry on while a write is in progress, and the busy
+/// timeout makes a writer wait for the lock instead of failing straight away
+/// with "database is locked". `synchronous=NORMAL` is durable in WAL mode
+/// except for the last commits before a power loss.
+pub fn connect_options(config: &AppConfig) -> Result<SqliteConnectOptions, sqlx::Error> {
+    let options = SqliteConnectOptions::from_str(&config.database_url)?
+        .create_if_missing(true)
+        .journal_mode(SqliteJournalMode::Wal)
+        .busy_timeout(config.db_busy_timeout)
+        .synchronous(SqliteSynchronous::Normal)
+        .foreign_keys(true);
+
+    Ok(options)
+}
+
+/// Opens the main pool. A database without tables yet is switched to
+/// `auto_vacuum=INCREMENTAL`, so the maintenance job can hand free pages back
+/// to the filesystem.
+///
+/// This can't be a connect option: sqlx switches to WAL first, which writes
+/// the file header and fixes the vacuum mode. An existing file keeps its mode
+/// until it is converted once with `PRAGMA auto_vacuum = INCREMENTAL; VACUUM;`.
+pub async fn init_db(config: &AppConfig) -> Result<sqlx::SqlitePool, sqlx::Error> {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(config.db_max_connections)
+        .connect_with(connect_options(config)?)
+        .await?;
+
+    let (tables,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM sqlite_schema").fetch_one(&pool).await?;
+    // 2 is INCREMENTAL.
+    let (auto_vacuum,): (i64,) = sqlx::query_as("PRAGMA auto_vacuum").fetch_one(&pool).await?;
+    if tables == 0 && auto_vacuum != 2 {
+        let mut conn = pool.acquire().await?;
+        sqlx::query("PRAGMA auto_vacuum = INCREMENTAL").execute(&mut *conn).await?;
+        sqlx::query("VACUUM").execute(&mut *conn).await?;
+    }
+
+    Ok(pool)
+}
+
+/// A pool with a single connection, for writes only.
+///
+/// SQLite allows one writer at a time anyway; queueing writes in the pool
+/// keeps them from contending for the database lock.
+pub async fn init_writer_pool(config: &AppConfig) -> Result<sqlx::SqlitePool, sqlx::Error> {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(connect_options(config)?)
+        .await?;
+
+    Ok(pool)
+}
+
+/// A fresh in-memory database with all migrations applied, for data that
+/// is meant to be thrown away.
+///
+/// The pool is pinned to a single connection that never expires, since every
+/// new `sqlite::memory:` connection would otherwise see an empty database.
+pub async fn init_scratch_db() -> Result<SqlitePool, sqlx::migrate::MigrateError> {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .idle_timeout(None)
+        .max_lifetime(None)
+        .connect("sqlite::memory:")
+        .await?;
+
+    run_migrations(&pool).await?;
+
+    Ok(pool)
+}
+
+pub async fn run_migrations(pool: &SqlitePool) -> Result<(), sqlx::migrate::MigrateError> {
+    sqlx::migrate!("./migrations").run(pool).await
+}
@@ -0,0 +1,50 @@
+use sqlx::error::{DatabaseError, ErrorKind};
+use crate::error::AppError;
+
+// Primary SQLite result codes; the extended codes reported by sqlx carry
+// these in their low byte.
+const SQLITE_BUSY: i32 = 5;
+const SQLITE_LOCKED: i32 = 6;
+const SQLITE_IOERR: i32 = 10;
+const SQLITE_CORRUPT: i32 = 11;
+const SQLITE_FULL: i32 = 13;
+const SQLITE_CANTOPEN: i32 = 14;
+const SQLITE_CONSTRAINT: i32 = 19;
+const SQLITE_NOTADB: i32 = 26;
+// Raised by `RAISE(ABORT, ...)` in the schema's validation triggers.
+const SQLITE_CONSTRAINT_TRIGGER: i32 = 1811;
+
+impl From<sqlx::Error> for AppError {
+    fn from(err: sqlx::Error) -> Self {
+        match err {
+            sqlx::Error::RowNotFound => AppError::NotFound,
+            sqlx::Error::Database(db_err) => from_database_error(db_err.as_ref()),
+            sqlx::Error::PoolTimedOut => AppError::Busy,
+            sqlx::Error::Io(_) | sqlx::Error::WorkerCrashed => AppError::Unavailable(err.to_string()),
+            _ => AppError::Internal(err.to_string()),
+        }
+    }
+}
+
+fn from_database_error(db_err: &dyn DatabaseError) -> AppError {
+    match db_err.kind() {
+        ErrorKind::UniqueViolation => return AppError::Conflict("A record with the same key already exists".to_string()),
+        ErrorKind::ForeignKeyViolation => return AppError::Conflict("A referenced record does not exist".to_string()),
+        ErrorKind::NotNullViolation | ErrorKind::CheckViolation => return AppError::Unprocessable(db_err.message().to_string()),
+        _ => {}
+    }
+
+    let extended = db_err.code().and_then(|code| code.parse::<i32>().ok()).unwrap_or_default();
+    if extended == SQLITE_CONSTRAINT_TRIGGER {
+        return AppError::Unprocessable(db_err.message().to_string());
+    }
+
+    match extended & 0xff {
+        SQLITE_BUSY | SQLITE_LOCKED => AppError::Busy,
+        SQLITE_FULL => AppError::StorageFull,
+        SQLITE_CORRUPT | SQLITE_NOTADB => AppError::Corrupt(db_err.message().to_string()),
+        SQLITE_IOERR | SQLITE_CANTOPEN => AppError::Unavailable(db_err.message().to_string()),
+        SQLITE_CONSTRAINT => AppError::Conflict(db_err.message().to_string()),
+        _ => AppError::Internal(db_err.message().to_string()),
+    }
+}
@@ -0,0 +1,121 @@
+//! Periodic SQLite housekeeping.
+//!
+//! Every run refreshes the query planner statistics with `PRAGMA optimize`
+//! (and a full `ANALYZE` every few runs), then hands free pages back to the
+//! filesystem with `PRAGMA incremental_vacuum`. The app creates databases with
+//! `auto_vacuum = INCREMENTAL`, but a file created before that keeps its old
+//! mode, and the vacuum step does nothing there, until it is converted once
+//! with `sqlite3 database.db 'PRAGMA auto_vacuum = INCREMENTAL; VACUUM;'`.
+//! Each run logs its duration and the database size, along with
+//! the change since the previous run, on the `db_maintenance` target.
+
+use std::time::{Duration, Instant};
+
+use sqlx::sqlite::SqlitePool;
+use crate::config::AppConfig;
+use crate::db_trace;
+
+pub struct MaintenanceConfig {
+    /// Time between runs. The first run happens one interval after startup.
+    pub interval: Duration,
+    /// Run a full `ANALYZE` instead of `PRAGMA optimize` every this many runs.
+    pub analyze_every: u32,
+    /// Most free pages released per run, so a run never blocks writers for
+    /// long.
+    pub vacuum_pages: i64,
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self::from_config(&AppConfig::default())
+    }
+}
+
+impl MaintenanceConfig {
+    /// The maintenance settings of `config`.
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            interval: config.db_maintenance_interval,
+            analyze_every: config.db_analyze_every,
+            vacuum_pages: config.db_vacuum_pages,
+        }
+    }
+}
+
+/// What a maintenance run did and the database size afterwards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MaintenanceReport {
+    pub analyzed: bool,
+    pub duration: Duration,
+    pub page_size: i64,
+    pub page_count: i64,
+    /// Pages allocated to the file but holding no data.
+    pub freelist_count: i64,
+}
+
+impl MaintenanceReport {
+    pub fn size_bytes(&self) -> i64 {
+        self.page_size * self.page_count
+    }
+}
+
+/// Runs maintenance forever. Meant to be spawned once at startup.
+pub async fn run(pool: SqlitePool, config: MaintenanceConfig) {
+    let mut interval = tokio::time::interval(config.interval);
+    // The first tick completes immediately; skip it so startup stays quiet.
+    interval.tick().await;
+
+    let mut runs: u32 = 0;
+    let mut previous_size: Option<i64> = None;
+
+    loop {
+        interval.tick().await;
+        runs = runs.wrapping_add(1);
+        let analyze = config.analyze_every > 0 && runs.is_multiple_of(config.analyze_every);
+
+        match run_maintenance(&pool, analyze, config.vacuum_pages).await {
+            Ok(report) => {
+                let size_bytes = report.size_bytes();
+                let growth_bytes = previous_size.map(|previous| size_bytes - previous);
+                previous_size = Some(size_bytes);
+
+                tracing::info!(
+                    target: "db_maintenance",
+                    analyzed = report.analyzed,
+                    duration_ms = report.duration.as_secs_f64() * 1000.0,
+                    size_bytes,
+                    growth_bytes,
+                    free_bytes = report.page_size * report.freelist_count,
+                    "database maintenance finished"
+                );
+            }
+            Err(err) => tracing::error!("Database maintenance failed: {err}"),
+        }
+    }
+}
+
+/// One maintenance pass. `analyze` runs a full `ANALYZE` rather than the
+/// cheaper `PRAGMA optimize`.
+pub async fn run_maintenance(pool: &SqlitePool, analyze: bool, vacuum_pages: i64) -> Result<MaintenanceReport, sqlx::Error> {
+    let started = Instant::now();
+    let mut conn = pool.acquire().await?;
+
+    let statement = if analyze { "ANALYZE" } else { "PRAGMA optimize" };
+    db_trace::query(statement).execute(&mut *conn).await?;
+
+    // PRAGMA arguments cannot be bound.
+    let vacuum = format!("PRAGMA incremental_vacuum({})", vacuum_pages.max(0));
+    db_trace::query(&vacuum).execute(&mut *conn).await?;
+
+    let (page_size,): (i64,) = db_trace::query_as("PRAGMA page_size").fetch_one(&mut *conn).await?;
+    let (page_count,): (i64,) = db_trace::query_as("PRAGMA page_count").fetch_one(&mut *conn).await?;
+    let (freelist_count,): (i64,) = db_trace::query_as("PRAGMA freelist_count").fetch_one(&mut *conn).await?;
+
+    Ok(MaintenanceReport {
+        analyzed: analyze,
+        duration: started.elapsed(),
+        page_size,
+        page_count,
+        freelist_count,
+    })
+}
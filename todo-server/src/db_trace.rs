@@ -0,0 +1,177 @@
+//! Statement-level SQL logging for debugging slow endpoints.
+//!
+//! [`query`] and [`query_as`] wrap their `sqlx` namesakes and emit one
+//! `DEBUG` event on the `sql` target per statement, with the bound parameters,
+//! the row count and the duration. Events are recorded inside whatever span is
+//! current, so statements run by a handler show up under its request span.
+//! When the `sql` target is not enabled nothing is recorded or formatted.
+
+use std::fmt::Debug;
+use std::time::Instant;
+
+use sqlx::query::{Query, QueryAs};
+use sqlx::sqlite::{Sqlite, SqliteArguments, SqliteQueryResult, SqliteRow};
+use sqlx::{Encode, Executor, FromRow, Type};
+use tracing::Level;
+
+const REDACTED: &str = "[redacted]";
+
+pub fn query(sql: &str) -> TracedQuery<'_> {
+    TracedQuery {
+        inner: sqlx::query(sql),
+        trace: Trace::new(sql),
+    }
+}
+
+pub fn query_as<'q, O>(sql: &'q str) -> TracedQueryAs<'q, O>
+where
+    O: for<'r> FromRow<'r, SqliteRow>,
+{
+    TracedQueryAs {
+        inner: sqlx::query_as(sql),
+        trace: Trace::new(sql),
+    }
+}
+
+struct Trace<'q> {
+    sql: &'q str,
+    /// `None` when SQL tracing is off, so binds are never formatted.
+    params: Option<Vec<String>>,
+}
+
+impl<'q> Trace<'q> {
+    fn new(sql: &'q str) -> Self {
+        let enabled = tracing::enabled!(target: "sql", Level::DEBUG);
+
+        Self { sql, params: enabled.then(Vec::new) }
+    }
+
+    fn param(&mut self, value: &dyn Debug) {
+        if let Some(params) = &mut self.params {
+            params.push(format!("{value:?}"));
+        }
+    }
+
+    fn redacted_param(&mut self) {
+        if let Some(params) = &mut self.params {
+            params.push(REDACTED.to_string());
+        }
+    }
+
+    fn finish<T>(&self, started: Instant, result: &Result<T, sqlx::Error>, rows: impl FnOnce(&T) -> u64) {
+        let Some(params) = &self.params else { return };
+        let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+        match result {
+            Ok(value) => tracing::debug!(target: "sql", statement = self.sql, ?params, rows = rows(value), elapsed_ms, "query"),
+            Err(err) => tracing::debug!(target: "sql", statement = self.sql, ?params, error = %err, elapsed_ms, "query failed"),
+        }
+    }
+}
+
+pub struct TracedQuery<'q> {
+    inner: Query<'q, Sqlite, SqliteArguments<'q>>,
+    trace: Trace<'q>,
+}
+
+impl<'q> TracedQuery<'q> {
+    pub fn bind<T>(mut self, value: T) -> Self
+    where
+        T: 'q + Send + Encode<'q, Sqlite> + Type<Sqlite> + Debug,
+    {
+        self.trace.param(&value);
+        self.inner = self.inner.bind(value);
+        self
+    }
+
+    /// Binds a value that must not show up in logs, such as a URL that may
+    /// carry credentials.
+    pub fn bind_redacted<T>(mut self, value: T) -> Self
+    where
+        T: 'q + Send + Encode<'q, Sqlite> + Type<Sqlite>,
+    {
+        self.trace.redacted_param();
+        self.inner = self.inner.bind(value);
+        self
+    }
+
+    /// Logs the number of rows affected.
+    pub async fn execute<'e, 'c: 'e, E>(self, executor: E) -> Result<SqliteQueryResult, sqlx::Error>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = Sqlite>,
+    {
+        let started = Instant::now();
+        let result = self.inner.execute(executor).await;
+        self.trace.finish(started, &result, SqliteQueryResult::rows_affected);
+
+        result
+    }
+}
+
+pub struct TracedQueryAs<'q, O> {
+    inner: QueryAs<'q, Sqlite, O, SqliteArguments<'q>>,
+    trace: Trace<'q>,
+}
+
+impl<'q, O> TracedQueryAs<'q, O>
+where
+    O: Send + Unpin + for<'r> FromRow<'r, SqliteRow>,
+{
+    pub fn bind<T>(mut self, value: T) -> Self
+    where
+        T: 'q + Send + Encode<'q, Sqlite> + Type<Sqlite> + Debug,
+    {
+        self.trace.param(&value);
+        self.inner = self.inner.bind(value);
+        self
+    }
+
+    pub fn bind_redacted<T>(mut self, value: T) -> Self
+    where
+        T: 'q + Send + Encode<'q, Sqlite> + Type<Sqlite>,
+    {
+        self.trace.redacted_param();
+        self.inner = self.inner.bind(value);
+        self
+    }
+
+    pub async fn fetch_all<'e, 'c: 'e, E>(self, executor: E) -> Result<Vec<O>, sqlx::Error>
+    where
+        'q: 'e,
+        O: 'e,
+        E: 'e + Executor<'c, Database = Sqlite>,
+    {
+        let started = Instant::now();
+        let result = self.inner.fetch_all(executor).await;
+        self.trace.finish(started, &result, |rows| rows.len() as u64);
+
+        result
+    }
+
+    pub async fn fetch_one<'e, 'c: 'e, E>(self, executor: E) -> Result<O, sqlx::Error>
+    where
+        'q: 'e,
+        O: 'e,
+        E: 'e + Executor<'c, Database = Sqlite>,
+    {
+        let started = Instant::now();
+        let result = self.inner.fetch_one(executor).await;
+        self.trace.finish(started, &result, |_| 1);
+
+        result
+    }
+
+    pub async fn fetch_optional<'e, 'c: 'e, E>(self, executor: E) -> Result<Option<O>, sqlx::Error>
+    where
+        'q: 'e,
+        O: 'e,
+        E: 'e + Executor<'c, Database = Sqlite>,
+    {
+        let started = Instant::now();
+        let result = self.inner.fetch_optional(executor).await;
+        self.trace.finish(started, &result, |row| u64::from(row.is_some()));
+
+        result
+    }
+}
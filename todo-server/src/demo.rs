@@ -0,0 +1,283 @@
+//! `todo-server --demo`: every browser session gets its own throwaway in-memory
+//! database, so the app can be offered as a public live demo without
+//! sign-ups and visitors never see each other's items.
+//!
+//! A session is identified by the [`SESSION_COOKIE`] cookie and dropped,
+//! with its data, after [`DemoConfig::session_ttl`] without requests; every
+//! response renews the cookie for as long. New sessions per client address
+//! and requests per session are rate limited.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::{header, HeaderMap, HeaderValue};
+use axum::response::{IntoResponse, Response};
+use axum::Router;
+use chrono::{DateTime, TimeDelta, Utc};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+use crate::clock::{Clock, SystemClock};
+use crate::config::AppConfig;
+use crate::create_app_with_state;
+use crate::db;
+use crate::error::AppError;
+use crate::modules::attachments::attachment_storage::InMemoryStorage;
+use crate::state::AppState;
+
+pub const SESSION_COOKIE: &str = "demo_session";
+
+pub struct DemoConfig {
+    /// Sessions idle for longer are dropped with their data.
+    pub session_ttl: Duration,
+    /// Most sessions alive at once.
+    pub max_sessions: usize,
+    /// Most sessions one client address may start per hour.
+    pub sessions_per_client_per_hour: u32,
+    /// Most requests one session may make per minute.
+    pub requests_per_session_per_minute: u32,
+    /// How often expired sessions are looked for.
+    pub sweep_interval: Duration,
+}
+
+impl Default for DemoConfig {
+    fn default() -> Self {
+        Self::from_config(&AppConfig::default())
+    }
+}
+
+impl DemoConfig {
+    /// The demo settings of `config`.
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            session_ttl: config.demo_session_ttl,
+            max_sessions: config.demo_max_sessions,
+            sessions_per_client_per_hour: config.demo_sessions_per_client_per_hour,
+            requests_per_session_per_minute: config.demo_requests_per_session_per_minute,
+            sweep_interval: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Counts hits in fixed windows of time.
+struct RateWindow {
+    started_at: DateTime<Utc>,
+    hits: u32,
+}
+
+impl RateWindow {
+    fn new(now: DateTime<Utc>) -> Self {
+        Self { started_at: now, hits: 0 }
+    }
+
+    /// Counts a hit, unless `max` were already counted in the current window.
+    fn hit(&mut self, now: DateTime<Utc>, length: TimeDelta, max: u32) -> bool {
+        if now - self.started_at >= length {
+            *self = Self::new(now);
+        }
+        if self.hits >= max {
+            return false;
+        }
+
+        self.hits += 1;
+        true
+    }
+}
+
+struct Session {
+    app: Router,
+    pool: SqlitePool,
+    last_seen: DateTime<Utc>,
+    requests: RateWindow,
+}
+
+/// The demo sessions, shared by every request.
+#[derive(Clone)]
+pub struct Demo {
+    inner: Arc<DemoInner>,
+}
+
+struct DemoInner {
+    config: DemoConfig,
+    app_config: AppConfig,
+    clock: Arc<dyn Clock>,
+    sessions: Mutex<HashMap<String, Session>>,
+    clients: Mutex<HashMap<String, RateWindow>>,
+}
+
+impl Demo {
+    /// Sessions run the app with `app_config`, except that attachments are
+    /// kept in memory.
+    pub fn new(config: DemoConfig, app_config: AppConfig) -> Self {
+        Self::with_clock(config, app_config, Arc::new(SystemClock))
+    }
+
+    pub fn with_clock(config: DemoConfig, app_config: AppConfig, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            inner: Arc::new(DemoInner {
+                config,
+                app_config,
+                clock,
+                sessions: Mutex::default(),
+                clients: Mutex::default(),
+            }),
+        }
+    }
+
+    /// Routes every request to the app of its session, starting a session
+    /// when there is none. Serve with
+    /// `into_make_service_with_connect_info::<SocketAddr>()` so sessions
+    /// are limited per client address.
+    pub fn router(&self) -> Router {
+        Router::new().fallback(handle).with_state(self.clone())
+    }
+
+    pub fn session_count(&self) -> usize {
+        self.inner.sessions.lock().unwrap().len()
+    }
+
+    /// Drops sessions that have been idle for longer than the TTL.
+    pub fn sweep(&self) {
+        let now = self.inner.clock.now();
+        let ttl = ttl(&self.inner.config);
+
+        let mut sessions = self.inner.sessions.lock().unwrap();
+        sessions.retain(|_, session| {
+            let alive = now - session.last_seen < ttl;
+            if !alive {
+                let pool = session.pool.clone();
+                tokio::spawn(async move { pool.close().await });
+            }
+            alive
+        });
+
+        let hour = TimeDelta::hours(1);
+        self.inner.clients.lock().unwrap().retain(|_, window| now - window.started_at < hour);
+    }
+
+    /// Sweeps forever. Meant to be spawned once at startup.
+    pub async fn run_sweeper(self) {
+        let mut interval = tokio::time::interval(self.inner.config.sweep_interval);
+
+        loop {
+            interval.tick().await;
+            self.sweep();
+        }
+    }
+
+    /// The id and app of the request's session, if it has a live one,
+    /// counting the request against the session's rate limit.
+    fn existing_session(&self, headers: &HeaderMap) -> Result<Option<(String, Router)>, AppError> {
+        let Some(id) = session_cookie(headers) else {
+            return Ok(None);
+        };
+        let now = self.inner.clock.now();
+        let config = &self.inner.config;
+
+        let mut sessions = self.inner.sessions.lock().unwrap();
+        let Some(session) = sessions.get_mut(id).filter(|session| now - session.last_seen < ttl(config)) else {
+            return Ok(None);
+        };
+        if !session.requests.hit(now, TimeDelta::minutes(1), config.requests_per_session_per_minute) {
+            return Err(AppError::RateLimited("Too many requests in this demo session".to_string()));
+        }
+        session.last_seen = now;
+
+        Ok(Some((id.to_string(), session.app.clone())))
+    }
+
+    async fn start_session(&self, client: String) -> Result<(String, Router), AppError> {
+        let now = self.inner.clock.now();
+        let config = &self.inner.config;
+
+        self.sweep();
+        // Checked again when the session is added; this only saves setting up
+        // a database, and a client's session allowance, when already full.
+        if self.session_count() >= config.max_sessions {
+            return Err(demo_full());
+        }
+        let allowed = self
+            .inner
+            .clients
+            .lock()
+            .unwrap()
+            .entry(client)
+            .or_insert_with(|| RateWindow::new(now))
+            .hit(now, TimeDelta::hours(1), config.sessions_per_client_per_hour);
+        if !allowed {
+            return Err(AppError::RateLimited("Too many demo sessions started from this address".to_string()));
+        }
+
+        let pool = db::init_scratch_db()
+            .await
+            .map_err(|err| AppError::Internal(format!("Failed to set up a demo database: {err}")))?;
+        let state = AppState::new(pool.clone())
+            .with_config(self.inner.app_config.clone())
+            .with_clock(self.inner.clock.clone())
+            .with_storage(Arc::new(InMemoryStorage::default()));
+        let app = create_app_with_state(state);
+
+        let id = Uuid::new_v4().simple().to_string();
+        // The request starting the session is its first.
+        let requests = RateWindow { started_at: now, hits: 1 };
+        let mut sessions = self.inner.sessions.lock().unwrap();
+        if sessions.len() >= config.max_sessions {
+            tokio::spawn(async move { pool.close().await });
+            return Err(demo_full());
+        }
+        sessions.insert(id.clone(), Session { app: app.clone(), pool, last_seen: now, requests });
+
+        Ok((id, app))
+    }
+}
+
+async fn handle(State(demo): State<Demo>, request: Request) -> Response {
+    let (id, app) = match demo.existing_session(request.headers()) {
+        Ok(Some(session)) => session,
+        Ok(None) => match demo.start_session(client_address(&request)).await {
+            Ok(session) => session,
+            Err(err) => return err.into_response(),
+        },
+        Err(err) => return err.into_response(),
+    };
+
+    let Ok(mut response) = app.oneshot(request).await;
+
+    // Sent on every response, so the cookie lives as long as the session.
+    let max_age = demo.inner.config.session_ttl.as_secs();
+    let cookie = format!("{SESSION_COOKIE}={id}; Path=/; HttpOnly; SameSite=Lax; Max-Age={max_age}");
+    if let Ok(cookie) = HeaderValue::from_str(&cookie) {
+        response.headers_mut().append(header::SET_COOKIE, cookie);
+    }
+
+    response
+}
+
+fn demo_full() -> AppError {
+    AppError::RateLimited("The demo is full, try again later".to_string())
+}
+
+fn session_cookie(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .find_map(|cookie| cookie.trim().strip_prefix(SESSION_COOKIE)?.strip_prefix('='))
+}
+
+/// Requests served without connect info share one bucket.
+fn client_address(request: &Request) -> String {
+    request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map_or_else(|| "unknown".to_string(), |ConnectInfo(address)| address.ip().to_string())
+}
+
+fn ttl(config: &DemoConfig) -> TimeDelta {
+    TimeDelta::from_std(config.session_ttl).unwrap_or(TimeDelta::MAX)
+}
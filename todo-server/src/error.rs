@@ -0,0 +1,115 @@
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Json, Response};
+use serde::Serialize;
+
+/// Errors surfaced by handlers.
+///
+/// Services keep returning `sqlx::Error`; the conversion in
+/// [`crate::db_error`] decides which variant a database failure becomes.
+#[derive(Debug, Clone)]
+pub enum AppError {
+    NotFound,
+    BadRequest(String),
+    /// The request body is larger than the configured limit.
+    PayloadTooLarge,
+    UnsupportedMediaType(String),
+    Unprocessable(String),
+    /// A uniqueness, foreign key or similar constraint rejected the write.
+    Conflict(String),
+    /// The database is locked by another writer or the pool is exhausted.
+    Busy,
+    /// The client has made too many requests.
+    RateLimited(String),
+    /// The disk or database file is full.
+    StorageFull,
+    /// The database cannot be reached right now.
+    Unavailable(String),
+    /// The database file is damaged or is not a database.
+    Corrupt(String),
+    Internal(String),
+}
+
+#[derive(Serialize)]
+pub struct ErrorBody {
+    pub code: &'static str,
+    pub message: String,
+    /// Whether repeating the same request later may succeed.
+    pub retryable: bool,
+}
+
+impl AppError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            AppError::NotFound => StatusCode::NOT_FOUND,
+            AppError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            AppError::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            AppError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            AppError::Unprocessable(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
+            AppError::Busy | AppError::Unavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            AppError::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+            AppError::StorageFull => StatusCode::INSUFFICIENT_STORAGE,
+            AppError::Corrupt(_) | AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::NotFound => "not_found",
+            AppError::BadRequest(_) => "bad_request",
+            AppError::PayloadTooLarge => "payload_too_large",
+            AppError::UnsupportedMediaType(_) => "unsupported_media_type",
+            AppError::Unprocessable(_) => "unprocessable",
+            AppError::Conflict(_) => "conflict",
+            AppError::Busy => "busy",
+            AppError::RateLimited(_) => "rate_limited",
+            AppError::StorageFull => "storage_full",
+            AppError::Unavailable(_) => "unavailable",
+            AppError::Corrupt(_) => "corrupt",
+            AppError::Internal(_) => "internal",
+        }
+    }
+
+    pub fn retryable(&self) -> bool {
+        matches!(self, AppError::Busy | AppError::RateLimited(_) | AppError::Unavailable(_))
+    }
+
+    /// The message shown to clients. Details of server-side failures are
+    /// logged instead of returned.
+    pub fn message(&self) -> String {
+        match self {
+            AppError::NotFound => "Not found".to_string(),
+            AppError::BadRequest(message)
+            | AppError::UnsupportedMediaType(message)
+            | AppError::Unprocessable(message)
+            | AppError::Conflict(message)
+            | AppError::RateLimited(message) => message.clone(),
+            AppError::PayloadTooLarge => "The request body is too large".to_string(),
+            AppError::Busy => "The database is busy, try again shortly".to_string(),
+            AppError::StorageFull => "The server is out of storage".to_string(),
+            AppError::Unavailable(_) => "The database is unavailable".to_string(),
+            AppError::Corrupt(_) | AppError::Internal(_) => "Internal server error".to_string(),
+        }
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        if let AppError::Unavailable(detail) | AppError::Corrupt(detail) | AppError::Internal(detail) = &self {
+            tracing::error!(code = self.code(), "{detail}");
+        }
+
+        let body = ErrorBody {
+            code: self.code(),
+            message: self.message(),
+            retryable: self.retryable(),
+        };
+        let mut response = (self.status(), Json(body)).into_response();
+
+        if self.retryable() {
+            response.headers_mut().insert(header::RETRY_AFTER, HeaderValue::from_static("1"));
+        }
+
+        response
+    }
+}
@@ -0,0 +1,230 @@
+use std::error::Error;
+use std::io::{self, BufRead, BufReader, ErrorKind};
+use std::ops::RangeInclusive;
+
+use axum::async_trait;
+use axum::extract::multipart::{MultipartError, MultipartRejection};
+use axum::extract::rejection::{JsonRejection, QueryRejection};
+use axum::extract::{FromRequest, FromRequestParts, Request};
+use axum::RequestExt;
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use futures_util::TryStreamExt;
+use http_body_util::LengthLimitError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tokio_util::io::{StreamReader, SyncIoBridge};
+use tower_http::request_id::RequestId;
+use crate::error::AppError;
+
+/// Drop-in replacement for [`axum::Json`] whose rejections are [`AppError`]s,
+/// so a bad body gets the same JSON error shape as every other failure.
+pub struct Json<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for Json<T>
+where
+    axum::Json<T>: FromRequest<S, Rejection = JsonRejection>,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let axum::Json(value) = axum::Json::<T>::from_request(req, state).await?;
+
+        Ok(Json(value))
+    }
+}
+
+impl<T: Serialize> IntoResponse for Json<T> {
+    fn into_response(self) -> Response {
+        axum::Json(self.0).into_response()
+    }
+}
+
+/// [`axum::extract::Query`] with [`AppError`] rejections.
+pub struct Query<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for Query<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let axum::extract::Query(value) = axum::extract::Query::<T>::from_request_parts(parts, state).await?;
+
+        Ok(Query(value))
+    }
+}
+
+/// Query parameters that have rules beyond their types, such as ranges.
+pub trait Validate {
+    /// Describes the first broken rule.
+    fn validate(&self) -> Result<(), String>;
+}
+
+/// Checks that the query parameter `name` lies in `range`.
+pub fn check_range(name: &str, value: i64, range: RangeInclusive<i64>) -> Result<(), String> {
+    if range.contains(&value) {
+        Ok(())
+    } else {
+        Err(format!("{name} must be between {} and {}", range.start(), range.end()))
+    }
+}
+
+/// [`Query`] that also runs [`Validate`], for list and search endpoints.
+///
+/// Defaults come from the type's `#[serde(default)]` attributes. Unparsable
+/// and out-of-range parameters are both rejected with a 400.
+pub struct ValidatedQuery<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for ValidatedQuery<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(value) = Query::<T>::from_request_parts(parts, state).await?;
+        value.validate().map_err(AppError::BadRequest)?;
+
+        Ok(ValidatedQuery(value))
+    }
+}
+
+impl From<QueryRejection> for AppError {
+    fn from(rejection: QueryRejection) -> Self {
+        AppError::BadRequest(rejection.body_text())
+    }
+}
+
+/// Who is making the request, taken from the `X-Actor` header, and which
+/// request it is, so an audit entry can be matched to the request's logs.
+///
+/// There is no authentication yet, so the header is trusted as-is; requests
+/// without it are attributed to [`Actor::ANONYMOUS`].
+#[derive(Clone)]
+pub struct Actor {
+    pub name: String,
+    /// The `x-request-id` the change was made under, if it came in over HTTP.
+    pub request_id: Option<String>,
+}
+
+impl Actor {
+    pub const HEADER: &'static str = "x-actor";
+    pub const ANONYMOUS: &'static str = "anonymous";
+
+    /// An actor acting outside any request, such as the command line.
+    pub fn named(name: impl Into<String>) -> Self {
+        Self { name: name.into(), request_id: None }
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Actor
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let actor = match parts.headers.get(Actor::HEADER) {
+            Some(value) => value
+                .to_str()
+                .ok()
+                .map(str::trim)
+                .filter(|actor| !actor.is_empty())
+                .ok_or_else(|| AppError::BadRequest("Invalid X-Actor header".to_string()))?,
+            None => Actor::ANONYMOUS,
+        };
+
+        let request_id = parts
+            .extensions
+            .get::<RequestId>()
+            .and_then(|id| id.header_value().to_str().ok())
+            .map(str::to_string);
+
+        Ok(Actor { name: actor.to_string(), request_id })
+    }
+}
+
+/// [`axum::extract::Multipart`] with [`AppError`] rejections.
+pub struct Multipart(pub axum::extract::Multipart);
+
+#[async_trait]
+impl<S> FromRequest<S> for Multipart
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let multipart = axum::extract::Multipart::from_request(req, state).await?;
+
+        Ok(Multipart(multipart))
+    }
+}
+
+/// The request body as a blocking reader, for handlers that work through a
+/// large body on a blocking thread instead of buffering it first.
+///
+/// Reads that go past the body limit fail with [`ErrorKind::FileTooLarge`].
+/// It must be created in the runtime, but read outside of it, for example
+/// in [`tokio::task::spawn_blocking`].
+pub struct BodyReader(pub Box<dyn BufRead + Send>);
+
+#[async_trait]
+impl<S> FromRequest<S> for BodyReader
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, _state: &S) -> Result<Self, Self::Rejection> {
+        let stream = req.into_limited_body().into_data_stream().map_err(|err| {
+            let too_large = std::iter::successors(Some(&err as &(dyn Error + 'static)), |&err| err.source())
+                .any(|err| err.is::<LengthLimitError>());
+            let kind = if too_large { ErrorKind::FileTooLarge } else { ErrorKind::Other };
+
+            io::Error::new(kind, err)
+        });
+
+        Ok(BodyReader(Box::new(BufReader::new(SyncIoBridge::new(StreamReader::new(stream))))))
+    }
+}
+
+impl From<MultipartRejection> for AppError {
+    fn from(rejection: MultipartRejection) -> Self {
+        AppError::UnsupportedMediaType(rejection.body_text())
+    }
+}
+
+impl From<MultipartError> for AppError {
+    fn from(err: MultipartError) -> Self {
+        if err.status() == StatusCode::PAYLOAD_TOO_LARGE {
+            AppError::PayloadTooLarge
+        } else {
+            AppError::BadRequest(err.body_text())
+        }
+    }
+}
+
+impl From<JsonRejection> for AppError {
+    fn from(rejection: JsonRejection) -> Self {
+        let message = rejection.body_text();
+
+        match rejection {
+            JsonRejection::MissingJsonContentType(_) => AppError::UnsupportedMediaType(message),
+            JsonRejection::JsonSyntaxError(_) => AppError::BadRequest(message),
+            JsonRejection::JsonDataError(_) => AppError::Unprocessable(message),
+            _ if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE => AppError::PayloadTooLarge,
+            _ => AppError::BadRequest(message),
+        }
+    }
+}
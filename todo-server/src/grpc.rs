@@ -0,0 +1,186 @@
+//! The item operations of `/api/v1/items`, served over gRPC as well.
+//!
+//! Requests go through the same functions as the REST handlers, so
+//! validation, batching, caching and the audit log behave identically. The
+//! caller is taken from the `x-actor` metadata, like the `X-Actor` header,
+//! and an `x-request-id` sent by the client is recorded in the audit log.
+
+use axum::Router;
+use tonic::server::NamedService;
+use tonic::{Request, Response, Status};
+
+use crate::error::AppError;
+use crate::extract::{Actor, Validate};
+use crate::modules::todos::todo_controller;
+use crate::modules::todos::todo_dto::{CreateItemDto, ItemListing, ItemSort, ListItemsQuery, Patch, UpdateItemDto};
+use crate::modules::todos::todo_entity::{Item, Priority};
+use crate::pagination;
+use crate::state::AppState;
+
+use proto::todo_service_server::{TodoService, TodoServiceServer};
+
+pub mod proto {
+    tonic::include_proto!("todo.v1");
+}
+
+/// Routes for the gRPC service, to merge into the app or serve on their own.
+///
+/// Only the service's own paths are routed, so unknown REST paths keep
+/// getting the usual JSON 404 rather than a gRPC `UNIMPLEMENTED`. Clients
+/// must speak HTTP/2, which `axum::serve` accepts without TLS.
+pub fn grpc_routes(state: AppState) -> Router {
+    let path = format!("/{}/*method", TodoServiceServer::<TodoGrpc>::NAME);
+
+    Router::new().route_service(&path, TodoServiceServer::new(TodoGrpc { state }))
+}
+
+pub struct TodoGrpc {
+    state: AppState,
+}
+
+#[tonic::async_trait]
+impl TodoService for TodoGrpc {
+    async fn create_item(&self, request: Request<proto::CreateItemRequest>) -> Result<Response<proto::Item>, Status> {
+        let actor = actor(&request)?;
+        let request = request.into_inner();
+        let dto = CreateItemDto {
+            name: request.name,
+            description: request.description,
+            priority: priority_from_proto(request.priority)?.unwrap_or_default(),
+            parent_id: request.parent_id,
+        };
+
+        let item = todo_controller::create(&self.state, actor, dto).await?;
+
+        Ok(Response::new(item.into()))
+    }
+
+    async fn get_item(&self, request: Request<proto::GetItemRequest>) -> Result<Response<proto::Item>, Status> {
+        let item = todo_controller::fetch(&self.state, request.into_inner().id).await?;
+
+        Ok(Response::new(item.into()))
+    }
+
+    async fn list_items(&self, request: Request<proto::ListItemsRequest>) -> Result<Response<proto::ListItemsResponse>, Status> {
+        let request = request.into_inner();
+        let query = ListItemsQuery {
+            limit: request.limit.unwrap_or(pagination::DEFAULT_PAGE_SIZE),
+            cursor: request.cursor,
+            sort: ItemSort::Created,
+            tree: false,
+        };
+        query.validate().map_err(AppError::BadRequest)?;
+
+        let ItemListing::Flat(page) = todo_controller::list(&self.state, query).await? else {
+            return Err(Status::internal("Unexpected tree listing"));
+        };
+
+        Ok(Response::new(proto::ListItemsResponse {
+            items: page.items.into_iter().map(Into::into).collect(),
+            next_cursor: page.next_cursor,
+        }))
+    }
+
+    async fn update_item(&self, request: Request<proto::UpdateItemRequest>) -> Result<Response<proto::Item>, Status> {
+        let actor = actor(&request)?;
+        let request = request.into_inner();
+        let description = match (request.clear_description, request.description) {
+            (true, _) => Patch::Null,
+            (false, Some(description)) => Patch::Value(description),
+            (false, None) => Patch::Missing,
+        };
+        let dto = UpdateItemDto {
+            name: request.name,
+            description,
+            priority: priority_from_proto(request.priority)?,
+            parent_id: Patch::Missing,
+        };
+
+        todo_controller::update(&self.state, actor, request.id.clone(), dto).await?;
+        let item = todo_controller::fetch(&self.state, request.id).await?;
+
+        Ok(Response::new(item.into()))
+    }
+
+    async fn delete_item(&self, request: Request<proto::DeleteItemRequest>) -> Result<Response<proto::DeleteItemResponse>, Status> {
+        let actor = actor(&request)?;
+        todo_controller::remove(&self.state, actor, request.into_inner().id).await?;
+
+        Ok(Response::new(proto::DeleteItemResponse {}))
+    }
+}
+
+fn actor<T>(request: &Request<T>) -> Result<Actor, AppError> {
+    let actor = match request.metadata().get(Actor::HEADER) {
+        Some(value) => value
+            .to_str()
+            .ok()
+            .map(str::trim)
+            .filter(|actor| !actor.is_empty())
+            .ok_or_else(|| AppError::BadRequest("Invalid x-actor metadata".to_string()))?,
+        None => Actor::ANONYMOUS,
+    };
+
+    let request_id = request
+        .metadata()
+        .get("x-request-id")
+        .and_then(|id| id.to_str().ok())
+        .map(str::to_string);
+
+    Ok(Actor { name: actor.to_string(), request_id })
+}
+
+/// `None` for `PRIORITY_UNSPECIFIED`.
+fn priority_from_proto(value: i32) -> Result<Option<Priority>, AppError> {
+    match proto::Priority::try_from(value) {
+        Ok(proto::Priority::Unspecified) => Ok(None),
+        Ok(proto::Priority::Low) => Ok(Some(Priority::Low)),
+        Ok(proto::Priority::Medium) => Ok(Some(Priority::Medium)),
+        Ok(proto::Priority::High) => Ok(Some(Priority::High)),
+        Err(_) => Err(AppError::BadRequest(format!("Unknown priority {value}"))),
+    }
+}
+
+impl From<Priority> for proto::Priority {
+    fn from(priority: Priority) -> Self {
+        match priority {
+            Priority::Low => proto::Priority::Low,
+            Priority::Medium => proto::Priority::Medium,
+            Priority::High => proto::Priority::High,
+        }
+    }
+}
+
+impl From<Item> for proto::Item {
+    fn from(item: Item) -> Self {
+        proto::Item {
+            id: item.id,
+            name: item.name,
+            description: item.description,
+            created_at: item.created_at.to_rfc3339(),
+            priority: proto::Priority::from(item.priority).into(),
+            position: item.position,
+            parent_id: item.parent_id,
+            updated_at: item.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<AppError> for Status {
+    fn from(err: AppError) -> Self {
+        let message = err.message();
+
+        match err {
+            AppError::NotFound => Status::not_found(message),
+            AppError::BadRequest(_) | AppError::UnsupportedMediaType(_) | AppError::Unprocessable(_) => {
+                Status::invalid_argument(message)
+            }
+            AppError::PayloadTooLarge | AppError::StorageFull | AppError::RateLimited(_) => {
+                Status::resource_exhausted(message)
+            }
+            AppError::Conflict(_) => Status::already_exists(message),
+            AppError::Busy | AppError::Unavailable(_) => Status::unavailable(message),
+            AppError::Corrupt(_) | AppError::Internal(_) => Status::internal(message),
+        }
+    }
+}
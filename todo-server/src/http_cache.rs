@@ -0,0 +1,105 @@
+//! Validators for conditional GETs.
+//!
+//! Responses carry an `ETag`, a `Last-Modified` date and `Cache-Control:
+//! no-cache`, so clients may keep a copy but must revalidate it. A request
+//! whose `If-None-Match` or `If-Modified-Since` shows the copy is still
+//! current gets an empty `304 Not Modified` instead of the body.
+//!
+//! The app compresses responses on the way out, so the validators describe
+//! the uncompressed JSON: a client sees the same `ETag` whichever encoding it
+//! asked for, and every response says `Vary: Accept-Encoding` so a shared
+//! cache keeps the encodings apart.
+
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use crate::error::AppError;
+
+const HTTP_DATE: &str = "%a, %d %b %Y %H:%M:%S GMT";
+
+/// `body` as JSON, or a 304 when the request's validators match it.
+///
+/// The `ETag` is the SHA-256 of the serialized body, so it changes with any
+/// visible change even within the one-second resolution of `Last-Modified`,
+/// and stays the same across restarts, builds and instances.
+/// As in RFC 9110, `If-Modified-Since` is ignored when `If-None-Match` is
+/// present, and the 304 repeats the `Vary` the full response would carry,
+/// since the compression layer only adds it to bodies it could compress.
+pub fn conditional_json<T: Serialize>(request_headers: &HeaderMap, last_modified: DateTime<Utc>, body: &T) -> Result<Response, AppError> {
+    let bytes = serde_json::to_vec(body).map_err(|err| AppError::Internal(format!("Failed to serialize response: {err}")))?;
+    let etag = etag(&bytes);
+
+    let not_modified = match request_headers.get(header::IF_NONE_MATCH) {
+        Some(if_none_match) => matches_etag(if_none_match, &etag),
+        None => request_headers
+            .get(header::IF_MODIFIED_SINCE)
+            .and_then(parse_http_date)
+            .is_some_and(|since| last_modified.timestamp() <= since.timestamp()),
+    };
+
+    let headers = [
+        (header::ETAG, etag),
+        (header::LAST_MODIFIED, last_modified.format(HTTP_DATE).to_string()),
+        (header::CACHE_CONTROL, "no-cache".to_string()),
+        (header::VARY, header::ACCEPT_ENCODING.to_string()),
+    ];
+
+    if not_modified {
+        return Ok((StatusCode::NOT_MODIFIED, headers).into_response());
+    }
+
+    Ok((headers, [(header::CONTENT_TYPE, "application/json")], bytes).into_response())
+}
+
+/// Collapses repeated `Vary` values into one header, so a response that
+/// named `Accept-Encoding` itself doesn't list it again after compression.
+pub async fn merge_vary(mut response: Response) -> Response {
+    if response.headers().get_all(header::VARY).iter().count() < 2 {
+        return response;
+    }
+
+    let mut names: Vec<&str> = Vec::new();
+    for value in response.headers().get_all(header::VARY) {
+        let Ok(value) = value.to_str() else {
+            return response;
+        };
+        for name in value.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+            if !names.iter().any(|seen| seen.eq_ignore_ascii_case(name)) {
+                names.push(name);
+            }
+        }
+    }
+
+    if let Ok(vary) = HeaderValue::from_str(&names.join(", ")) {
+        response.headers_mut().insert(header::VARY, vary);
+    }
+
+    response
+}
+
+fn etag(bytes: &[u8]) -> String {
+    let hex: String = Sha256::digest(bytes).iter().map(|byte| format!("{byte:02x}")).collect();
+
+    format!("\"{hex}\"")
+}
+
+/// Whether `If-None-Match` lists `etag` or is `*`. Weak tags compare equal
+/// to their strong form, as GET uses weak comparison.
+fn matches_etag(if_none_match: &HeaderValue, etag: &str) -> bool {
+    let Ok(if_none_match) = if_none_match.to_str() else {
+        return false;
+    };
+
+    if_none_match
+        .split(',')
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == etag)
+}
+
+fn parse_http_date(value: &HeaderValue) -> Option<DateTime<Utc>> {
+    let value = value.to_str().ok()?;
+
+    DateTime::parse_from_rfc2822(value).ok().map(|date| date.with_timezone(&Utc))
+}
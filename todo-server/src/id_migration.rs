@@ -0,0 +1,76 @@
+//! One-off rewrite of existing item ids to time-ordered v7 UUIDs.
+//!
+//! Switching `ID_SCHEME` to `uuidv7` only affects new rows. Running
+//! [`migrate_item_ids`] (or the `migrate_ids` binary) once afterwards gives
+//! older items v7 ids derived from their `created_at`, so the whole table sorts
+//! by creation time. References from subtasks, reminders, attachments,
+//! comments and the audit log are rewritten in the same transaction.
+
+use chrono::{DateTime, Utc};
+use sqlx::sqlite::SqlitePool;
+use uuid::{NoContext, Timestamp, Uuid};
+
+use crate::db_trace;
+use crate::modules::audit::audit_service;
+
+/// Columns that point at `items.id`.
+const ITEM_REFERENCES: [&str; 4] = [
+    "UPDATE items SET parent_id = ? WHERE parent_id = ?",
+    "UPDATE reminders SET item_id = ? WHERE item_id = ?",
+    "UPDATE attachments SET item_id = ? WHERE item_id = ?",
+    "UPDATE comments SET item_id = ? WHERE item_id = ?",
+];
+
+/// Gives every item whose id is not already a v7 UUID a new one, and returns
+/// how many were rewritten. Safe to run more than once.
+pub async fn migrate_item_ids(pool: &SqlitePool) -> Result<usize, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    // Children briefly point at the old id; check the keys at commit instead.
+    db_trace::query("PRAGMA defer_foreign_keys = ON").execute(&mut *tx).await?;
+
+    let items: Vec<(String, DateTime<Utc>)> = db_trace::query_as("SELECT id, created_at FROM items ORDER BY created_at, id")
+        .fetch_all(&mut *tx)
+        .await?;
+
+    let mut migrated = 0;
+    for (old_id, created_at) in items {
+        if is_v7(&old_id) {
+            continue;
+        }
+        let new_id = v7_at(created_at);
+
+        db_trace::query("UPDATE items SET id = ? WHERE id = ?")
+            .bind(&new_id)
+            .bind(&old_id)
+            .execute(&mut *tx)
+            .await?;
+        for sql in ITEM_REFERENCES {
+            db_trace::query(sql).bind(&new_id).bind(&old_id).execute(&mut *tx).await?;
+        }
+        db_trace::query("UPDATE audit_log SET entity_id = ? WHERE entity_type = ? AND entity_id = ?")
+            .bind(&new_id)
+            .bind(audit_service::ITEM)
+            .bind(&old_id)
+            .execute(&mut *tx)
+            .await?;
+
+        migrated += 1;
+    }
+
+    tx.commit().await?;
+
+    Ok(migrated)
+}
+
+fn is_v7(id: &str) -> bool {
+    Uuid::parse_str(id).is_ok_and(|uuid| uuid.get_version_num() == 7)
+}
+
+/// A v7 UUID whose timestamp is `at`; rows created before 1970 get the epoch.
+fn v7_at(at: DateTime<Utc>) -> String {
+    let seconds = u64::try_from(at.timestamp()).unwrap_or(0);
+    let timestamp = Timestamp::from_unix(NoContext, seconds, at.timestamp_subsec_nanos());
+
+    Uuid::new_v7(timestamp).to_string()
+}
@@ -0,0 +1,52 @@
+use axum::extract::DefaultBodyLimit;
+use axum::middleware;
+use axum::Router;
+use sqlx::SqlitePool;
+use tower_http::compression::CompressionLayer;
+use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
+use tower_http::trace::TraceLayer;
+use crate::modules::status::create_status_routes;
+use crate::modules::status::status_controller::record_request;
+use crate::routing::{create_api_routes, create_legacy_routes};
+use crate::state::AppState;
+
+pub use crate::builder::AppBuilder;
+pub use todo_core::{clock, id_generator};
+
+pub mod builder;
+pub mod config;
+pub mod db;
+pub mod db_error;
+pub mod db_maintenance;
+pub mod db_trace;
+pub mod demo;
+pub mod error;
+pub mod extract;
+pub mod grpc;
+pub mod http_cache;
+pub mod id_migration;
+pub mod modules;
+pub mod pagination;
+pub mod routing;
+pub mod smoke;
+pub mod state;
+pub mod telemetry;
+
+pub fn create_app(pool: SqlitePool) -> Router {
+    create_app_with_state(AppState::new(pool))
+}
+
+pub fn create_app_with_state(state: AppState) -> Router {
+    Router::new()
+        .nest("/api", create_api_routes())
+        .merge(create_legacy_routes())
+        .merge(create_status_routes())
+        .layer(middleware::from_fn_with_state(state.clone(), record_request))
+        .layer(DefaultBodyLimit::max(state.config.max_body_bytes))
+        .layer(CompressionLayer::new())
+        .layer(middleware::map_response(http_cache::merge_vary))
+        .layer(PropagateRequestIdLayer::x_request_id())
+        .layer(TraceLayer::new_for_http().make_span_with(telemetry::request_span))
+        .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
+        .with_state(state)
+}
@@ -0,0 +1,88 @@
+use std::net::SocketAddr;
+
+use todo_server::config::AppConfig;
+use todo_server::create_app_with_state;
+use todo_server::db::{init_db, init_writer_pool};
+use todo_server::db_maintenance::MaintenanceConfig;
+use todo_server::demo::{Demo, DemoConfig};
+use todo_server::grpc::grpc_routes;
+use todo_server::modules::reminders::reminder_worker::ReminderWorkerConfig;
+use todo_server::modules::todos::todo_hooks::{ItemHookLimits, ItemHooks};
+use todo_server::telemetry;
+use todo_server::AppBuilder;
+use clap::Parser;
+
+/// Serves the REST and gRPC APIs. Administration tasks are in `todo-cli`.
+#[derive(Parser)]
+#[command(version, about = "Todo API server")]
+struct Args {
+    /// Give every browser session its own throwaway in-memory database
+    /// instead of using `DATABASE_URL`, for a public demo.
+    #[arg(long)]
+    demo: bool,
+}
+
+#[tokio::main]
+async fn main() {
+    let args = Args::parse();
+    let config = AppConfig::from_env();
+    telemetry::init(&config);
+
+    // The demo runs without opening DATABASE_URL
+    if args.demo {
+        serve_demo(config).await;
+    } else {
+        let pool = init_db(&config).await.expect("Failed to initialize the database");
+        serve(pool, config).await;
+    }
+}
+
+async fn serve(pool: sqlx::SqlitePool, config: AppConfig) {
+    let grpc_port = config.grpc_port;
+
+    // Create app state, delivering due reminders and maintaining the database
+    // in the background
+    let mut builder = AppBuilder::new(pool);
+    if config.db_writer_pool {
+        let writer = init_writer_pool(&config).await.expect("Failed to open the writer pool");
+        builder = builder.with_writer_pool(writer);
+    }
+    if let Some(dir) = &config.item_hooks_dir {
+        let limits = ItemHookLimits { timeout: config.item_hook_timeout, ..ItemHookLimits::default() };
+        let hooks = ItemHooks::load_dir(dir, limits).expect("Failed to load item hooks");
+        builder = builder.with_item_hooks(hooks);
+    }
+    let maintenance = MaintenanceConfig::from_config(&config);
+    let state = builder
+        .with_config(config)
+        .with_reminder_worker(ReminderWorkerConfig::default())
+        .with_maintenance(maintenance)
+        .build_state()
+        .await
+        .expect("Failed to build the app");
+
+    // Serve gRPC on its own port when one is configured, otherwise next to
+    // REST on the main one
+    let mut app = create_app_with_state(state.clone());
+    match grpc_port {
+        Some(port) => {
+            let listener = tokio::net::TcpListener::bind(("0.0.0.0", port)).await.unwrap();
+            tokio::spawn(async move { axum::serve(listener, grpc_routes(state)).await.unwrap() });
+        }
+        None => app = app.merge(grpc_routes(state)),
+    }
+
+    // Start server
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:3005").await.unwrap();
+    axum::serve(listener, app).await.unwrap();
+}
+
+async fn serve_demo(config: AppConfig) {
+    let demo = Demo::new(DemoConfig::from_config(&config), config);
+    tokio::spawn(demo.clone().run_sweeper());
+
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:3005").await.unwrap();
+    axum::serve(listener, demo.router().into_make_service_with_connect_info::<SocketAddr>())
+        .await
+        .unwrap();
+}
@@ -0,0 +1,37 @@
+use axum::extract::State;
+
+use crate::error::AppError;
+use crate::extract::{Actor, Json};
+use crate::modules::actions::action_dto::{ActionInfo, ActionResult, RunActionDto};
+use crate::state::AppState;
+
+/// Every registered action with the schema of its params, for clients to
+/// build a command palette from.
+pub async fn list_actions(State(state): State<AppState>) -> Json<Vec<ActionInfo>> {
+    let actions = state
+        .actions
+        .iter()
+        .map(|action| ActionInfo {
+            name: action.name().to_string(),
+            description: action.description().to_string(),
+            params: action.params_schema(),
+        })
+        .collect();
+
+    Json(actions)
+}
+
+pub async fn run_action(
+    State(state): State<AppState>,
+    actor: Actor,
+    Json(payload): Json<RunActionDto>,
+) -> Result<Json<ActionResult>, AppError> {
+    let action = state
+        .actions
+        .get(&payload.action)
+        .ok_or_else(|| AppError::Unprocessable(format!("Unknown action {:?}", payload.action)))?;
+
+    let result = action.run(&state, actor, payload.params).await?;
+
+    Ok(Json(ActionResult { action: payload.action, result }))
+}
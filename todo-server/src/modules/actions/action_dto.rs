@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize)]
+pub struct RunActionDto {
+    pub action: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ActionResult {
+    pub action: String,
+    /// What the action returned, e.g. the changed item; `null` when nothing.
+    pub result: Value,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ActionInfo {
+    pub name: String,
+    pub description: String,
+    /// JSON Schema of `params`.
+    pub params: Value,
+}
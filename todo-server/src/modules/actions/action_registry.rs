@@ -0,0 +1,229 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use crate::error::AppError;
+use crate::extract::Actor;
+use crate::modules::todos::todo_controller;
+use crate::modules::todos::todo_dto::{Patch, UpdateItemDto};
+use crate::modules::todos::todo_entity::Priority;
+use crate::state::AppState;
+
+/// Something a client can do by name through `POST /actions`, such as from
+/// a command palette.
+#[async_trait]
+pub trait Action: Send + Sync {
+    /// Identifier clients send as `action`, e.g. `move`.
+    fn name(&self) -> &'static str;
+
+    /// One line for people picking the action.
+    fn description(&self) -> &'static str;
+
+    /// JSON Schema of the `params` object.
+    fn params_schema(&self) -> Value;
+
+    /// Runs the action. Returns what the client should show, or `null`.
+    async fn run(&self, state: &AppState, actor: Actor, params: Value) -> Result<Value, AppError>;
+}
+
+/// The actions offered by `/actions`, in the order they are listed.
+///
+/// The default registry holds the built-in actions. An embedding app adds its
+/// own with [`ActionRegistry::register`], which also replaces a built-in of
+/// the same name.
+#[derive(Clone)]
+pub struct ActionRegistry {
+    actions: Vec<Arc<dyn Action>>,
+}
+
+impl ActionRegistry {
+    /// A registry without any actions.
+    pub fn empty() -> Self {
+        Self { actions: Vec::new() }
+    }
+
+    pub fn register(&mut self, action: Arc<dyn Action>) {
+        self.actions.retain(|existing| existing.name() != action.name());
+        self.actions.push(action);
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn Action>> {
+        self.actions.iter().find(|action| action.name() == name).cloned()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<dyn Action>> {
+        self.actions.iter()
+    }
+}
+
+impl Default for ActionRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register(Arc::new(RenameAction));
+        registry.register(Arc::new(SetPriorityAction));
+        registry.register(Arc::new(MoveAction));
+        registry.register(Arc::new(DeleteAction));
+        registry
+    }
+}
+
+/// Reads `params` into the action's own type; a mismatch is the client's
+/// fault.
+pub fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, AppError> {
+    serde_json::from_value(params).map_err(|err| AppError::Unprocessable(format!("Invalid params: {err}")))
+}
+
+fn to_value(value: impl serde::Serialize) -> Result<Value, AppError> {
+    serde_json::to_value(value).map_err(|err| AppError::Internal(err.to_string()))
+}
+
+const ID_DESCRIPTION: &str = "Id of the item";
+
+pub struct RenameAction;
+
+#[derive(Deserialize)]
+struct RenameParams {
+    id: String,
+    name: String,
+}
+
+#[async_trait]
+impl Action for RenameAction {
+    fn name(&self) -> &'static str {
+        "rename"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rename an item"
+    }
+
+    fn params_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "id": { "type": "string", "description": ID_DESCRIPTION },
+                "name": { "type": "string" },
+            },
+            "required": ["id", "name"],
+        })
+    }
+
+    async fn run(&self, state: &AppState, actor: Actor, params: Value) -> Result<Value, AppError> {
+        let params: RenameParams = parse_params(params)?;
+        let dto = UpdateItemDto { name: Some(params.name), description: Patch::Missing, priority: None, parent_id: Patch::Missing };
+        todo_controller::update(state, actor, params.id.clone(), dto).await?;
+
+        to_value(todo_controller::fetch(state, params.id).await?)
+    }
+}
+
+pub struct SetPriorityAction;
+
+#[derive(Deserialize)]
+struct SetPriorityParams {
+    id: String,
+    priority: Priority,
+}
+
+#[async_trait]
+impl Action for SetPriorityAction {
+    fn name(&self) -> &'static str {
+        "set_priority"
+    }
+
+    fn description(&self) -> &'static str {
+        "Change an item's priority"
+    }
+
+    fn params_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "id": { "type": "string", "description": ID_DESCRIPTION },
+                "priority": { "type": "string", "enum": ["low", "medium", "high"] },
+            },
+            "required": ["id", "priority"],
+        })
+    }
+
+    async fn run(&self, state: &AppState, actor: Actor, params: Value) -> Result<Value, AppError> {
+        let params: SetPriorityParams = parse_params(params)?;
+        let dto = UpdateItemDto { name: None, description: Patch::Missing, priority: Some(params.priority), parent_id: Patch::Missing };
+        todo_controller::update(state, actor, params.id.clone(), dto).await?;
+
+        to_value(todo_controller::fetch(state, params.id).await?)
+    }
+}
+
+pub struct MoveAction;
+
+#[derive(Deserialize)]
+struct MoveParams {
+    id: String,
+    position: i64,
+}
+
+#[async_trait]
+impl Action for MoveAction {
+    fn name(&self) -> &'static str {
+        "move"
+    }
+
+    fn description(&self) -> &'static str {
+        "Move an item to a position in the manual ordering"
+    }
+
+    fn params_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "id": { "type": "string", "description": ID_DESCRIPTION },
+                "position": { "type": "integer", "minimum": 0 },
+            },
+            "required": ["id", "position"],
+        })
+    }
+
+    async fn run(&self, state: &AppState, actor: Actor, params: Value) -> Result<Value, AppError> {
+        let params: MoveParams = parse_params(params)?;
+
+        to_value(todo_controller::reposition(state, actor, params.id, params.position).await?)
+    }
+}
+
+pub struct DeleteAction;
+
+#[derive(Deserialize)]
+struct DeleteParams {
+    id: String,
+}
+
+#[async_trait]
+impl Action for DeleteAction {
+    fn name(&self) -> &'static str {
+        "delete"
+    }
+
+    fn description(&self) -> &'static str {
+        "Delete an item with its subtasks"
+    }
+
+    fn params_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "id": { "type": "string", "description": ID_DESCRIPTION },
+            },
+            "required": ["id"],
+        })
+    }
+
+    async fn run(&self, state: &AppState, actor: Actor, params: Value) -> Result<Value, AppError> {
+        let params: DeleteParams = parse_params(params)?;
+        todo_controller::remove(state, actor, params.id).await?;
+
+        Ok(Value::Null)
+    }
+}
@@ -0,0 +1,14 @@
+use axum::Router;
+use axum::routing::get;
+use crate::modules::actions::action_controller::{list_actions, run_action};
+use crate::state::AppState;
+
+pub mod action_controller;
+pub mod action_dto;
+pub mod action_registry;
+
+
+pub fn create_action_routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_actions).post(run_action))
+}
@@ -0,0 +1,41 @@
+use axum::extract::State;
+
+use sqlx::sqlite::SqlitePool;
+use crate::error::AppError;
+use crate::extract::Json;
+use crate::modules::admin::admin_dto::{ConsistencyReport, StorageReport};
+use crate::modules::admin::admin_service;
+use crate::modules::todos::todo_cache::CacheStats;
+use crate::state::AppState;
+
+pub async fn storage_report(
+    State(pool): State<SqlitePool>,
+) -> Result<Json<StorageReport>, AppError> {
+    let report = admin_service::storage_report(&pool).await?;
+
+    Ok(Json(report))
+}
+
+/// Hits and misses of the item cache since startup.
+pub async fn cache_report(
+    State(state): State<AppState>,
+) -> Json<CacheStats> {
+    Json(state.item_cache.stats())
+}
+
+pub async fn consistency_report(
+    State(state): State<AppState>,
+) -> Result<Json<ConsistencyReport>, AppError> {
+    let report = admin_service::check_consistency(&state.pool, state.storage.as_ref(), false).await?;
+
+    Ok(Json(report))
+}
+
+pub async fn repair_consistency(
+    State(state): State<AppState>,
+) -> Result<Json<ConsistencyReport>, AppError> {
+    let report = admin_service::check_consistency(&state.writer, state.storage.as_ref(), true).await?;
+    state.item_cache.invalidate_all();
+
+    Ok(Json(report))
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct StorageReport {
+    /// Size of the main database file: every allocated page, used or not.
+    pub database_bytes: i64,
+    /// Part of `database_bytes` in free pages, reclaimable by a vacuum.
+    pub free_bytes: i64,
+    /// Size of the write-ahead log; `None` when the database has none.
+    pub wal_bytes: Option<u64>,
+    /// Largest tables first.
+    pub tables: Vec<TableUsage>,
+    pub attachments: AttachmentUsage,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TableUsage {
+    pub name: String,
+    pub rows: i64,
+    pub table_bytes: i64,
+    /// Space used by the table's indexes.
+    pub index_bytes: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AttachmentUsage {
+    pub count: i64,
+    pub bytes: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ConsistencyReport {
+    /// Rows whose foreign key points at a row that no longer exists.
+    pub dangling_references: Vec<DanglingReference>,
+    /// Ids of attachments whose file is missing from storage.
+    pub missing_attachment_files: Vec<String>,
+    /// Whether the problems listed were repaired.
+    pub repaired: bool,
+}
+
+impl ConsistencyReport {
+    pub fn is_consistent(&self) -> bool {
+        self.dangling_references.is_empty() && self.missing_attachment_files.is_empty()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct DanglingReference {
+    pub table: String,
+    pub id: String,
+    /// The table the missing row belonged to.
+    pub references: String,
+}
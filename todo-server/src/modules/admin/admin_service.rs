@@ -0,0 +1,128 @@
+use std::path::Path;
+
+use sqlx::sqlite::SqlitePool;
+use crate::db_trace;
+use crate::modules::admin::admin_dto::{AttachmentUsage, ConsistencyReport, DanglingReference, StorageReport, TableUsage};
+use crate::modules::attachments::attachment_storage::AttachmentStorage;
+
+pub async fn storage_report(pool: &SqlitePool) -> Result<StorageReport, sqlx::Error> {
+    let (page_size,): (i64,) = db_trace::query_as("PRAGMA page_size").fetch_one(pool).await?;
+    let (page_count,): (i64,) = db_trace::query_as("PRAGMA page_count").fetch_one(pool).await?;
+    let (freelist_count,): (i64,) = db_trace::query_as("PRAGMA freelist_count").fetch_one(pool).await?;
+
+    // `file` is empty for in-memory databases.
+    let (_, _, file): (i64, String, String) = db_trace::query_as("PRAGMA database_list").fetch_one(pool).await?;
+    let wal_bytes = if file.is_empty() {
+        None
+    } else {
+        tokio::fs::metadata(Path::new(&format!("{file}-wal"))).await.ok().map(|metadata| metadata.len())
+    };
+
+    let (count, bytes): (i64, i64) = db_trace::query_as("SELECT COUNT(*), COALESCE(SUM(size_bytes), 0) FROM attachments")
+        .fetch_one(pool)
+        .await?;
+
+    Ok(StorageReport {
+        database_bytes: page_size * page_count,
+        free_bytes: page_size * freelist_count,
+        wal_bytes,
+        tables: table_usage(pool).await?,
+        attachments: AttachmentUsage { count, bytes },
+    })
+}
+
+async fn table_usage(pool: &SqlitePool) -> Result<Vec<TableUsage>, sqlx::Error> {
+    // `dbstat` with `aggregate` reports one row per table or index.
+    let sizes: Vec<(String, i64, i64)> = db_trace::query_as(
+        "SELECT schema.tbl_name, \
+                COALESCE(SUM(CASE WHEN schema.type = 'table' THEN stat.pgsize END), 0), \
+                COALESCE(SUM(CASE WHEN schema.type = 'index' THEN stat.pgsize END), 0) \
+         FROM dbstat AS stat JOIN sqlite_schema AS schema ON schema.name = stat.name \
+         WHERE stat.aggregate = TRUE AND schema.tbl_name NOT LIKE 'sqlite_%' \
+         GROUP BY schema.tbl_name",
+    )
+        .fetch_all(pool)
+        .await?;
+
+    let mut tables = Vec::with_capacity(sizes.len());
+    for (name, table_bytes, index_bytes) in sizes {
+        // Names come from sqlite_schema, but quote them anyway.
+        let count = format!("SELECT COUNT(*) FROM \"{}\"", name.replace('"', "\"\""));
+        let (rows,): (i64,) = db_trace::query_as(&count).fetch_one(pool).await?;
+
+        tables.push(TableUsage { name, rows, table_bytes, index_bytes });
+    }
+    tables.sort_by(|a, b| (b.table_bytes + b.index_bytes).cmp(&(a.table_bytes + a.index_bytes)).then_with(|| a.name.cmp(&b.name)));
+
+    Ok(tables)
+}
+
+/// Looks for rows whose foreign key target is gone and for attachments whose
+/// file is missing. Foreign keys are enforced, so these only appear when the
+/// database was written with enforcement off or files were removed by hand.
+///
+/// With `repair`, in one transaction, subtasks of a missing parent become
+/// top-level items and every other broken row is deleted. Files of deleted
+/// attachment rows are removed afterwards.
+pub async fn check_consistency(pool: &SqlitePool, storage: &dyn AttachmentStorage, repair: bool) -> Result<ConsistencyReport, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let violations: Vec<(String, i64, String)> = db_trace::query_as(
+        "SELECT \"table\", rowid, parent FROM pragma_foreign_key_check() WHERE rowid IS NOT NULL ORDER BY \"table\", rowid",
+    )
+        .fetch_all(&mut *tx)
+        .await?;
+
+    let mut dangling_references = Vec::with_capacity(violations.len());
+    for (table, rowid, parent) in violations {
+        // Names come from the schema, but quote them anyway.
+        let table_name = format!("\"{}\"", table.replace('"', "\"\""));
+        let (id,): (String,) = db_trace::query_as(&format!("SELECT id FROM {table_name} WHERE rowid = ?"))
+            .bind(rowid)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        if repair {
+            let fix = if table == "items" {
+                "UPDATE items SET parent_id = NULL WHERE rowid = ?".to_string()
+            } else {
+                format!("DELETE FROM {table_name} WHERE rowid = ?")
+            };
+            db_trace::query(&fix).bind(rowid).execute(&mut *tx).await?;
+        }
+
+        dangling_references.push(DanglingReference { table, id, references: parent });
+    }
+
+    let attachments: Vec<(String,)> = db_trace::query_as("SELECT id FROM attachments ORDER BY id")
+        .fetch_all(&mut *tx)
+        .await?;
+
+    let mut missing_attachment_files = Vec::new();
+    for (id,) in attachments {
+        match storage.exists(&id).await {
+            Ok(true) => {}
+            Ok(false) => missing_attachment_files.push(id),
+            Err(err) => tracing::warn!("Failed to look up the file of attachment {id}: {err}"),
+        }
+    }
+
+    if repair {
+        for id in &missing_attachment_files {
+            db_trace::query("DELETE FROM attachments WHERE id = ?").bind(id).execute(&mut *tx).await?;
+        }
+    }
+
+    tx.commit().await?;
+
+    if repair {
+        let deleted_attachments = dangling_references.iter().filter(|reference| reference.table == "attachments");
+        for reference in deleted_attachments {
+            if let Err(err) = storage.delete(&reference.id).await {
+                tracing::warn!("Failed to delete file of attachment {}: {err}", reference.id);
+            }
+        }
+    }
+
+    Ok(ConsistencyReport { dangling_references, missing_attachment_files, repaired: repair })
+}
@@ -0,0 +1,18 @@
+use axum::Router;
+use axum::routing::{get, post};
+use crate::modules::admin::admin_controller::{cache_report, consistency_report, repair_consistency, storage_report};
+use crate::state::AppState;
+
+pub mod admin_controller;
+pub mod admin_service;
+pub mod admin_dto;
+
+
+/// Operator endpoints, nested under `/admin`.
+pub fn create_admin_routes() -> Router<AppState> {
+    Router::new()
+        .route("/storage", get(storage_report))
+        .route("/cache", get(cache_report))
+        .route("/consistency", get(consistency_report))
+        .route("/consistency/repair", post(repair_consistency))
+}
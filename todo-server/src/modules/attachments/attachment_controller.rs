@@ -0,0 +1,133 @@
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+
+use sqlx::sqlite::SqlitePool;
+use crate::config::AppConfig;
+use crate::error::AppError;
+use crate::extract::{Actor, Json, Multipart};
+use crate::modules::attachments::attachment_dto::CreateAttachmentDto;
+use crate::modules::attachments::attachment_entity::Attachment;
+use crate::modules::attachments::attachment_service;
+use crate::modules::audit::audit_service::AuditContext;
+use crate::modules::todos::todo_service;
+use crate::state::AppState;
+
+/// Name of the multipart field carrying the file.
+const FILE_FIELD: &str = "file";
+
+pub async fn upload_attachment(
+    State(state): State<AppState>,
+    actor: Actor,
+    Path(id): Path<String>,
+    Multipart(mut multipart): Multipart,
+) -> Result<Json<Attachment>, AppError> {
+    todo_service::get_item(&state.pool, id.clone()).await?;
+
+    let (dto, bytes) = read_file_field(&mut multipart, &state.config).await?;
+    let audit = AuditContext::new(&state, actor);
+    let attachment = attachment_service::create_attachment(&state.writer, &audit, id, dto).await?;
+
+    if let Err(err) = state.storage.put(&attachment.id, bytes).await {
+        attachment_service::delete_attachment(&state.writer, &audit, attachment.item_id, attachment.id).await?;
+        return Err(AppError::Internal(format!("Failed to store attachment: {err}")));
+    }
+
+    Ok(Json(attachment))
+}
+
+pub async fn list_attachments(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<Attachment>>, AppError> {
+    todo_service::get_item(&pool, id.clone()).await?;
+
+    let attachments = attachment_service::list_attachments(&pool, id).await?;
+
+    Ok(Json(attachments))
+}
+
+pub async fn download_attachment(
+    State(state): State<AppState>,
+    Path((id, attachment_id)): Path<(String, String)>,
+) -> Result<Response, AppError> {
+    let attachment = attachment_service::get_attachment(&state.pool, id, attachment_id).await?;
+    let bytes = state.storage
+        .get(&attachment.id)
+        .await
+        .map_err(|err| AppError::Internal(format!("Failed to read attachment {}: {err}", attachment.id)))?;
+
+    let headers = [
+        (header::CONTENT_TYPE, attachment.content_type),
+        (header::CONTENT_DISPOSITION, content_disposition(&attachment.file_name)),
+    ];
+
+    Ok((headers, bytes).into_response())
+}
+
+pub async fn delete_attachment(
+    State(state): State<AppState>,
+    actor: Actor,
+    Path((id, attachment_id)): Path<(String, String)>,
+) -> Result<StatusCode, AppError> {
+    // Only a row of this item may take a file with it.
+    attachment_service::delete_attachment(&state.writer, &AuditContext::new(&state, actor), id, attachment_id.clone()).await?;
+
+    state.storage
+        .delete(&attachment_id)
+        .await
+        .map_err(|err| AppError::Internal(format!("Failed to delete attachment {attachment_id}: {err}")))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Reads the `file` field, enforcing the configured size and MIME type limits
+/// while streaming so oversized uploads are never fully buffered.
+async fn read_file_field(multipart: &mut axum::extract::Multipart, config: &AppConfig) -> Result<(CreateAttachmentDto, Bytes), AppError> {
+    while let Some(mut field) = multipart.next_field().await? {
+        if field.name() != Some(FILE_FIELD) {
+            continue;
+        }
+
+        let content_type = field
+            .content_type()
+            .and_then(|content_type| content_type.split(';').next())
+            .unwrap_or("application/octet-stream")
+            .trim()
+            .to_ascii_lowercase();
+        if !config.allowed_attachment_types.contains(&content_type) {
+            return Err(AppError::UnsupportedMediaType(format!("Attachments of type {content_type} are not allowed")));
+        }
+
+        let file_name = field.file_name().unwrap_or("attachment").to_string();
+        let mut bytes = Vec::new();
+        while let Some(chunk) = field.chunk().await? {
+            if bytes.len() + chunk.len() > config.max_attachment_bytes {
+                return Err(AppError::PayloadTooLarge);
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+
+        let dto = CreateAttachmentDto {
+            file_name,
+            content_type,
+            size_bytes: bytes.len() as i64,
+        };
+
+        return Ok((dto, Bytes::from(bytes)));
+    }
+
+    Err(AppError::BadRequest(format!("Missing multipart field `{FILE_FIELD}`")))
+}
+
+fn content_disposition(file_name: &str) -> String {
+    let safe: String = file_name
+        .chars()
+        .map(|c| if c.is_ascii_graphic() && c != '"' && c != '\\' || c == ' ' { c } else { '_' })
+        .collect();
+
+    format!("attachment; filename=\"{safe}\"")
+}
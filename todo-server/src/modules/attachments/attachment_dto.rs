@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct CreateAttachmentDto {
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+}
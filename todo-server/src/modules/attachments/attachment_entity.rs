@@ -0,0 +1,16 @@
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use sqlx::FromRow;
+
+/// Metadata of a stored file. The bytes live in an
+/// [`AttachmentStorage`](crate::modules::attachments::attachment_storage::AttachmentStorage)
+/// under the attachment's id.
+#[derive(Serialize, Deserialize, FromRow, Clone)]
+pub struct Attachment {
+    pub id: String,
+    pub item_id: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub created_at: DateTime<Utc>,
+}
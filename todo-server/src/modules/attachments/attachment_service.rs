@@ -0,0 +1,87 @@
+use sqlx::sqlite::{SqliteConnection, SqlitePool};
+use sqlx::types::Json;
+use crate::db_trace;
+use crate::modules::attachments::attachment_dto::CreateAttachmentDto;
+use crate::modules::attachments::attachment_entity::Attachment;
+use crate::modules::audit::audit_entity::AuditAction;
+use crate::modules::audit::audit_service::{self, AuditContext};
+
+pub async fn create_attachment(pool: &SqlitePool, audit: &AuditContext<'_>, item_id: String, dto: CreateAttachmentDto) -> Result<Attachment, sqlx::Error> {
+    let attachment = Attachment {
+        id: audit.ids.next_id(),
+        item_id,
+        file_name: dto.file_name,
+        content_type: dto.content_type,
+        size_bytes: dto.size_bytes,
+        created_at: audit.now,
+    };
+
+    let mut tx = pool.begin().await?;
+    db_trace::query("INSERT INTO attachments (id, item_id, file_name, content_type, size_bytes, created_at) VALUES (?, ?, ?, ?, ?, ?)")
+        .bind(&attachment.id)
+        .bind(&attachment.item_id)
+        .bind(&attachment.file_name)
+        .bind(&attachment.content_type)
+        .bind(attachment.size_bytes)
+        .bind(attachment.created_at)
+        .execute(&mut *tx)
+        .await?;
+
+    record(&mut tx, audit, AuditAction::Create, None, Some(&attachment)).await?;
+    tx.commit().await?;
+
+    Ok(attachment)
+}
+
+pub async fn list_attachments(pool: &SqlitePool, item_id: String) -> Result<Vec<Attachment>, sqlx::Error> {
+    let attachments = db_trace::query_as("SELECT * FROM attachments WHERE item_id = ? ORDER BY created_at, id")
+        .bind(item_id)
+        .fetch_all(pool)
+        .await?;
+
+    Ok(attachments)
+}
+
+pub async fn get_attachment(pool: &SqlitePool, item_id: String, id: String) -> Result<Attachment, sqlx::Error> {
+    let attachment = db_trace::query_as("SELECT * FROM attachments WHERE id = ? AND item_id = ?")
+        .bind(id)
+        .bind(item_id)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(attachment)
+}
+
+/// Fails with [`sqlx::Error::RowNotFound`] when `item_id` has no attachment
+/// `id`, so the caller knows not to touch the stored file.
+pub async fn delete_attachment(pool: &SqlitePool, audit: &AuditContext<'_>, item_id: String, id: String) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let existing_attachment: Attachment = db_trace::query_as("DELETE FROM attachments WHERE id = ? AND item_id = ? RETURNING *")
+        .bind(id)
+        .bind(item_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+
+    record(&mut tx, audit, AuditAction::Delete, Some(&existing_attachment), None).await?;
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// The attachments of any of `item_ids`, inside an open transaction.
+pub async fn fetch_for_items(conn: &mut SqliteConnection, item_ids: &[String]) -> Result<Vec<Attachment>, sqlx::Error> {
+    db_trace::query_as("SELECT * FROM attachments WHERE item_id IN (SELECT value FROM json_each(?)) ORDER BY created_at, id")
+        .bind(Json(item_ids))
+        .fetch_all(conn)
+        .await
+}
+
+/// Records one change to an attachment's row in the audit log.
+pub async fn record(conn: &mut SqliteConnection, audit: &AuditContext<'_>, action: AuditAction, before: Option<&Attachment>, after: Option<&Attachment>) -> Result<(), sqlx::Error> {
+    let Some(id) = before.or(after).map(|attachment| attachment.id.as_str()) else {
+        return Ok(());
+    };
+
+    audit_service::record(conn, audit, audit_service::ATTACHMENT, id, action, before, after).await
+}
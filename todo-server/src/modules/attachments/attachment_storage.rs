@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use axum::body::Bytes;
+
+/// Where attachment bytes are kept, keyed by attachment id.
+///
+/// Only local disk is implemented; an object store such as S3 can be added
+/// as another implementation without touching the handlers.
+#[async_trait]
+pub trait AttachmentStorage: Send + Sync {
+    async fn put(&self, key: &str, bytes: Bytes) -> io::Result<()>;
+
+    async fn get(&self, key: &str) -> io::Result<Bytes>;
+
+    /// Removing a key that does not exist is not an error.
+    async fn delete(&self, key: &str) -> io::Result<()>;
+
+    async fn exists(&self, key: &str) -> io::Result<bool>;
+}
+
+/// Stores each attachment as a file named after its key under `root`.
+pub struct LocalDiskStorage {
+    root: PathBuf,
+}
+
+impl LocalDiskStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, key: &str) -> io::Result<PathBuf> {
+        // Keys are generated ids; anything else could escape `root`.
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("invalid storage key {key:?}")));
+        }
+
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl AttachmentStorage for LocalDiskStorage {
+    async fn put(&self, key: &str, bytes: Bytes) -> io::Result<()> {
+        let path = self.path(key)?;
+        let partial = path.with_extension("partial");
+
+        tokio::fs::create_dir_all(&self.root).await?;
+        tokio::fs::write(&partial, &bytes).await?;
+        tokio::fs::rename(&partial, &path).await
+    }
+
+    async fn get(&self, key: &str) -> io::Result<Bytes> {
+        tokio::fs::read(self.path(key)?).await.map(Bytes::from)
+    }
+
+    async fn delete(&self, key: &str) -> io::Result<()> {
+        match tokio::fs::remove_file(self.path(key)?).await {
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+
+    async fn exists(&self, key: &str) -> io::Result<bool> {
+        tokio::fs::try_exists(self.path(key)?).await
+    }
+}
+
+/// Keeps attachments in memory. Meant for tests.
+#[derive(Default)]
+pub struct InMemoryStorage {
+    files: Mutex<HashMap<String, Bytes>>,
+}
+
+impl InMemoryStorage {
+    pub fn contains(&self, key: &str) -> bool {
+        self.files.lock().unwrap().contains_key(key)
+    }
+}
+
+#[async_trait]
+impl AttachmentStorage for InMemoryStorage {
+    async fn put(&self, key: &str, bytes: Bytes) -> io::Result<()> {
+        self.files.lock().unwrap().insert(key.to_string(), bytes);
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> io::Result<Bytes> {
+        self.files
+            .lock()
+            .unwrap()
+            .get(key)
+            .cloned()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("no attachment stored under {key:?}")))
+    }
+
+    async fn delete(&self, key: &str) -> io::Result<()> {
+        self.files.lock().unwrap().remove(key);
+        Ok(())
+    }
+
+    async fn exists(&self, key: &str) -> io::Result<bool> {
+        Ok(self.contains(key))
+    }
+}
@@ -0,0 +1,22 @@
+use axum::Router;
+use axum::extract::DefaultBodyLimit;
+use axum::routing::{get, post};
+use crate::modules::attachments::attachment_controller::{delete_attachment, download_attachment, list_attachments, upload_attachment};
+use crate::state::AppState;
+
+pub mod attachment_controller;
+pub mod attachment_service;
+pub mod attachment_entity;
+pub mod attachment_dto;
+pub mod attachment_storage;
+
+
+/// Routes nested under `/items`, alongside the item routes.
+///
+/// Uploads bypass the global body limit; `max_attachment_bytes` is enforced
+/// by the handler instead.
+pub fn create_attachment_routes() -> Router<AppState> {
+    Router::new()
+        .route("/:id/attachments", post(upload_attachment).layer(DefaultBodyLimit::disable()).get(list_attachments))
+        .route("/:id/attachments/:attachment_id", get(download_attachment).delete(delete_attachment))
+}
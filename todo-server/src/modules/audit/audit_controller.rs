@@ -0,0 +1,26 @@
+use axum::extract::{Path, State};
+
+use sqlx::sqlite::SqlitePool;
+use crate::error::AppError;
+use crate::extract::{Json, ValidatedQuery};
+use crate::modules::audit::audit_entity::AuditEntry;
+use crate::modules::audit::audit_service;
+use crate::modules::todos::todo_service;
+use crate::pagination::{self, CreatedAtCursor, PageQuery, Paginated};
+
+pub async fn item_history(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<String>,
+    ValidatedQuery(query): ValidatedQuery<PageQuery>,
+) -> Result<Json<Paginated<AuditEntry>>, AppError> {
+    let after = pagination::parse_cursor::<CreatedAtCursor>(query.cursor.as_deref())?;
+    let first_page = after.is_none();
+    let page = audit_service::list_history(&pool, audit_service::ITEM, id.clone(), query.limit, after).await?;
+
+    // Deleted items keep their history; only ids never seen at all are 404s.
+    if first_page && page.items.is_empty() {
+        todo_service::get_item(&pool, id).await?;
+    }
+
+    Ok(Json(page))
+}
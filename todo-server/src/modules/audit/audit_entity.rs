@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use sqlx::types::Json;
+use sqlx::FromRow;
+
+#[derive(Serialize, Deserialize, FromRow, Clone)]
+pub struct AuditEntry {
+    pub id: String,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub action: AuditAction,
+    pub actor: String,
+    /// The `x-request-id` of the request that made the change; `None` for
+    /// changes made outside HTTP, such as from the command line.
+    pub request_id: Option<String>,
+    /// The entity as it was before the change; `None` for creates.
+    pub before: Option<Json<Value>>,
+    /// The entity as it was after the change; `None` for deletes.
+    pub after: Option<Json<Value>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, sqlx::Type, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(rename_all = "lowercase")]
+pub enum AuditAction {
+    Create,
+    Update,
+    Move,
+    Delete,
+}
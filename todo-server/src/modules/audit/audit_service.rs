@@ -0,0 +1,100 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::sqlite::{SqliteConnection, SqlitePool};
+use crate::db_trace;
+use crate::extract::Actor;
+use crate::id_generator::IdGenerator;
+use crate::modules::audit::audit_entity::{AuditAction, AuditEntry};
+use crate::pagination::{CreatedAtCursor, Paginated};
+use crate::state::AppState;
+
+pub const ITEM: &str = "item";
+pub const COMMENT: &str = "comment";
+pub const ATTACHMENT: &str = "attachment";
+pub const REMINDER: &str = "reminder";
+
+/// Who is making a change, under which request, and when. Passed to every
+/// mutating service so the audit entry can be written in the same
+/// transaction as the change.
+pub struct AuditContext<'a> {
+    pub ids: &'a dyn IdGenerator,
+    pub actor: Actor,
+    pub now: DateTime<Utc>,
+}
+
+impl<'a> AuditContext<'a> {
+    /// Context for a change made now by `actor`, using the app's id generator
+    /// and clock.
+    pub fn new(state: &'a AppState, actor: Actor) -> Self {
+        Self {
+            ids: state.ids.as_ref(),
+            actor,
+            now: state.clock.now(),
+        }
+    }
+}
+
+/// Records one change to `entity_type`/`entity_id`. Call it on the
+/// mutation's own transaction so the entry commits or rolls back with it.
+pub async fn record<T: Serialize + Sync>(
+    conn: &mut SqliteConnection,
+    ctx: &AuditContext<'_>,
+    entity_type: &str,
+    entity_id: &str,
+    action: AuditAction,
+    before: Option<&T>,
+    after: Option<&T>,
+) -> Result<(), sqlx::Error> {
+    db_trace::query(
+        "INSERT INTO audit_log (id, entity_type, entity_id, action, actor, request_id, before, after, created_at) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+        .bind(ctx.ids.next_id())
+        .bind(entity_type)
+        .bind(entity_id)
+        .bind(action)
+        .bind(&ctx.actor.name)
+        .bind(&ctx.actor.request_id)
+        .bind(before.map(to_json).transpose()?)
+        .bind(after.map(to_json).transpose()?)
+        .bind(ctx.now)
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, sqlx::Error> {
+    serde_json::to_string(value).map_err(|err| sqlx::Error::Io(err.into()))
+}
+
+/// One page of the recorded changes to `entity_type`/`entity_id`, oldest
+/// first, starting after `after`.
+pub async fn list_history(pool: &SqlitePool, entity_type: &str, entity_id: String, limit: i64, after: Option<CreatedAtCursor>) -> Result<Paginated<AuditEntry>, sqlx::Error> {
+    // Fetch one extra row to learn whether another page follows.
+    let entries: Vec<AuditEntry> = match after {
+        Some(cursor) => db_trace::query_as(
+            "SELECT * FROM audit_log \
+             WHERE entity_type = ? AND entity_id = ? AND (created_at, id) > (?, ?) \
+             ORDER BY created_at, id LIMIT ?",
+        )
+            .bind(entity_type)
+            .bind(entity_id)
+            .bind(cursor.created_at)
+            .bind(cursor.id)
+            .bind(limit + 1)
+            .fetch_all(pool)
+            .await?,
+        None => db_trace::query_as(
+            "SELECT * FROM audit_log WHERE entity_type = ? AND entity_id = ? ORDER BY created_at, id LIMIT ?",
+        )
+            .bind(entity_type)
+            .bind(entity_id)
+            .bind(limit + 1)
+            .fetch_all(pool)
+            .await?,
+    };
+
+    Ok(Paginated::from_rows(entries, limit, |entry| CreatedAtCursor::new(entry.created_at, &entry.id)))
+}
+
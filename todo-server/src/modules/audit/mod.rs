@@ -0,0 +1,15 @@
+use axum::Router;
+use axum::routing::get;
+use crate::modules::audit::audit_controller::item_history;
+use crate::state::AppState;
+
+pub mod audit_controller;
+pub mod audit_service;
+pub mod audit_entity;
+
+
+/// Routes nested under `/items`, alongside the item routes.
+pub fn create_audit_routes() -> Router<AppState> {
+    Router::new()
+        .route("/:id/history", get(item_history))
+}
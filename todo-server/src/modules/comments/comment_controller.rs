@@ -0,0 +1,59 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+};
+
+use sqlx::sqlite::SqlitePool;
+use crate::error::AppError;
+use crate::extract::{Actor, Json, ValidatedQuery};
+use crate::modules::audit::audit_service::AuditContext;
+use crate::modules::comments::comment_dto::CreateCommentDto;
+use crate::modules::comments::comment_entity::Comment;
+use crate::modules::comments::comment_service;
+use crate::modules::todos::todo_service;
+use crate::pagination::{self, CreatedAtCursor, PageQuery, Paginated};
+use crate::state::AppState;
+
+pub async fn create_comment(
+    State(state): State<AppState>,
+    actor: Actor,
+    Path(id): Path<String>,
+    Json(payload): Json<CreateCommentDto>,
+) -> Result<Json<Comment>, AppError> {
+    if payload.author.trim().is_empty() {
+        return Err(AppError::Unprocessable("author must not be empty".to_string()));
+    }
+    if payload.body.trim().is_empty() {
+        return Err(AppError::Unprocessable("body must not be empty".to_string()));
+    }
+
+    todo_service::get_item(&state.pool, id.clone()).await?;
+
+    let comment = comment_service::create_comment(&state.writer, &AuditContext::new(&state, actor), id, payload).await?;
+
+    Ok(Json(comment))
+}
+
+pub async fn list_comments(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<String>,
+    ValidatedQuery(query): ValidatedQuery<PageQuery>,
+) -> Result<Json<Paginated<Comment>>, AppError> {
+    let after = pagination::parse_cursor::<CreatedAtCursor>(query.cursor.as_deref())?;
+
+    todo_service::get_item(&pool, id.clone()).await?;
+
+    let page = comment_service::list_comments(&pool, id, query.limit, after).await?;
+
+    Ok(Json(page))
+}
+
+pub async fn delete_comment(
+    State(state): State<AppState>,
+    actor: Actor,
+    Path((id, comment_id)): Path<(String, String)>,
+) -> Result<StatusCode, AppError> {
+    comment_service::delete_comment(&state.writer, &AuditContext::new(&state, actor), id, comment_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
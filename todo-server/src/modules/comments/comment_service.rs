@@ -0,0 +1,93 @@
+use sqlx::sqlite::{SqliteConnection, SqlitePool};
+use sqlx::types::Json;
+use crate::db_trace;
+use crate::modules::audit::audit_entity::AuditAction;
+use crate::modules::audit::audit_service::{self, AuditContext};
+use crate::modules::comments::comment_dto::CreateCommentDto;
+use crate::modules::comments::comment_entity::Comment;
+use crate::pagination::{CreatedAtCursor, Paginated};
+
+
+pub async fn create_comment(pool: &SqlitePool, audit: &AuditContext<'_>, item_id: String, dto: CreateCommentDto) -> Result<Comment, sqlx::Error> {
+    let comment = Comment {
+        id: audit.ids.next_id(),
+        item_id,
+        author: dto.author,
+        body: dto.body,
+        created_at: audit.now,
+    };
+
+    let mut tx = pool.begin().await?;
+    db_trace::query("INSERT INTO comments (id, item_id, author, body, created_at) VALUES (?, ?, ?, ?, ?)")
+        .bind(&comment.id)
+        .bind(&comment.item_id)
+        .bind(&comment.author)
+        .bind(&comment.body)
+        .bind(comment.created_at)
+        .execute(&mut *tx)
+        .await?;
+
+    record(&mut tx, audit, AuditAction::Create, None, Some(&comment)).await?;
+    tx.commit().await?;
+
+    Ok(comment)
+}
+
+/// One page of an item's comments, oldest first, starting after `after`.
+pub async fn list_comments(pool: &SqlitePool, item_id: String, limit: i64, after: Option<CreatedAtCursor>) -> Result<Paginated<Comment>, sqlx::Error> {
+    // Fetch one extra row to learn whether another page follows.
+    let comments: Vec<Comment> = match after {
+        Some(cursor) => db_trace::query_as(
+            "SELECT * FROM comments \
+             WHERE item_id = ? AND (created_at, id) > (?, ?) \
+             ORDER BY created_at, id LIMIT ?",
+        )
+            .bind(item_id)
+            .bind(cursor.created_at)
+            .bind(cursor.id)
+            .bind(limit + 1)
+            .fetch_all(pool)
+            .await?,
+        None => db_trace::query_as("SELECT * FROM comments WHERE item_id = ? ORDER BY created_at, id LIMIT ?")
+            .bind(item_id)
+            .bind(limit + 1)
+            .fetch_all(pool)
+            .await?,
+    };
+
+    Ok(Paginated::from_rows(comments, limit, |comment| CreatedAtCursor::new(comment.created_at, &comment.id)))
+}
+
+/// Deleting a comment that does not exist is a no-op and records nothing.
+pub async fn delete_comment(pool: &SqlitePool, audit: &AuditContext<'_>, item_id: String, id: String) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let existing_comment: Option<Comment> = db_trace::query_as("DELETE FROM comments WHERE id = ? AND item_id = ? RETURNING *")
+        .bind(id)
+        .bind(item_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+    if let Some(existing_comment) = existing_comment {
+        record(&mut tx, audit, AuditAction::Delete, Some(&existing_comment), None).await?;
+    }
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// The comments of any of `item_ids`, inside an open transaction.
+pub async fn fetch_for_items(conn: &mut SqliteConnection, item_ids: &[String]) -> Result<Vec<Comment>, sqlx::Error> {
+    db_trace::query_as("SELECT * FROM comments WHERE item_id IN (SELECT value FROM json_each(?)) ORDER BY created_at, id")
+        .bind(Json(item_ids))
+        .fetch_all(conn)
+        .await
+}
+
+/// Records one change to a comment in the audit log.
+pub async fn record(conn: &mut SqliteConnection, audit: &AuditContext<'_>, action: AuditAction, before: Option<&Comment>, after: Option<&Comment>) -> Result<(), sqlx::Error> {
+    let Some(id) = before.or(after).map(|comment| comment.id.as_str()) else {
+        return Ok(());
+    };
+
+    audit_service::record(conn, audit, audit_service::COMMENT, id, action, before, after).await
+}
@@ -0,0 +1,17 @@
+use axum::Router;
+use axum::routing::{delete, post};
+use crate::modules::comments::comment_controller::{create_comment, delete_comment, list_comments};
+use crate::state::AppState;
+
+pub mod comment_controller;
+pub mod comment_service;
+pub mod comment_entity;
+pub mod comment_dto;
+
+
+/// Routes nested under `/items`, alongside the item routes.
+pub fn create_comment_routes() -> Router<AppState> {
+    Router::new()
+        .route("/:id/comments", post(create_comment).get(list_comments))
+        .route("/:id/comments/:comment_id", delete(delete_comment))
+}
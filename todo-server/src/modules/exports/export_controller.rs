@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::{header, HeaderMap};
+use axum::response::{IntoResponse, Response};
+
+use crate::error::AppError;
+use crate::extract::Query;
+use crate::modules::exports::export_dto::ExportQuery;
+use crate::modules::exports::export_service;
+use crate::modules::exports::exporter::{Exporter, ExporterRegistry};
+use crate::state::AppState;
+
+/// Format used when neither `?format=` nor `Accept` names a registered one.
+const DEFAULT_FORMAT: &str = "json";
+
+/// Downloads every item in the format chosen by `?format=`, or else by the
+/// first media type in `Accept` that a registered format produces.
+pub async fn export_items(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, AppError> {
+    let exporter = choose_exporter(&state.exporters, query.format.as_deref(), &headers)?;
+    let items = export_service::all_items(&state.pool).await?;
+
+    let mut body = Vec::new();
+    exporter
+        .write(&items, &mut body)
+        .map_err(|err| AppError::Internal(format!("Failed to write the export: {err}")))?;
+
+    let disposition = format!("attachment; filename=\"items.{}\"", exporter.file_extension());
+
+    Ok((
+        [(header::CONTENT_TYPE, exporter.media_type().to_string()), (header::CONTENT_DISPOSITION, disposition)],
+        body,
+    )
+        .into_response())
+}
+
+fn choose_exporter(
+    registry: &ExporterRegistry,
+    format: Option<&str>,
+    headers: &HeaderMap,
+) -> Result<Arc<dyn Exporter>, AppError> {
+    if let Some(format) = format {
+        return registry.get(format).ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "Unknown export format {format:?}; expected one of {}",
+                registry.names().join(", ")
+            ))
+        });
+    }
+
+    let accepted = headers
+        .get(header::ACCEPT)
+        .and_then(|accept| accept.to_str().ok())
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|media_type| media_type.split(';').next())
+        .map(str::trim);
+
+    accepted
+        .filter_map(|media_type| registry.for_media_type(media_type))
+        .next()
+        .or_else(|| registry.get(DEFAULT_FORMAT))
+        .ok_or_else(|| AppError::BadRequest("No export format is registered".to_string()))
+}
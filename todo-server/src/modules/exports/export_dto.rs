@@ -0,0 +1,7 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportQuery {
+    /// Name of a registered format. Takes precedence over `Accept`.
+    pub format: Option<String>,
+}
@@ -0,0 +1,19 @@
+use sqlx::sqlite::SqlitePool;
+use crate::modules::todos::todo_dto::{ItemCursor, ItemSort};
+use crate::modules::todos::todo_entity::Item;
+use crate::modules::todos::todo_service;
+use crate::pagination;
+
+/// Every item, subtasks included, in creation order.
+pub async fn all_items(pool: &SqlitePool) -> Result<Vec<Item>, sqlx::Error> {
+    let mut items = Vec::new();
+    let mut after = None;
+    loop {
+        let page = todo_service::list_items(pool, ItemSort::Created, false, pagination::MAX_PAGE_SIZE, after).await?;
+        items.extend(page.items);
+        if page.next_cursor.is_none() {
+            return Ok(items);
+        }
+        after = items.last().map(|item| ItemCursor::after(ItemSort::Created, item));
+    }
+}
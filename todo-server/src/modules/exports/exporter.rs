@@ -0,0 +1,189 @@
+use std::io::{self, Write};
+use std::sync::Arc;
+
+use crate::modules::todos::todo_entity::{Item, Priority};
+
+/// Writes items in one file format.
+///
+/// Formats are looked up in an [`ExporterRegistry`] by name (`?format=`) or
+/// by media type (`Accept`), so a new format is a new implementation
+/// registered with the app rather than a change to the export handler.
+pub trait Exporter: Send + Sync {
+    /// Identifier used in `?format=` and on the command line, e.g. `csv`.
+    fn name(&self) -> &'static str;
+
+    fn media_type(&self) -> &'static str;
+
+    /// Extension of the suggested download file name, without the dot.
+    fn file_extension(&self) -> &'static str;
+
+    fn write(&self, items: &[Item], out: &mut dyn Write) -> io::Result<()>;
+}
+
+/// The export formats the app offers.
+///
+/// The default registry holds the built-in formats. An embedding app adds its
+/// own with [`ExporterRegistry::register`], which also replaces a built-in
+/// of the same name.
+#[derive(Clone)]
+pub struct ExporterRegistry {
+    exporters: Vec<Arc<dyn Exporter>>,
+}
+
+impl ExporterRegistry {
+    /// A registry without any formats.
+    pub fn empty() -> Self {
+        Self { exporters: Vec::new() }
+    }
+
+    pub fn register(&mut self, exporter: Arc<dyn Exporter>) {
+        self.exporters.retain(|existing| existing.name() != exporter.name());
+        self.exporters.push(exporter);
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn Exporter>> {
+        self.exporters.iter().find(|exporter| exporter.name() == name).cloned()
+    }
+
+    pub fn for_media_type(&self, media_type: &str) -> Option<Arc<dyn Exporter>> {
+        self.exporters
+            .iter()
+            .find(|exporter| exporter.media_type().eq_ignore_ascii_case(media_type))
+            .cloned()
+    }
+
+    /// Registered format names, in registration order.
+    pub fn names(&self) -> Vec<&'static str> {
+        self.exporters.iter().map(|exporter| exporter.name()).collect()
+    }
+}
+
+impl Default for ExporterRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register(Arc::new(JsonExporter));
+        registry.register(Arc::new(CsvExporter));
+        registry.register(Arc::new(TodoTxtExporter));
+        registry
+    }
+}
+
+/// The items as a pretty-printed JSON array, as the API returns them.
+pub struct JsonExporter;
+
+impl Exporter for JsonExporter {
+    fn name(&self) -> &'static str {
+        "json"
+    }
+
+    fn media_type(&self) -> &'static str {
+        "application/json"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "json"
+    }
+
+    fn write(&self, items: &[Item], out: &mut dyn Write) -> io::Result<()> {
+        serde_json::to_writer_pretty(&mut *out, items)?;
+        writeln!(out)
+    }
+}
+
+/// One row per item under a header row, quoted as in RFC 4180.
+pub struct CsvExporter;
+
+impl CsvExporter {
+    const HEADER: [&'static str; 8] =
+        ["id", "name", "description", "priority", "position", "parent_id", "created_at", "updated_at"];
+}
+
+impl Exporter for CsvExporter {
+    fn name(&self) -> &'static str {
+        "csv"
+    }
+
+    fn media_type(&self) -> &'static str {
+        "text/csv"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "csv"
+    }
+
+    fn write(&self, items: &[Item], out: &mut dyn Write) -> io::Result<()> {
+        write_csv_row(out, &Self::HEADER.map(String::from))?;
+
+        for item in items {
+            write_csv_row(
+                out,
+                &[
+                    item.id.clone(),
+                    item.name.clone(),
+                    item.description.clone().unwrap_or_default(),
+                    item.priority.as_str().to_string(),
+                    item.position.to_string(),
+                    item.parent_id.clone().unwrap_or_default(),
+                    item.created_at.to_rfc3339(),
+                    item.updated_at.to_rfc3339(),
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn write_csv_row(out: &mut dyn Write, fields: &[String]) -> io::Result<()> {
+    let row: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.clone()
+            }
+        })
+        .collect();
+
+    write!(out, "{}\r\n", row.join(","))
+}
+
+/// One line per item in the todo.txt format: priority, creation date and
+/// name, with the id and parent as `key:value` tags. Descriptions have no
+/// place in the format and are left out.
+pub struct TodoTxtExporter;
+
+impl Exporter for TodoTxtExporter {
+    fn name(&self) -> &'static str {
+        "todotxt"
+    }
+
+    fn media_type(&self) -> &'static str {
+        "text/plain"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "txt"
+    }
+
+    fn write(&self, items: &[Item], out: &mut dyn Write) -> io::Result<()> {
+        for item in items {
+            let priority = match item.priority {
+                Priority::High => 'A',
+                Priority::Medium => 'B',
+                Priority::Low => 'C',
+            };
+            // A line break would start a new task.
+            let name = item.name.replace(['\r', '\n'], " ");
+
+            write!(out, "({priority}) {} {name} id:{}", item.created_at.format("%Y-%m-%d"), item.id)?;
+            if let Some(parent_id) = &item.parent_id {
+                write!(out, " parent:{parent_id}")?;
+            }
+            writeln!(out)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+use axum::Router;
+use axum::routing::get;
+use crate::modules::exports::export_controller::export_items;
+use crate::state::AppState;
+
+pub mod export_controller;
+pub mod export_service;
+pub mod export_dto;
+pub mod exporter;
+
+
+pub fn create_export_routes() -> Router<AppState> {
+    Router::new()
+        .route("/export", get(export_items))
+}
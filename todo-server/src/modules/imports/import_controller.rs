@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::{header, HeaderMap, StatusCode};
+
+use crate::error::AppError;
+use crate::extract::{Actor, BodyReader, Json, Query};
+use crate::modules::imports::import_dto::{ImportQuery, ImportReport};
+use crate::modules::imports::import_service;
+use crate::modules::imports::importer::{Importer, ImporterRegistry};
+use crate::state::AppState;
+
+/// Creates items from the request body, read in the format named by
+/// `?format=` or else by `Content-Type`.
+///
+/// Answers 422 with the report, and creates nothing, when any row is invalid.
+pub async fn import_items(
+    State(state): State<AppState>,
+    actor: Actor,
+    headers: HeaderMap,
+    Query(query): Query<ImportQuery>,
+    BodyReader(body): BodyReader,
+) -> Result<(StatusCode, Json<ImportReport>), AppError> {
+    let importer = choose_importer(&state.importers, query.format.as_deref(), &headers)?;
+    let report = import_service::import(&state, actor, importer, body, query.dry_run, &mut |_, _| {}).await?;
+
+    let status = if report.errors.is_empty() { StatusCode::OK } else { StatusCode::UNPROCESSABLE_ENTITY };
+
+    Ok((status, Json(report)))
+}
+
+fn choose_importer(
+    registry: &ImporterRegistry,
+    format: Option<&str>,
+    headers: &HeaderMap,
+) -> Result<Arc<dyn Importer>, AppError> {
+    if let Some(format) = format {
+        return registry.get(format).ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "Unknown import format {format:?}; expected one of {}",
+                registry.names().join(", ")
+            ))
+        });
+    }
+
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .and_then(|content_type| content_type.split(';').next())
+        .map(str::trim)
+        .unwrap_or_default();
+
+    registry.for_media_type(content_type).ok_or_else(|| {
+        AppError::UnsupportedMediaType(format!(
+            "Cannot import {content_type:?}; pass ?format= with one of {}",
+            registry.names().join(", ")
+        ))
+    })
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct ImportQuery {
+    /// Name of a registered format. Takes precedence over `Content-Type`.
+    pub format: Option<String>,
+    /// Validate every row and report, without creating anything.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Outcome of an import.
+///
+/// Items are only created when every row is valid, so a file with errors
+/// can be fixed and sent again as a whole. Rows with the same name and
+/// description as an item, or as an earlier row, are skipped, so sending a
+/// file again creates nothing new.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ImportReport {
+    pub format: String,
+    pub dry_run: bool,
+    /// Rows read, valid or not.
+    pub rows: usize,
+    pub created: usize,
+    pub duplicates: Vec<DuplicateRow>,
+    pub errors: Vec<RowError>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DuplicateRow {
+    /// 1-based, as in [`RowError`].
+    pub row: usize,
+    /// The item with the same content, or `None` when it is an earlier row
+    /// of the same import.
+    pub item_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RowError {
+    /// 1-based, counting items rather than lines; a CSV header is not a row.
+    pub row: usize,
+    pub message: String,
+}
@@ -0,0 +1,171 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::io::{self, BufRead, ErrorKind, Read};
+use std::sync::Arc;
+
+use sha2::{Digest, Sha256};
+use tokio::sync::mpsc;
+
+use crate::error::AppError;
+use crate::extract::Actor;
+use crate::modules::imports::import_dto::{DuplicateRow, ImportReport, RowError};
+use crate::modules::imports::importer::{ImportRow, Importer};
+use crate::modules::audit::audit_service::AuditContext;
+use crate::modules::todos::todo_service;
+use crate::modules::todos::todo_dto::CreateItemDto;
+use crate::state::AppState;
+
+/// Reads and validates every row of `input`, skips the ones whose content
+/// an item or an earlier row already has, then, unless this is a dry run or
+/// some row is invalid, creates the items in file order.
+///
+/// `input` is read on a blocking thread and its rows are validated as they
+/// arrive, so only the valid rows are held, never the whole input. The items
+/// are created in one transaction after every item hook has accepted them,
+/// so a failed import leaves nothing behind. `progress` is told how many
+/// items have been inserted out of how many after each one. Shared by
+/// `POST /items/import` and the `import` command.
+pub async fn import(
+    state: &AppState,
+    actor: Actor,
+    importer: Arc<dyn Importer>,
+    input: Box<dyn BufRead + Send>,
+    dry_run: bool,
+    progress: &mut (dyn FnMut(usize, usize) + Send),
+) -> Result<ImportReport, AppError> {
+    let mut report = ImportReport {
+        format: importer.name().to_string(),
+        dry_run,
+        rows: 0,
+        created: 0,
+        duplicates: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    let mut seen: HashMap<_, _> = todo_service::list_contents(&state.pool)
+        .await?
+        .into_iter()
+        .map(|(id, name, description)| (content_hash(&name, description.as_deref()), Some(id)))
+        .collect();
+
+    let (sender, mut rows) = mpsc::channel(64);
+    let reading = tokio::task::spawn_blocking(move || {
+        let mut input = ReadErrors { input, error: None };
+        for row in importer.rows(Box::new(&mut input)) {
+            if sender.blocking_send(row).is_err() {
+                break;
+            }
+        }
+
+        input.error
+    });
+
+    let mut valid = Vec::new();
+    let mut index = 0;
+    while let Some(row) = rows.recv().await {
+        index += 1;
+        report.rows += 1;
+        let dto = match row.and_then(validate) {
+            Ok(dto) => dto,
+            Err(message) => {
+                report.errors.push(RowError { row: index, message });
+                continue;
+            }
+        };
+
+        match seen.entry(content_hash(&dto.name, dto.description.as_deref())) {
+            Entry::Occupied(item_id) => report.duplicates.push(DuplicateRow { row: index, item_id: item_id.get().clone() }),
+            Entry::Vacant(entry) => {
+                entry.insert(None);
+                valid.push(dto);
+            }
+        }
+    }
+
+    let read_error = reading
+        .await
+        .map_err(|err| AppError::Internal(format!("Import reader panicked: {err}")))?;
+    if let Some(err) = read_error {
+        return Err(match err.kind() {
+            ErrorKind::FileTooLarge => AppError::PayloadTooLarge,
+            _ => AppError::BadRequest(format!("Failed to read the import: {err}")),
+        });
+    }
+
+    if dry_run || !report.errors.is_empty() {
+        return Ok(report);
+    }
+
+    let mut accepted = Vec::with_capacity(valid.len());
+    for dto in valid {
+        accepted.push(state.item_hooks.on_create(dto).await?);
+    }
+
+    let total = accepted.len();
+    let audit = AuditContext::new(state, actor);
+    let items = todo_service::create_items(&state.writer, &audit, accepted, &mut |created| progress(created, total)).await?;
+    state.item_cache.invalidate_lists();
+    report.created = items.len();
+
+    Ok(report)
+}
+
+/// Keeps the first error reading an import, which the formats only see as a
+/// row they could not read.
+struct ReadErrors {
+    input: Box<dyn BufRead + Send>,
+    error: Option<io::Error>,
+}
+
+impl ReadErrors {
+    fn record(&mut self, err: &io::Error) {
+        self.error.get_or_insert_with(|| io::Error::new(err.kind(), err.to_string()));
+    }
+}
+
+impl Read for ReadErrors {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.input.read(buf).inspect_err(|err| self.record(err))
+    }
+}
+
+impl BufRead for ReadErrors {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        match self.input.fill_buf() {
+            Ok(buf) => Ok(buf),
+            Err(err) => {
+                self.error.get_or_insert_with(|| io::Error::new(err.kind(), err.to_string()));
+                Err(err)
+            }
+        }
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.input.consume(amount);
+    }
+}
+
+/// SHA-256 of an item's name and description, which is what makes two items
+/// the same as far as imports go.
+fn content_hash(name: &str, description: Option<&str>) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(name.as_bytes());
+    hasher.update([0]);
+    hasher.update(description.unwrap_or_default().as_bytes());
+
+    hasher.finalize().into()
+}
+
+fn validate(row: ImportRow) -> Result<CreateItemDto, String> {
+    let name = row.name.trim();
+    if name.is_empty() {
+        return Err("name must not be empty".to_string());
+    }
+
+    Ok(CreateItemDto {
+        name: name.to_string(),
+        description: row.description.filter(|description| !description.is_empty()),
+        priority: row.priority,
+        parent_id: None,
+    })
+}
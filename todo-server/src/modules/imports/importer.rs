@@ -0,0 +1,566 @@
+use std::io::{self, BufRead};
+use std::sync::Arc;
+
+use serde::Deserialize;
+use crate::modules::todos::todo_entity::Priority;
+
+/// One item read from an import file.
+///
+/// Only the fields a new item can be created with are read. Ids, positions,
+/// timestamps and parents are left to the app, so an import never clashes
+/// with what is already there.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ImportRow {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub priority: Priority,
+}
+
+/// Rows of an import, read one at a time. A row that cannot be read is an
+/// `Err` with the reason, and reading goes on with the next one.
+pub type ImportRows<'a> = Box<dyn Iterator<Item = Result<ImportRow, String>> + 'a>;
+
+/// Reads items from one file format.
+///
+/// Formats are looked up in an [`ImporterRegistry`] by name (`?format=`) or
+/// by media type (`Content-Type`). Validation, dry runs and progress are
+/// handled once for every format by the import handler.
+///
+/// The import handler pulls rows on a blocking thread while the input is
+/// still arriving, so a format should read only as far as the next row
+/// needs. The built-in formats all do.
+pub trait Importer: Send + Sync {
+    /// Identifier used in `?format=` and on the command line, e.g. `csv`.
+    fn name(&self) -> &'static str;
+
+    fn media_type(&self) -> &'static str;
+
+    fn rows<'a>(&self, input: Box<dyn BufRead + 'a>) -> ImportRows<'a>;
+}
+
+/// The import formats the app accepts.
+///
+/// The default registry holds the built-in formats. An embedding app adds its
+/// own with [`ImporterRegistry::register`], which also replaces a built-in
+/// of the same name.
+#[derive(Clone)]
+pub struct ImporterRegistry {
+    importers: Vec<Arc<dyn Importer>>,
+}
+
+impl ImporterRegistry {
+    /// A registry without any formats.
+    pub fn empty() -> Self {
+        Self { importers: Vec::new() }
+    }
+
+    pub fn register(&mut self, importer: Arc<dyn Importer>) {
+        self.importers.retain(|existing| existing.name() != importer.name());
+        self.importers.push(importer);
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn Importer>> {
+        self.importers.iter().find(|importer| importer.name() == name).cloned()
+    }
+
+    pub fn for_media_type(&self, media_type: &str) -> Option<Arc<dyn Importer>> {
+        self.importers
+            .iter()
+            .find(|importer| importer.media_type().eq_ignore_ascii_case(media_type))
+            .cloned()
+    }
+
+    /// Registered format names, in registration order.
+    pub fn names(&self) -> Vec<&'static str> {
+        self.importers.iter().map(|importer| importer.name()).collect()
+    }
+}
+
+impl Default for ImporterRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register(Arc::new(JsonImporter));
+        registry.register(Arc::new(CsvImporter));
+        registry.register(Arc::new(TodoTxtImporter));
+        registry.register(Arc::new(TodoistImporter));
+        registry.register(Arc::new(TrelloImporter));
+        registry
+    }
+}
+
+/// A JSON array of objects with `name`, and optionally `description` and
+/// `priority`, such as the JSON export. Other fields are ignored.
+///
+/// Elements are read one at a time, so a large array is never held in
+/// memory as a whole.
+pub struct JsonImporter;
+
+impl Importer for JsonImporter {
+    fn name(&self) -> &'static str {
+        "json"
+    }
+
+    fn media_type(&self) -> &'static str {
+        "application/json"
+    }
+
+    fn rows<'a>(&self, input: Box<dyn BufRead + 'a>) -> ImportRows<'a> {
+        Box::new(JsonArray::new(JsonScanner { input }).map(|element| {
+            let element = element.map_err(|err| format!("Invalid JSON array: {err}"))?;
+            serde_json::from_slice(&element).map_err(|err| err.to_string())
+        }))
+    }
+}
+
+/// CSV with a header row, quoted as in RFC 4180. A `name` column is
+/// required; `description` and `priority` are read when present and other
+/// columns, such as those of the CSV export, are ignored.
+pub struct CsvImporter;
+
+impl Importer for CsvImporter {
+    fn name(&self) -> &'static str {
+        "csv"
+    }
+
+    fn media_type(&self) -> &'static str {
+        "text/csv"
+    }
+
+    fn rows<'a>(&self, input: Box<dyn BufRead + 'a>) -> ImportRows<'a> {
+        let mut records = CsvRecords { input };
+        let header = match records.next() {
+            Some(Ok(header)) => header,
+            Some(Err(err)) => return Box::new(std::iter::once(Err(err.to_string()))),
+            None => return Box::new(std::iter::empty()),
+        };
+
+        let column = |name: &str| header.iter().position(|field| field.trim().eq_ignore_ascii_case(name));
+        let Some(name) = column("name") else {
+            return Box::new(std::iter::once(Err("The header has no name column".to_string())));
+        };
+        let description = column("description");
+        let priority = column("priority");
+
+        Box::new(records.map(move |record| {
+            let record = record.map_err(|err| err.to_string())?;
+            let field = |index: Option<usize>| {
+                index
+                    .and_then(|index| record.get(index))
+                    .filter(|value| !value.is_empty())
+                    .cloned()
+            };
+
+            Ok(ImportRow {
+                name: field(Some(name)).unwrap_or_default(),
+                description: field(description),
+                priority: field(priority).map(|value| parse_priority(&value)).transpose()?.unwrap_or_default(),
+            })
+        }))
+    }
+}
+
+/// CSV records from a reader, one at a time. Quoted fields may span lines.
+struct CsvRecords<'a> {
+    input: Box<dyn BufRead + 'a>,
+}
+
+impl Iterator for CsvRecords<'_> {
+    type Item = io::Result<Vec<String>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut record = String::new();
+        loop {
+            match self.input.read_line(&mut record) {
+                Err(err) => return Some(Err(err)),
+                Ok(0) if record.is_empty() => return None,
+                Ok(0) => break,
+                // An even number of quotes means no quoted field is left open.
+                Ok(_) if record.matches('"').count().is_multiple_of(2) => break,
+                Ok(_) => {}
+            }
+        }
+
+        let record = record.strip_suffix('\n').unwrap_or(&record);
+        let record = record.strip_suffix('\r').unwrap_or(record);
+        if record.is_empty() {
+            return self.next();
+        }
+
+        Some(Ok(split_csv_record(record)))
+    }
+}
+
+fn split_csv_record(record: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = record.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', _) => quoted = !quoted,
+            (',', false) => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    fields
+}
+
+/// todo.txt: one task per line, with an optional `(A)`-style priority and
+/// creation date before the text. `key:value` tags, such as the `id:` the
+/// todo.txt export writes, are dropped. Completed (`x `) tasks are imported
+/// like open ones.
+pub struct TodoTxtImporter;
+
+impl Importer for TodoTxtImporter {
+    fn name(&self) -> &'static str {
+        "todotxt"
+    }
+
+    fn media_type(&self) -> &'static str {
+        "text/plain"
+    }
+
+    fn rows<'a>(&self, input: Box<dyn BufRead + 'a>) -> ImportRows<'a> {
+        Box::new(input.lines().filter_map(|line| match line {
+            Ok(line) if line.trim().is_empty() => None,
+            Ok(line) => Some(Ok(parse_todo_txt_line(&line))),
+            Err(err) => Some(Err(err.to_string())),
+        }))
+    }
+}
+
+fn parse_todo_txt_line(line: &str) -> ImportRow {
+    let mut words = line.split_whitespace().peekable();
+
+    if words.peek() == Some(&"x") {
+        words.next();
+        // The completion date, then possibly the creation date below.
+        words.next_if(|word| is_date(word));
+    }
+    let priority = match words.next_if(|word| word.len() == 3 && word.starts_with('(') && word.ends_with(')')) {
+        Some("(A)") => Priority::High,
+        Some("(B)") | None => Priority::Medium,
+        Some(_) => Priority::Low,
+    };
+    words.next_if(|word| is_date(word));
+
+    let name = words
+        .filter(|word| !is_tag(word))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    ImportRow { name, description: None, priority }
+}
+
+/// A Todoist project exported as CSV. Only `task` rows are read, from their
+/// `CONTENT`, `DESCRIPTION` and `PRIORITY` columns; sections, notes and
+/// layout rows are skipped. Subtasks come in as top-level items.
+///
+/// Todoist numbers priorities from 1 (p1, highest) to 4 (p4, no priority),
+/// so 1 is high, 2 medium, 3 low and 4 the default. Only chosen with
+/// `?format=todoist`, since `text/csv` picks the plain CSV format.
+pub struct TodoistImporter;
+
+impl Importer for TodoistImporter {
+    fn name(&self) -> &'static str {
+        "todoist"
+    }
+
+    fn media_type(&self) -> &'static str {
+        "text/csv"
+    }
+
+    fn rows<'a>(&self, input: Box<dyn BufRead + 'a>) -> ImportRows<'a> {
+        let mut records = CsvRecords { input };
+        let header = match records.next() {
+            Some(Ok(header)) => header,
+            Some(Err(err)) => return Box::new(std::iter::once(Err(err.to_string()))),
+            None => return Box::new(std::iter::empty()),
+        };
+
+        // Todoist writes a byte order mark before the header.
+        let column = |name: &str| header.iter().position(|field| field.trim_start_matches('\u{feff}').trim() == name);
+        let (Some(kind), Some(content)) = (column("TYPE"), column("CONTENT")) else {
+            return Box::new(std::iter::once(Err("The header has no TYPE and CONTENT columns".to_string())));
+        };
+        let description = column("DESCRIPTION");
+        let priority = column("PRIORITY");
+
+        Box::new(records.filter_map(move |record| {
+            let record = match record {
+                Ok(record) => record,
+                Err(err) => return Some(Err(err.to_string())),
+            };
+            let field = |index: Option<usize>| {
+                index
+                    .and_then(|index| record.get(index))
+                    .map(|value| value.trim())
+                    .filter(|value| !value.is_empty())
+                    .map(str::to_string)
+            };
+            if field(Some(kind)).as_deref() != Some("task") {
+                return None;
+            }
+
+            let priority = match field(priority).as_deref() {
+                Some("1") => Priority::High,
+                Some("2") => Priority::Medium,
+                Some("3") => Priority::Low,
+                Some("4") | None => Priority::default(),
+                Some(other) => return Some(Err(format!("Unknown Todoist priority {other:?}"))),
+            };
+
+            Some(Ok(ImportRow {
+                name: field(Some(content)).unwrap_or_default(),
+                description: field(description),
+                priority,
+            }))
+        }))
+    }
+}
+
+/// A Trello board exported as JSON. Every open card becomes an item, with
+/// the card description as its description; archived cards are skipped. A
+/// card labelled `high`, `medium` or `low` gets that priority.
+///
+/// Only chosen with `?format=trello`, since `application/json` picks the
+/// plain JSON format. Cards are read one at a time and the rest of the
+/// board, such as its actions, is skipped over without being kept.
+pub struct TrelloImporter;
+
+#[derive(Deserialize)]
+struct TrelloCard {
+    name: String,
+    #[serde(default)]
+    desc: String,
+    #[serde(default)]
+    closed: bool,
+    #[serde(default)]
+    labels: Vec<TrelloLabel>,
+}
+
+#[derive(Deserialize)]
+struct TrelloLabel {
+    #[serde(default)]
+    name: String,
+}
+
+impl Importer for TrelloImporter {
+    fn name(&self) -> &'static str {
+        "trello"
+    }
+
+    fn media_type(&self) -> &'static str {
+        "application/json"
+    }
+
+    fn rows<'a>(&self, input: Box<dyn BufRead + 'a>) -> ImportRows<'a> {
+        let mut board = JsonScanner { input };
+        match board.find_key("cards") {
+            Ok(true) => {}
+            Ok(false) => return Box::new(std::iter::once(Err("Invalid Trello board: it has no cards".to_string()))),
+            Err(err) => return Box::new(std::iter::once(Err(format!("Invalid Trello board: {err}")))),
+        }
+
+        Box::new(JsonArray::new(board).filter_map(|element| {
+            let card = element
+                .map_err(|err| format!("Invalid Trello board: {err}"))
+                .and_then(|element| serde_json::from_slice::<TrelloCard>(&element).map_err(|err| format!("Invalid Trello card: {err}")));
+
+            match card {
+                Ok(card) if card.closed => None,
+                Ok(card) => Some(Ok(ImportRow {
+                    priority: card
+                        .labels
+                        .iter()
+                        .find_map(|label| parse_priority(&label.name).ok())
+                        .unwrap_or_default(),
+                    description: Some(card.desc).filter(|desc| !desc.is_empty()),
+                    name: card.name,
+                })),
+                Err(err) => Some(Err(err)),
+            }
+        }))
+    }
+}
+
+/// Walks a JSON document byte by byte, finding where values start and end
+/// without parsing them, so the parts that matter can be handed to serde one
+/// at a time and the rest skipped.
+struct JsonScanner<'a> {
+    input: Box<dyn BufRead + 'a>,
+}
+
+impl JsonScanner<'_> {
+    fn peek(&mut self) -> Result<Option<u8>, String> {
+        let buffer = self.input.fill_buf().map_err(|err| err.to_string())?;
+
+        Ok(buffer.first().copied())
+    }
+
+    /// The next byte that isn't whitespace, left unread.
+    fn peek_token(&mut self) -> Result<Option<u8>, String> {
+        while let Some(byte) = self.peek()? {
+            if !byte.is_ascii_whitespace() {
+                return Ok(Some(byte));
+            }
+            self.input.consume(1);
+        }
+
+        Ok(None)
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), String> {
+        match self.peek_token()? {
+            Some(byte) if byte == expected => {
+                self.input.consume(1);
+                Ok(())
+            }
+            Some(byte) => Err(format!("expected `{}`, found `{}`", expected as char, byte as char)),
+            None => Err(format!("expected `{}`, found the end of the input", expected as char)),
+        }
+    }
+
+    /// Reads the value at the current position, copying it into `out` when
+    /// given. Scalars end at the first delimiter, which is left unread.
+    fn value(&mut self, mut out: Option<&mut Vec<u8>>) -> Result<(), String> {
+        self.peek_token()?;
+        let (mut depth, mut in_string, mut escaped, mut empty) = (0usize, false, false, true);
+
+        loop {
+            let Some(byte) = self.peek()? else {
+                return match depth == 0 && !in_string && !empty {
+                    true => Ok(()),
+                    false => Err("unexpected end of the input".to_string()),
+                };
+            };
+
+            if !in_string && depth == 0 && !empty && matches!(byte, b',' | b']' | b'}' | b':' | b' ' | b'\t' | b'\n' | b'\r') {
+                return Ok(());
+            }
+            self.input.consume(1);
+            if let Some(out) = out.as_mut() {
+                out.push(byte);
+            }
+            empty = false;
+
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' if in_string => escaped = true,
+                b'"' => in_string = !in_string,
+                _ if in_string => {}
+                b'{' | b'[' => depth += 1,
+                b'}' | b']' if depth > 0 => depth -= 1,
+                b'}' | b']' => return Err(format!("unexpected `{}`", byte as char)),
+                _ => {}
+            }
+
+            if depth == 0 && !in_string && matches!(byte, b'"' | b'}' | b']') {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Moves into a JSON object up to the value of `key`. Returns `false`
+    /// when the object has no such key.
+    fn find_key(&mut self, key: &str) -> Result<bool, String> {
+        self.expect(b'{')?;
+        if self.peek_token()? == Some(b'}') {
+            return Ok(false);
+        }
+
+        loop {
+            let mut name = Vec::new();
+            self.value(Some(&mut name))?;
+            let name: String = serde_json::from_slice(&name).map_err(|err| format!("invalid key: {err}"))?;
+            self.expect(b':')?;
+            if name == key {
+                return Ok(true);
+            }
+            self.value(None)?;
+
+            match self.peek_token()? {
+                Some(b',') => self.input.consume(1),
+                Some(b'}') => return Ok(false),
+                _ => return Err("expected `,` or `}` after a value".to_string()),
+            }
+        }
+    }
+}
+
+/// The elements of the JSON array at a scanner's position, each as the raw
+/// bytes of its value. Stops after the first structural error.
+struct JsonArray<'a> {
+    scanner: JsonScanner<'a>,
+    started: bool,
+    done: bool,
+}
+
+impl<'a> JsonArray<'a> {
+    fn new(scanner: JsonScanner<'a>) -> Self {
+        Self { scanner, started: false, done: false }
+    }
+
+    fn element(&mut self) -> Result<Option<Vec<u8>>, String> {
+        if !self.started {
+            self.started = true;
+            self.scanner.expect(b'[')?;
+            if self.scanner.peek_token()? == Some(b']') {
+                return Ok(None);
+            }
+        } else {
+            match self.scanner.peek_token()? {
+                Some(b',') => self.scanner.input.consume(1),
+                Some(b']') => return Ok(None),
+                _ => return Err("expected `,` or `]` after an element".to_string()),
+            }
+        }
+
+        let mut element = Vec::new();
+        self.scanner.value(Some(&mut element))?;
+
+        Ok(Some(element))
+    }
+}
+
+impl Iterator for JsonArray<'_> {
+    type Item = Result<Vec<u8>, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let element = self.element().transpose();
+        self.done = !matches!(element, Some(Ok(_)));
+
+        element
+    }
+}
+
+fn is_date(word: &str) -> bool {
+    chrono::NaiveDate::parse_from_str(word, "%Y-%m-%d").is_ok()
+}
+
+fn is_tag(word: &str) -> bool {
+    word.split_once(':').is_some_and(|(key, value)| {
+        key.starts_with(|c: char| c.is_ascii_alphabetic())
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            && !value.is_empty()
+            // Keeps links such as `https://...` in the text.
+            && !value.starts_with('/')
+    })
+}
+
+fn parse_priority(value: &str) -> Result<Priority, String> {
+    value.trim().to_ascii_lowercase().parse().map_err(|_| format!("Unknown priority {value:?}"))
+}
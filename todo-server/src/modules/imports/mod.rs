@@ -0,0 +1,15 @@
+use axum::Router;
+use axum::routing::post;
+use crate::modules::imports::import_controller::import_items;
+use crate::state::AppState;
+
+pub mod import_controller;
+pub mod import_dto;
+pub mod import_service;
+pub mod importer;
+
+
+pub fn create_import_routes() -> Router<AppState> {
+    Router::new()
+        .route("/import", post(import_items))
+}
@@ -0,0 +1,10 @@
+pub mod actions;
+pub mod admin;
+pub mod attachments;
+pub mod audit;
+pub mod comments;
+pub mod exports;
+pub mod imports;
+pub mod reminders;
+pub mod status;
+pub mod todos;
\ No newline at end of file
@@ -0,0 +1,18 @@
+use axum::Router;
+use axum::routing::post;
+use crate::modules::reminders::reminder_controller::{create_reminder, list_reminders};
+use crate::state::AppState;
+
+pub mod reminder_controller;
+pub mod reminder_service;
+pub mod reminder_entity;
+pub mod reminder_dto;
+pub mod reminder_worker;
+pub mod reminder_webhook;
+
+
+/// Routes nested under `/items`, alongside the item routes.
+pub fn create_reminder_routes() -> Router<AppState> {
+    Router::new()
+        .route("/:id/reminders", post(create_reminder).get(list_reminders))
+}
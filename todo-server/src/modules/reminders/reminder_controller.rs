@@ -0,0 +1,41 @@
+use axum::extract::{Path, State};
+
+use reqwest::Url;
+use sqlx::sqlite::SqlitePool;
+use crate::error::AppError;
+use crate::extract::{Actor, Json};
+use crate::modules::audit::audit_service::AuditContext;
+use crate::modules::reminders::reminder_dto::CreateReminderDto;
+use crate::modules::reminders::reminder_entity::Reminder;
+use crate::modules::reminders::reminder_service;
+use crate::modules::reminders::reminder_webhook::WebhookPolicy;
+use crate::modules::todos::todo_service;
+use crate::state::AppState;
+
+pub async fn create_reminder(
+    State(state): State<AppState>,
+    actor: Actor,
+    Path(id): Path<String>,
+    Json(payload): Json<CreateReminderDto>,
+) -> Result<Json<Reminder>, AppError> {
+    let url = Url::parse(&payload.webhook_url)
+        .map_err(|_| AppError::Unprocessable("webhook_url is not a valid URL".to_string()))?;
+    WebhookPolicy::from_config(&state.config).check_new(&url).await.map_err(AppError::Unprocessable)?;
+
+    todo_service::get_item(&state.pool, id.clone()).await?;
+
+    let reminder = reminder_service::create_reminder(&state.writer, &AuditContext::new(&state, actor), id, payload).await?;
+
+    Ok(Json(reminder))
+}
+
+pub async fn list_reminders(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<Reminder>>, AppError> {
+    todo_service::get_item(&pool, id.clone()).await?;
+
+    let reminders = reminder_service::list_reminders(&pool, id).await?;
+
+    Ok(Json(reminders))
+}
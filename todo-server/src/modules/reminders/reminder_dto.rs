@@ -0,0 +1,18 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct CreateReminderDto {
+    pub remind_at: DateTime<Utc>,
+    pub webhook_url: String,
+}
+
+/// JSON body POSTed to a reminder's webhook once it is due.
+#[derive(Serialize, Deserialize)]
+pub struct ReminderPayload {
+    pub reminder_id: String,
+    pub item_id: String,
+    pub item_name: String,
+    pub item_description: Option<String>,
+    pub remind_at: DateTime<Utc>,
+}
@@ -0,0 +1,134 @@
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::sqlite::{SqliteConnection, SqlitePool};
+use sqlx::types::Json;
+use crate::db_trace;
+use crate::modules::audit::audit_entity::AuditAction;
+use crate::modules::audit::audit_service::{self, AuditContext};
+use crate::modules::reminders::reminder_dto::CreateReminderDto;
+use crate::modules::reminders::reminder_entity::Reminder;
+
+pub async fn create_reminder(pool: &SqlitePool, audit: &AuditContext<'_>, item_id: String, dto: CreateReminderDto) -> Result<Reminder, sqlx::Error> {
+    let reminder = Reminder {
+        id: audit.ids.next_id(),
+        item_id,
+        remind_at: dto.remind_at,
+        webhook_url: dto.webhook_url,
+        attempts: 0,
+        next_attempt_at: dto.remind_at,
+        last_error: None,
+        delivered_at: None,
+    };
+
+    let mut tx = pool.begin().await?;
+    db_trace::query("INSERT INTO reminders (id, item_id, remind_at, webhook_url, next_attempt_at) VALUES (?, ?, ?, ?, ?)")
+        .bind(&reminder.id)
+        .bind(&reminder.item_id)
+        .bind(reminder.remind_at)
+        .bind_redacted(&reminder.webhook_url)
+        .bind(reminder.next_attempt_at)
+        .execute(&mut *tx)
+        .await?;
+
+    record(&mut tx, audit, AuditAction::Create, None, Some(&reminder)).await?;
+    tx.commit().await?;
+
+    Ok(reminder)
+}
+
+pub async fn list_reminders(pool: &SqlitePool, item_id: String) -> Result<Vec<Reminder>, sqlx::Error> {
+    let reminders = db_trace::query_as("SELECT * FROM reminders WHERE item_id = ? ORDER BY remind_at")
+        .bind(item_id)
+        .fetch_all(pool)
+        .await?;
+
+    Ok(reminders)
+}
+
+/// Undelivered reminders whose next attempt is due, oldest first.
+pub async fn list_due_reminders(pool: &SqlitePool, now: DateTime<Utc>, max_attempts: i64, limit: i64) -> Result<Vec<Reminder>, sqlx::Error> {
+    let reminders = db_trace::query_as(
+        "SELECT * FROM reminders \
+         WHERE delivered_at IS NULL AND next_attempt_at <= ? AND attempts < ? \
+         ORDER BY next_attempt_at LIMIT ?",
+    )
+        .bind(now)
+        .bind(max_attempts)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+    Ok(reminders)
+}
+
+/// Records a successful delivery of `reminder`.
+pub async fn mark_delivered(pool: &SqlitePool, audit: &AuditContext<'_>, reminder: &Reminder) -> Result<(), sqlx::Error> {
+    let delivered = Reminder {
+        attempts: reminder.attempts + 1,
+        delivered_at: Some(audit.now),
+        last_error: None,
+        ..reminder.clone()
+    };
+
+    let mut tx = pool.begin().await?;
+    db_trace::query("UPDATE reminders SET attempts = attempts + 1, delivered_at = ?, last_error = NULL WHERE id = ?")
+        .bind(audit.now)
+        .bind(&reminder.id)
+        .execute(&mut *tx)
+        .await?;
+
+    record(&mut tx, audit, AuditAction::Update, Some(reminder), Some(&delivered)).await?;
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// Records a failed delivery of `reminder` and when to try again.
+pub async fn mark_failed(pool: &SqlitePool, audit: &AuditContext<'_>, reminder: &Reminder, error: String, next_attempt_at: DateTime<Utc>) -> Result<(), sqlx::Error> {
+    let failed = Reminder {
+        attempts: reminder.attempts + 1,
+        last_error: Some(error),
+        next_attempt_at,
+        ..reminder.clone()
+    };
+
+    let mut tx = pool.begin().await?;
+    db_trace::query("UPDATE reminders SET attempts = attempts + 1, last_error = ?, next_attempt_at = ? WHERE id = ?")
+        .bind(&failed.last_error)
+        .bind(next_attempt_at)
+        .bind(&reminder.id)
+        .execute(&mut *tx)
+        .await?;
+
+    record(&mut tx, audit, AuditAction::Update, Some(reminder), Some(&failed)).await?;
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// The reminders of any of `item_ids`, inside an open transaction.
+pub async fn fetch_for_items(conn: &mut SqliteConnection, item_ids: &[String]) -> Result<Vec<Reminder>, sqlx::Error> {
+    db_trace::query_as("SELECT * FROM reminders WHERE item_id IN (SELECT value FROM json_each(?)) ORDER BY remind_at")
+        .bind(Json(item_ids))
+        .fetch_all(conn)
+        .await
+}
+
+/// Records one change to a reminder in the audit log. The webhook URL may
+/// carry credentials, so it is left out of the recorded snapshots.
+pub async fn record(conn: &mut SqliteConnection, audit: &AuditContext<'_>, action: AuditAction, before: Option<&Reminder>, after: Option<&Reminder>) -> Result<(), sqlx::Error> {
+    let Some(id) = before.or(after).map(|reminder| reminder.id.clone()) else {
+        return Ok(());
+    };
+
+    audit_service::record(conn, audit, audit_service::REMINDER, &id, action, before.map(snapshot).as_ref(), after.map(snapshot).as_ref()).await
+}
+
+fn snapshot(reminder: &Reminder) -> Value {
+    let mut snapshot = serde_json::to_value(reminder).unwrap_or_default();
+    if let Some(fields) = snapshot.as_object_mut() {
+        fields.remove("webhook_url");
+    }
+
+    snapshot
+}
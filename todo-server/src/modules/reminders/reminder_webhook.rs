@@ -0,0 +1,126 @@
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::redirect;
+use reqwest::{Client, Url};
+use crate::config::AppConfig;
+
+const PRIVATE_ADDRESS: &str = "webhook_url must not point at a loopback, private or link-local address";
+
+/// Where reminder webhooks may be sent.
+///
+/// Webhooks are POSTed from inside the deployment, so they must not reach
+/// loopback, private or link-local addresses, such as a cloud metadata
+/// endpoint, unless their host is one of `webhook_private_hosts`. A name is
+/// checked against every address it resolves to.
+#[derive(Clone, Default)]
+pub struct WebhookPolicy {
+    private_hosts: Arc<[String]>,
+}
+
+impl WebhookPolicy {
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self { private_hosts: config.webhook_private_hosts.clone().into() }
+    }
+
+    /// Checks the webhook of a new reminder. A name that does not resolve
+    /// yet is let through; it is checked again before every delivery.
+    pub async fn check_new(&self, url: &Url) -> Result<(), String> {
+        let Some(name) = self.check_url(url)? else {
+            return Ok(());
+        };
+
+        match tokio::net::lookup_host((name, 0)).await {
+            Ok(mut addrs) => addrs.try_for_each(|addr| check_ip(addr.ip())),
+            Err(_) => Ok(()),
+        }
+    }
+
+    /// Checks a webhook right before it is delivered.
+    pub async fn check_delivery(&self, url: &Url) -> Result<(), String> {
+        let Some(name) = self.check_url(url)? else {
+            return Ok(());
+        };
+
+        tokio::net::lookup_host((name, 0))
+            .await
+            .map_err(|err| format!("Failed to resolve {name}: {err}"))?
+            .try_for_each(|addr| check_ip(addr.ip()))
+    }
+
+    /// Checks what can be told from `url` alone and returns the name still
+    /// to be resolved, if any.
+    fn check_url<'u>(&self, url: &'u Url) -> Result<Option<&'u str>, String> {
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err("webhook_url must be an http or https URL".to_string());
+        }
+        let host = url.host_str().ok_or_else(|| "webhook_url has no host".to_string())?;
+        if self.allows_private(host) {
+            return Ok(None);
+        }
+
+        match host.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>() {
+            Ok(ip) => check_ip(ip).map(|()| None),
+            Err(_) if host == "localhost" || host.ends_with(".localhost") => Err(PRIVATE_ADDRESS.to_string()),
+            Err(_) => Ok(Some(host)),
+        }
+    }
+
+    fn allows_private(&self, host: &str) -> bool {
+        self.private_hosts.iter().any(|allowed| allowed.eq_ignore_ascii_case(host))
+    }
+}
+
+/// Client the reminder worker delivers webhooks with. It resolves names
+/// under `policy` as it connects, so a name cannot be pointed somewhere else
+/// between the check and the request, and it does not follow redirects.
+pub fn client(policy: WebhookPolicy, timeout: Duration) -> Client {
+    Client::builder()
+        .timeout(timeout)
+        .redirect(redirect::Policy::none())
+        .dns_resolver(Arc::new(PolicyResolver(policy)))
+        .build()
+        .expect("Failed to build the webhook client")
+}
+
+struct PolicyResolver(WebhookPolicy);
+
+impl Resolve for PolicyResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let policy = self.0.clone();
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0)).await?.collect();
+            if !policy.allows_private(name.as_str()) {
+                addrs.iter().try_for_each(|addr| check_ip(addr.ip()))?;
+            }
+
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+fn check_ip(ip: IpAddr) -> Result<(), String> {
+    if is_public(ip) {
+        Ok(())
+    } else {
+        Err(PRIVATE_ADDRESS.to_string())
+    }
+}
+
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            // 100.64.0.0/10 is shared address space behind carrier-grade NAT
+            let shared = ip.octets()[0] == 100 && ip.octets()[1] & 0xc0 == 64;
+            !(ip.is_loopback() || ip.is_private() || ip.is_link_local() || ip.is_unspecified()
+                || ip.is_broadcast() || ip.is_multicast() || shared)
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public(ip.into()),
+            None => !(ip.is_loopback() || ip.is_unique_local() || ip.is_unicast_link_local()
+                || ip.is_unspecified() || ip.is_multicast()),
+        },
+    }
+}
@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use chrono::TimeDelta;
+use reqwest::{Client, Url};
+use sqlx::sqlite::SqlitePool;
+use crate::extract::Actor;
+use crate::modules::audit::audit_service::AuditContext;
+use crate::modules::reminders::reminder_dto::ReminderPayload;
+use crate::modules::reminders::reminder_entity::Reminder;
+use crate::modules::reminders::reminder_service;
+use crate::modules::reminders::reminder_webhook::{self, WebhookPolicy};
+use crate::modules::todos::todo_service;
+use crate::state::AppState;
+
+/// Actor recorded in the audit log for delivery attempts.
+pub const WORKER_ACTOR: &str = "reminder-worker";
+
+pub struct ReminderWorkerConfig {
+    /// How often the database is polled for due reminders.
+    pub poll_interval: Duration,
+    /// Maximum number of reminders delivered per poll.
+    pub batch_size: i64,
+    /// Deliveries are abandoned after this many failed attempts.
+    pub max_attempts: i64,
+    /// Delay before the first retry; doubled after every further failure.
+    pub base_backoff: Duration,
+    pub max_backoff: Duration,
+    pub request_timeout: Duration,
+}
+
+impl Default for ReminderWorkerConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(15),
+            batch_size: 50,
+            max_attempts: 8,
+            base_backoff: Duration::from_secs(30),
+            max_backoff: Duration::from_secs(60 * 60),
+            request_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Polls for due reminders forever. Meant to be spawned once at startup.
+pub async fn run(state: AppState, config: ReminderWorkerConfig) {
+    let client = reminder_webhook::client(WebhookPolicy::from_config(&state.config), config.request_timeout);
+    let mut interval = tokio::time::interval(config.poll_interval);
+
+    loop {
+        interval.tick().await;
+
+        if let Err(err) = deliver_due_reminders(&state, &client, &config).await {
+            tracing::error!("Failed to deliver due reminders: {err}");
+        }
+    }
+}
+
+/// Sends every reminder that is currently due and records the outcome.
+///
+/// Failed deliveries are rescheduled with exponential backoff; the schedule is
+/// stored in the database so retries survive a restart. Returns the number of
+/// reminders delivered.
+pub async fn deliver_due_reminders(state: &AppState, client: &Client, config: &ReminderWorkerConfig) -> Result<usize, sqlx::Error> {
+    let pool = &state.pool;
+    let reminders = reminder_service::list_due_reminders(pool, state.clock.now(), config.max_attempts, config.batch_size).await?;
+    let policy = WebhookPolicy::from_config(&state.config);
+    let mut delivered = 0;
+
+    for reminder in reminders {
+        match deliver(pool, client, &policy, &reminder).await {
+            Ok(()) => {
+                let audit = AuditContext::new(state, Actor::named(WORKER_ACTOR));
+                reminder_service::mark_delivered(&state.writer, &audit, &reminder).await?;
+                delivered += 1;
+            }
+            Err(error) => {
+                let audit = AuditContext::new(state, Actor::named(WORKER_ACTOR));
+                let next_attempt_at = audit.now + backoff(config, reminder.attempts);
+                reminder_service::mark_failed(&state.writer, &audit, &reminder, error, next_attempt_at).await?;
+            }
+        }
+    }
+
+    Ok(delivered)
+}
+
+async fn deliver(pool: &SqlitePool, client: &Client, policy: &WebhookPolicy, reminder: &Reminder) -> Result<(), String> {
+    let url = Url::parse(&reminder.webhook_url).map_err(|err| err.to_string())?;
+    policy.check_delivery(&url).await?;
+
+    let item = todo_service::get_item(pool, reminder.item_id.clone())
+        .await
+        .map_err(|err| err.to_string())?;
+
+    let payload = ReminderPayload {
+        reminder_id: reminder.id.clone(),
+        item_id: item.id,
+        item_name: item.name,
+        item_description: item.description,
+        remind_at: reminder.remind_at,
+    };
+
+    let response = client
+        .post(url)
+        .json(&payload)
+        .send()
+        .await
+        // The webhook URL may carry a token, and the error ends up in the
+        // logs and in `last_error`.
+        .map_err(|err| err.without_url().to_string())?;
+
+    // Redirects are not followed, so they count as failures too
+    match response.status() {
+        status if status.is_success() => Ok(()),
+        status => Err(format!("Webhook responded with {status}")),
+    }
+}
+
+/// Delay before the next attempt, given how many attempts already failed.
+fn backoff(config: &ReminderWorkerConfig, previous_attempts: i64) -> TimeDelta {
+    let exponent = previous_attempts.clamp(0, 16) as u32;
+    let delay = config.base_backoff.saturating_mul(2u32.pow(exponent)).min(config.max_backoff);
+
+    TimeDelta::from_std(delay).unwrap_or(TimeDelta::MAX)
+}
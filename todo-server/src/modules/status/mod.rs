@@ -0,0 +1,17 @@
+use axum::Router;
+use axum::routing::get;
+use crate::modules::status::status_controller::status;
+use crate::state::AppState;
+
+pub mod status_controller;
+pub mod status_dto;
+pub mod status_metrics;
+pub mod status_service;
+
+
+/// `GET /status`, served outside the versioned API so it keeps working for
+/// whatever version a client uses.
+pub fn create_status_routes() -> Router<AppState> {
+    Router::new()
+        .route("/status", get(status))
+}
@@ -0,0 +1,67 @@
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderMap};
+use axum::middleware::Next;
+use axum::response::{Html, IntoResponse, Response};
+
+use crate::extract::Json;
+use crate::modules::status::status_dto::{OverallStatus, StatusReport};
+use crate::modules::status::status_service;
+use crate::state::AppState;
+
+/// JSON by default; a small HTML page for clients that ask for `text/html`,
+/// such as browsers.
+pub async fn status(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    let report = status_service::status_report(&state).await;
+
+    let wants_html = headers
+        .get(header::ACCEPT)
+        .and_then(|accept| accept.to_str().ok())
+        .is_some_and(|accept| accept.contains("text/html"));
+
+    if wants_html {
+        Html(render_html(&report)).into_response()
+    } else {
+        Json(report).into_response()
+    }
+}
+
+/// Counts every response, and whether it was a server error, for the
+/// status page.
+pub async fn record_request(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let response = next.run(request).await;
+    state.request_metrics.record(state.clock.now(), response.status().is_server_error());
+
+    response
+}
+
+fn render_html(report: &StatusReport) -> String {
+    let status = match report.status {
+        OverallStatus::Ok => "All systems operational",
+        OverallStatus::Degraded => "Degraded performance",
+        OverallStatus::Down => "Service unavailable",
+    };
+    let error_rate = report
+        .error_rate
+        .map_or_else(|| "no requests".to_string(), |rate| format!("{:.2}%", rate * 100.0));
+    let dependencies: String = report
+        .dependencies
+        .iter()
+        .map(|dependency| {
+            format!(
+                "<tr><td>{}</td><td>{}</td><td>{} ms</td></tr>",
+                dependency.name,
+                if dependency.healthy { "healthy" } else { "unhealthy" },
+                dependency.latency_ms,
+            )
+        })
+        .collect();
+
+    format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Status</title></head><body>\n\
+         <h1>{status}</h1>\n\
+         <p>Up for {} seconds. Server errors in the last {} minutes: {} of {} requests ({error_rate}).</p>\n\
+         <table><tr><th>Dependency</th><th>State</th><th>Latency</th></tr>{dependencies}</table>\n\
+         </body></html>\n",
+        report.uptime_seconds, report.window_minutes, report.server_errors, report.requests,
+    )
+}
@@ -0,0 +1,36 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OverallStatus {
+    /// Everything is working.
+    Ok,
+    /// Requests are served, but some fail or a dependency is unhealthy.
+    Degraded,
+    /// The database cannot be reached, so almost nothing works.
+    Down,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct StatusReport {
+    pub status: OverallStatus,
+    pub started_at: DateTime<Utc>,
+    pub uptime_seconds: i64,
+    /// Length of the window `requests` and `server_errors` cover.
+    pub window_minutes: i64,
+    pub requests: u64,
+    pub server_errors: u64,
+    /// `server_errors / requests`; `None` when there were no requests.
+    pub error_rate: Option<f64>,
+    pub dependencies: Vec<DependencyHealth>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DependencyHealth {
+    pub name: String,
+    pub healthy: bool,
+    pub latency_ms: u64,
+    /// `"unavailable"` when unhealthy; the cause is only logged.
+    pub error: Option<String>,
+}
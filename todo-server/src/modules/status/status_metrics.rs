@@ -0,0 +1,72 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+
+/// How far back request counts are kept.
+pub const WINDOW_MINUTES: i64 = 60;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RequestCounts {
+    pub requests: u64,
+    pub server_errors: u64,
+}
+
+struct Bucket {
+    minute: i64,
+    counts: RequestCounts,
+}
+
+/// Request and server error counts for the last [`WINDOW_MINUTES`], in
+/// one-minute buckets, plus when the process started serving.
+///
+/// Kept in memory only, so the numbers start over on every restart.
+pub struct RequestMetrics {
+    started_at: DateTime<Utc>,
+    buckets: Mutex<VecDeque<Bucket>>,
+}
+
+impl RequestMetrics {
+    pub fn new(started_at: DateTime<Utc>) -> Self {
+        Self { started_at, buckets: Mutex::default() }
+    }
+
+    pub fn started_at(&self) -> DateTime<Utc> {
+        self.started_at
+    }
+
+    pub fn record(&self, now: DateTime<Utc>, server_error: bool) {
+        let minute = now.timestamp().div_euclid(60);
+        let mut buckets = self.buckets.lock().unwrap();
+
+        match buckets.back_mut() {
+            Some(bucket) if bucket.minute == minute => {
+                bucket.counts.requests += 1;
+                bucket.counts.server_errors += u64::from(server_error);
+            }
+            _ => buckets.push_back(Bucket {
+                minute,
+                counts: RequestCounts { requests: 1, server_errors: u64::from(server_error) },
+            }),
+        }
+
+        while buckets.front().is_some_and(|bucket| bucket.minute <= minute - WINDOW_MINUTES) {
+            buckets.pop_front();
+        }
+    }
+
+    /// Totals over the window ending at `now`.
+    pub fn recent(&self, now: DateTime<Utc>) -> RequestCounts {
+        let minute = now.timestamp().div_euclid(60);
+
+        self.buckets
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|bucket| bucket.minute > minute - WINDOW_MINUTES)
+            .fold(RequestCounts { requests: 0, server_errors: 0 }, |total, bucket| RequestCounts {
+                requests: total.requests + bucket.counts.requests,
+                server_errors: total.server_errors + bucket.counts.server_errors,
+            })
+    }
+}
@@ -0,0 +1,72 @@
+use std::fmt::Display;
+use std::future::Future;
+use std::time::Instant;
+
+use crate::db_trace;
+use crate::modules::status::status_dto::{DependencyHealth, OverallStatus, StatusReport};
+use crate::modules::status::status_metrics::WINDOW_MINUTES;
+use crate::state::AppState;
+
+/// Share of failed requests above which the service counts as degraded.
+pub const DEGRADED_ERROR_RATE: f64 = 0.05;
+
+/// Key probed in attachment storage. Never written, so the probe only checks
+/// that storage can be reached.
+const STORAGE_PROBE_KEY: &str = "status-probe";
+
+/// Reported for a failed check. The status page is public, so the actual error
+/// only goes to the logs.
+pub const UNAVAILABLE: &str = "unavailable";
+
+pub async fn status_report(state: &AppState) -> StatusReport {
+    let now = state.clock.now();
+    let counts = state.request_metrics.recent(now);
+    let error_rate = (counts.requests > 0).then(|| counts.server_errors as f64 / counts.requests as f64);
+
+    let database = check("database", async {
+        db_trace::query("SELECT 1").execute(&state.pool).await.map(|_| ())
+    })
+    .await;
+    let storage = check("attachment_storage", async {
+        state.storage.exists(STORAGE_PROBE_KEY).await.map(|_| ())
+    })
+    .await;
+
+    let status = if !database.healthy {
+        OverallStatus::Down
+    } else if !storage.healthy || error_rate.is_some_and(|rate| rate > DEGRADED_ERROR_RATE) {
+        OverallStatus::Degraded
+    } else {
+        OverallStatus::Ok
+    };
+
+    let started_at = state.request_metrics.started_at();
+
+    StatusReport {
+        status,
+        started_at,
+        uptime_seconds: (now - started_at).num_seconds().max(0),
+        window_minutes: WINDOW_MINUTES,
+        requests: counts.requests,
+        server_errors: counts.server_errors,
+        error_rate,
+        dependencies: vec![database, storage],
+    }
+}
+
+async fn check<E: Display>(name: &str, probe: impl Future<Output = Result<(), E>>) -> DependencyHealth {
+    let started = Instant::now();
+    let result = probe.await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    if let Err(err) = &result {
+        tracing::warn!("Status check of {name} failed: {err}");
+    }
+
+    DependencyHealth {
+        name: name.to_string(),
+        healthy: result.is_ok(),
+        latency_ms,
+        error: result.err().map(|_| UNAVAILABLE.to_string()),
+    }
+}
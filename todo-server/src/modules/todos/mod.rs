@@ -0,0 +1,21 @@
+use axum::Router;
+use axum::routing::{get, patch, post};
+use crate::modules::todos::todo_controller::{create_item, delete_item, get_item, list_items, list_subtasks, move_item, update_item};
+use crate::state::AppState;
+
+pub mod todo_batcher;
+pub mod todo_cache;
+pub mod todo_controller;
+pub mod todo_hooks;
+pub mod todo_service;
+pub mod todo_entity;
+pub mod todo_dto;
+
+
+pub fn create_item_routes() -> Router<AppState> {
+    Router::new()
+        .route("/", post(create_item).get(list_items))
+        .route("/:id", get(get_item).put(update_item).patch(update_item).delete(delete_item))
+        .route("/:id/move", patch(move_item))
+        .route("/:id/subtasks", get(list_subtasks))
+}
\ No newline at end of file
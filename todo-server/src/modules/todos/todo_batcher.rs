@@ -0,0 +1,137 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::sqlite::SqlitePool;
+use todo_core::item_service;
+use tokio::sync::{mpsc, oneshot};
+use crate::error::AppError;
+use crate::extract::Actor;
+use crate::id_generator::IdGenerator;
+use crate::modules::audit::audit_service::AuditContext;
+use crate::modules::todos::todo_dto::CreateItemDto;
+use crate::modules::todos::todo_entity::Item;
+use crate::modules::todos::todo_service::SqliteItemRepository;
+use crate::state::AppState;
+
+pub struct ItemBatcherConfig {
+    /// Most inserts written in one transaction.
+    pub max_batch_size: usize,
+    /// How long the first insert of a batch waits for others to join it.
+    pub max_delay: Duration,
+    /// Inserts that may wait for the flusher before callers are held back.
+    pub queue_capacity: usize,
+}
+
+impl Default for ItemBatcherConfig {
+    fn default() -> Self {
+        Self {
+            max_batch_size: 100,
+            max_delay: Duration::from_millis(5),
+            queue_capacity: 1024,
+        }
+    }
+}
+
+struct PendingItem {
+    actor: Actor,
+    now: DateTime<Utc>,
+    dto: CreateItemDto,
+    ack: oneshot::Sender<Result<Item, AppError>>,
+}
+
+/// Coalesces item inserts into shared transactions.
+///
+/// SQLite pays for durability once per commit, so bursts of `POST /items`
+/// are much cheaper written together. Each insert runs in its own savepoint:
+/// one failing insert is reported to its caller alone, while a failed commit
+/// is reported to everyone in the batch. Callers only get their response
+/// once the batch is committed.
+#[derive(Clone)]
+pub struct ItemBatcher {
+    sender: mpsc::Sender<PendingItem>,
+}
+
+impl ItemBatcher {
+    /// Starts the flusher task. Must be called inside a Tokio runtime.
+    pub fn spawn(state: &AppState, config: ItemBatcherConfig) -> Self {
+        let (sender, receiver) = mpsc::channel(config.queue_capacity);
+        tokio::spawn(run(state.writer.clone(), state.ids.clone(), receiver, config));
+
+        Self { sender }
+    }
+
+    pub async fn create_item(&self, actor: Actor, now: DateTime<Utc>, dto: CreateItemDto) -> Result<Item, AppError> {
+        let (ack, done) = oneshot::channel();
+        let unavailable = || AppError::Unavailable("Item writer is not running".to_string());
+
+        self.sender
+            .send(PendingItem { actor, now, dto, ack })
+            .await
+            .map_err(|_| unavailable())?;
+
+        done.await.map_err(|_| unavailable())?
+    }
+}
+
+async fn run(pool: SqlitePool, ids: Arc<dyn IdGenerator>, mut receiver: mpsc::Receiver<PendingItem>, config: ItemBatcherConfig) {
+    while let Some(first) = receiver.recv().await {
+        let mut batch = vec![first];
+        let deadline = tokio::time::sleep(config.max_delay);
+        tokio::pin!(deadline);
+
+        while batch.len() < config.max_batch_size {
+            tokio::select! {
+                pending = receiver.recv() => match pending {
+                    Some(pending) => batch.push(pending),
+                    None => break,
+                },
+                _ = &mut deadline => break,
+            }
+        }
+
+        flush(&pool, ids.as_ref(), batch).await;
+    }
+}
+
+async fn flush(pool: &SqlitePool, ids: &dyn IdGenerator, batch: Vec<PendingItem>) {
+    let (acks, requests): (Vec<_>, Vec<_>) = batch
+        .into_iter()
+        .map(|pending| (pending.ack, (pending.actor, pending.now, pending.dto)))
+        .unzip();
+
+    // A caller that went away just drops its result.
+    match write_batch(pool, ids, requests).await {
+        Ok(results) => {
+            for (ack, result) in acks.into_iter().zip(results) {
+                let _ = ack.send(result.map_err(AppError::from));
+            }
+        }
+        Err(err) => {
+            tracing::error!("Failed to write a batch of {} items: {err}", acks.len());
+            let err = AppError::from(err);
+            for ack in acks {
+                let _ = ack.send(Err(err.clone()));
+            }
+        }
+    }
+}
+
+async fn write_batch(
+    pool: &SqlitePool,
+    ids: &dyn IdGenerator,
+    requests: Vec<(Actor, DateTime<Utc>, CreateItemDto)>,
+) -> Result<Vec<Result<Item, sqlx::Error>>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let mut results = Vec::with_capacity(requests.len());
+
+    for (actor, now, dto) in requests {
+        let audit = AuditContext { ids, actor, now };
+        let repo = SqliteItemRepository::in_transaction(&mut tx, &audit);
+        results.push(item_service::create_item(&repo, ids, now, dto).await);
+    }
+
+    tx.commit().await?;
+
+    Ok(results)
+}
@@ -0,0 +1,169 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::Serialize;
+use crate::config::AppConfig;
+use crate::modules::todos::todo_dto::{ItemListing, ItemSort};
+use crate::modules::todos::todo_entity::Item;
+
+/// Identifies one cached `GET /items` page.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct PageKey {
+    pub sort: ItemSort,
+    pub limit: i64,
+    pub cursor: Option<String>,
+    pub tree: bool,
+}
+
+/// Lookups since startup, reported by `GET /admin/cache`.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+struct Entry<T> {
+    value: T,
+    expires_at: DateTime<Utc>,
+}
+
+/// In-process cache in front of `GET /items` and `GET /items/:id`.
+///
+/// Entries live for the configured TTL and each map holds at most the
+/// configured number of entries; a TTL or capacity of zero turns caching off.
+/// Handlers invalidate after every item write. A read that started before an
+/// invalidation does not store its result, so a slow read cannot put back a
+/// value a write has just replaced.
+pub struct ItemCache {
+    ttl: TimeDelta,
+    capacity: usize,
+    generation: AtomicU64,
+    items: RwLock<HashMap<String, Entry<Item>>>,
+    pages: RwLock<HashMap<PageKey, Entry<ItemListing>>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ItemCache {
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl: TimeDelta::from_std(ttl).unwrap_or(TimeDelta::MAX),
+            capacity,
+            generation: AtomicU64::new(0),
+            items: RwLock::default(),
+            pages: RwLock::default(),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self::new(config.item_cache_ttl, config.item_cache_capacity)
+    }
+
+    fn enabled(&self) -> bool {
+        self.capacity > 0 && self.ttl > TimeDelta::zero()
+    }
+
+    /// Token to pass back to the `insert_*` methods; take it before reading
+    /// from the database.
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    pub fn item(&self, id: &str, now: DateTime<Utc>) -> Option<Item> {
+        self.lookup(&self.items, id, now)
+    }
+
+    pub fn insert_item(&self, generation: u64, item: Item, now: DateTime<Utc>) {
+        self.store(&self.items, generation, item.id.clone(), item, now);
+    }
+
+    pub fn page(&self, key: &PageKey, now: DateTime<Utc>) -> Option<ItemListing> {
+        self.lookup(&self.pages, key, now)
+    }
+
+    pub fn insert_page(&self, generation: u64, key: PageKey, page: ItemListing, now: DateTime<Utc>) {
+        self.store(&self.pages, generation, key, page, now);
+    }
+
+    /// After an item was created: every list may now include it.
+    pub fn invalidate_lists(&self) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        self.pages.write().unwrap().clear();
+    }
+
+    /// After an item was updated or deleted.
+    pub fn invalidate_item(&self, id: &str) {
+        self.invalidate_lists();
+        self.items.write().unwrap().remove(id);
+    }
+
+    /// After a change that touches many items, such as a move.
+    pub fn invalidate_all(&self) {
+        self.invalidate_lists();
+        self.items.write().unwrap().clear();
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    fn lookup<K, Q, T>(&self, map: &RwLock<HashMap<K, Entry<T>>>, key: &Q, now: DateTime<Utc>) -> Option<T>
+    where
+        K: Eq + Hash + Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+        T: Clone,
+    {
+        if !self.enabled() {
+            return None;
+        }
+
+        let value = map
+            .read()
+            .unwrap()
+            .get(key)
+            .filter(|entry| entry.expires_at > now)
+            .map(|entry| entry.value.clone());
+
+        let counter = if value.is_some() { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        tracing::debug!(target: "cache", hit = value.is_some(), "item cache lookup");
+
+        value
+    }
+
+    fn store<K: Eq + Hash + Clone, T>(&self, map: &RwLock<HashMap<K, Entry<T>>>, generation: u64, key: K, value: T, now: DateTime<Utc>) {
+        if !self.enabled() {
+            return;
+        }
+
+        let mut map = map.write().unwrap();
+        // Checked under the lock, so an invalidation cannot slip in between.
+        if generation != self.generation() {
+            return;
+        }
+
+        if map.len() >= self.capacity && !map.contains_key(&key) {
+            map.retain(|_, entry| entry.expires_at > now);
+        }
+        if map.len() >= self.capacity && !map.contains_key(&key) {
+            // Every entry shares the TTL, so the soonest to expire is the oldest.
+            let oldest = map.iter().min_by_key(|(_, entry)| entry.expires_at).map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                map.remove(&oldest);
+            }
+        }
+
+        let expires_at = now.checked_add_signed(self.ttl).unwrap_or(DateTime::<Utc>::MAX_UTC);
+        map.insert(key, Entry { value, expires_at });
+    }
+}
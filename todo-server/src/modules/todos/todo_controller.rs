@@ -0,0 +1,208 @@
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::Response,
+};
+
+use crate::error::AppError;
+use crate::extract::{Actor, Json, ValidatedQuery};
+use crate::http_cache;
+use crate::modules::attachments::attachment_service;
+use crate::modules::audit::audit_service::AuditContext;
+use crate::modules::todos::todo_dto::{CreateItemDto, ItemCursor, ItemListing, ListItemsQuery, MoveItemDto, Patch, UpdateItemDto};
+use crate::modules::todos::todo_cache::PageKey;
+use crate::modules::todos::todo_entity::Item;
+use crate::modules::todos::todo_service;
+use crate::pagination;
+use crate::state::AppState;
+
+pub async fn create_item(
+    State(state): State<AppState>,
+    actor: Actor,
+    Json(payload): Json<CreateItemDto>,
+) -> Result<Json<Item>, AppError> {
+    Ok(Json(create(&state, actor, payload).await?))
+}
+
+pub async fn list_items(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<ListItemsQuery>,
+) -> Result<Json<ItemListing>, AppError> {
+    Ok(Json(list(&state, query).await?))
+}
+
+/// Supports conditional requests, answering 304 when the client's copy is
+/// current.
+pub async fn get_item(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let item = fetch(&state, id).await?;
+
+    http_cache::conditional_json(&headers, item.updated_at, &item)
+}
+
+// The functions below do the work of the handlers above and are shared with
+// the gRPC service, so both transports validate, batch, cache and clean up
+// the same way.
+
+pub(crate) async fn create(state: &AppState, actor: Actor, payload: CreateItemDto) -> Result<Item, AppError> {
+    let payload = state.item_hooks.on_create(payload).await?;
+    if let Some(parent_id) = &payload.parent_id {
+        ensure_parent_exists(state, parent_id).await?;
+    }
+
+    let item = match &state.item_batcher {
+        Some(batcher) => batcher.create_item(actor, state.clock.now(), payload).await?,
+        None => todo_service::create_item(&state.writer, &AuditContext::new(state, actor), payload).await?,
+    };
+    state.item_cache.invalidate_lists();
+
+    Ok(item)
+}
+
+pub(crate) async fn list(state: &AppState, query: ListItemsQuery) -> Result<ItemListing, AppError> {
+    let limit = query.limit;
+    let key = PageKey { sort: query.sort, limit, cursor: query.cursor.clone(), tree: query.tree };
+    if let Some(page) = state.item_cache.page(&key, state.clock.now()) {
+        return Ok(page);
+    }
+
+    let after = pagination::parse_cursor::<ItemCursor>(query.cursor.as_deref())?;
+    if after.as_ref().is_some_and(|cursor| cursor.sort != query.sort) {
+        return Err(pagination::invalid_cursor());
+    }
+
+    let generation = state.item_cache.generation();
+    let page = if query.tree {
+        ItemListing::Tree(todo_service::list_item_tree(&state.pool, query.sort, limit, after).await?)
+    } else {
+        ItemListing::Flat(todo_service::list_items(&state.pool, query.sort, false, limit, after).await?)
+    };
+    state.item_cache.insert_page(generation, key, page.clone(), state.clock.now());
+
+    Ok(page)
+}
+
+pub(crate) async fn fetch(state: &AppState, id: String) -> Result<Item, AppError> {
+    if let Some(item) = state.item_cache.item(&id, state.clock.now()) {
+        return Ok(item);
+    }
+
+    let generation = state.item_cache.generation();
+    let item = todo_service::get_item(&state.pool, id).await?;
+    state.item_cache.insert_item(generation, item.clone(), state.clock.now());
+
+    Ok(item)
+}
+
+pub(crate) async fn update(state: &AppState, actor: Actor, id: String, payload: UpdateItemDto) -> Result<(), AppError> {
+    let payload = if state.item_hooks.is_empty() {
+        payload
+    } else {
+        let before = todo_service::get_item(&state.pool, id.clone()).await?;
+        state.item_hooks.on_update(&before, payload).await?
+    };
+    if let Patch::Value(parent_id) = &payload.parent_id {
+        ensure_parent_exists(state, parent_id).await?;
+    }
+
+    let result = todo_service::update_item(&state.writer, &AuditContext::new(state, actor), id.clone(), payload).await;
+    state.item_cache.invalidate_item(&id);
+    result?;
+
+    Ok(())
+}
+
+pub(crate) async fn reposition(state: &AppState, actor: Actor, id: String, position: i64) -> Result<Item, AppError> {
+    if position < 0 {
+        return Err(AppError::Unprocessable("position must not be negative".to_string()));
+    }
+
+    let result = todo_service::move_item(&state.writer, &AuditContext::new(state, actor), id, position).await;
+    state.item_cache.invalidate_all();
+
+    Ok(result?)
+}
+
+pub(crate) async fn remove(state: &AppState, actor: Actor, id: String) -> Result<(), AppError> {
+    if !state.item_hooks.is_empty() {
+        // Deleting a missing item succeeds without running hooks, as it does
+        // without them.
+        match todo_service::get_item(&state.pool, id.clone()).await {
+            Ok(item) => state.item_hooks.on_delete(&item).await?,
+            Err(sqlx::Error::RowNotFound) => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    // Attachment rows go with the item and its subtasks; their files have to
+    // be removed here.
+    let mut attachments = attachment_service::list_attachments(&state.pool, id.clone()).await?;
+    for subtask in todo_service::list_descendants(&state.pool, std::slice::from_ref(&id)).await? {
+        attachments.extend(attachment_service::list_attachments(&state.pool, subtask.id).await?);
+    }
+
+    let result = todo_service::delete_item(&state.writer, &AuditContext::new(state, actor), id).await;
+    state.item_cache.invalidate_all();
+    result?;
+
+    for attachment in attachments {
+        if let Err(err) = state.storage.delete(&attachment.id).await {
+            tracing::warn!("Failed to delete file of attachment {}: {err}", attachment.id);
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn list_subtasks(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<Item>>, AppError> {
+    todo_service::get_item(&state.pool, id.clone()).await?;
+    let subtasks = todo_service::list_subtasks(&state.pool, id).await?;
+
+    Ok(Json(subtasks))
+}
+
+pub async fn update_item(
+    State(state): State<AppState>,
+    actor: Actor,
+    Path(id): Path<String>,
+    Json(payload): Json<UpdateItemDto>,
+) -> Result<StatusCode, AppError> {
+    update(&state, actor, id, payload).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn move_item(
+    State(state): State<AppState>,
+    actor: Actor,
+    Path(id): Path<String>,
+    Json(payload): Json<MoveItemDto>,
+) -> Result<Json<Item>, AppError> {
+    Ok(Json(reposition(&state, actor, id, payload.position).await?))
+}
+
+pub async fn delete_item(
+    State(state): State<AppState>,
+    actor: Actor,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    remove(&state, actor, id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// A missing parent is a problem with the request body, not a conflict, so
+/// check before the foreign key gets a chance to reject it.
+async fn ensure_parent_exists(state: &AppState, parent_id: &str) -> Result<(), AppError> {
+    match todo_service::get_item(&state.pool, parent_id.to_string()).await {
+        Ok(_) => Ok(()),
+        Err(sqlx::Error::RowNotFound) => Err(AppError::Unprocessable("Parent item not found".to_string())),
+        Err(err) => Err(err.into()),
+    }
+}
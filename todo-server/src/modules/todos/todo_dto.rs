@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::extract::{check_range, Validate};
+use crate::modules::todos::todo_entity::Item;
+use crate::pagination::{self, Cursor, Paginated};
+
+pub use todo_core::item::{CreateItemDto, Patch, UpdateItemDto};
+
+#[derive(Serialize, Deserialize)]
+pub struct MoveItemDto {
+    /// Zero-based target index in the manual ordering. Positions past the
+    /// end move the item to the end.
+    pub position: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ItemSort {
+    /// Oldest first.
+    #[default]
+    Created,
+    /// Manual ordering.
+    Position,
+    /// Most urgent first, then manual ordering.
+    Priority,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ListItemsQuery {
+    #[serde(default = "pagination::default_page_size")]
+    pub limit: i64,
+    pub cursor: Option<String>,
+    #[serde(default)]
+    pub sort: ItemSort,
+    /// Page over top-level items only, each with its subtasks nested.
+    #[serde(default)]
+    pub tree: bool,
+}
+
+impl Validate for ListItemsQuery {
+    fn validate(&self) -> Result<(), String> {
+        check_range("limit", self.limit, 1..=pagination::MAX_PAGE_SIZE)
+    }
+}
+
+/// An item with its subtasks, at any depth.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ItemNode {
+    #[serde(flatten)]
+    pub item: Item,
+    pub subtasks: Vec<ItemNode>,
+}
+
+impl ItemNode {
+    /// Arranges `roots` and their `descendants` into trees. Subtasks keep the
+    /// order they have in `descendants`.
+    pub fn build_trees(roots: Vec<Item>, descendants: Vec<Item>) -> Vec<ItemNode> {
+        let mut children: HashMap<String, Vec<Item>> = HashMap::new();
+        for item in descendants {
+            if let Some(parent_id) = item.parent_id.clone() {
+                children.entry(parent_id).or_default().push(item);
+            }
+        }
+
+        roots.into_iter().map(|root| Self::build(root, &mut children)).collect()
+    }
+
+    fn build(item: Item, children: &mut HashMap<String, Vec<Item>>) -> ItemNode {
+        let subtasks = children
+            .remove(&item.id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|child| Self::build(child, children))
+            .collect();
+
+        ItemNode { item, subtasks }
+    }
+}
+
+/// Response of `GET /items`: flat unless `tree=true` was asked for.
+#[derive(Serialize, Clone)]
+#[serde(untagged)]
+pub enum ItemListing {
+    Flat(Paginated<Item>),
+    /// Top-level items only; pass the cursor back with `tree=true`.
+    Tree(Paginated<ItemNode>),
+}
+
+/// Position of an item in one of the [`ItemSort`] orderings.
+///
+/// Clients only ever see it as an opaque base64url string. A cursor is only
+/// valid for the sort it was issued for.
+pub struct ItemCursor {
+    pub sort: ItemSort,
+    pub created_at: DateTime<Utc>,
+    pub position: i64,
+    pub priority_rank: i64,
+    pub id: String,
+}
+
+impl ItemCursor {
+    pub fn after(sort: ItemSort, item: &Item) -> Self {
+        Self {
+            sort,
+            created_at: item.created_at,
+            position: item.position,
+            priority_rank: item.priority.rank(),
+            id: item.id.clone(),
+        }
+    }
+}
+
+impl Cursor for ItemCursor {
+    const FIELDS: usize = 5;
+
+    fn to_fields(&self) -> Vec<String> {
+        let sort = match self.sort {
+            ItemSort::Created => "c",
+            ItemSort::Position => "p",
+            ItemSort::Priority => "r",
+        };
+
+        vec![
+            sort.to_string(),
+            self.created_at.to_rfc3339(),
+            self.position.to_string(),
+            self.priority_rank.to_string(),
+            self.id.clone(),
+        ]
+    }
+
+    fn from_fields(fields: &[&str]) -> Option<Self> {
+        let sort = match fields[0] {
+            "c" => ItemSort::Created,
+            "p" => ItemSort::Position,
+            "r" => ItemSort::Priority,
+            _ => return None,
+        };
+
+        Some(Self {
+            sort,
+            created_at: DateTime::parse_from_rfc3339(fields[1]).ok()?.with_timezone(&Utc),
+            position: fields[2].parse().ok()?,
+            priority_rank: fields[3].parse().ok()?,
+            id: fields[4].to_string(),
+        })
+    }
+}
@@ -0,0 +1,4 @@
+//! The item entity lives in `todo-core`, so the domain rules can be used and
+//! tested without the server.
+
+pub use todo_core::item::{Item, Priority};
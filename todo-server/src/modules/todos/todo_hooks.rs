@@ -0,0 +1,279 @@
+//! Operator scripts run on item lifecycle events.
+//!
+//! Each script is a [Rhai](https://rhai.rs) file that may define any of:
+//!
+//! ```text
+//! fn on_create(item) { ... }          // before an item is created
+//! fn on_update(item, before) { ... }  // before an item is changed
+//! fn on_delete(item) { ... }          // before an item is deleted
+//! ```
+//!
+//! `item` is a map with `id`, `name`, `description`, `priority` and
+//! `parent_id`; on create `id` is `()`. Returning the map, changed or not,
+//! replaces the item that will be written; returning nothing keeps it.
+//! Only `name`, `description` and `priority` can be changed this way. A
+//! `throw "reason"` vetoes the operation, which then fails with 422 and the
+//! reason. Scripts run in file name order, each seeing the output of the
+//! previous one, under the limits of [`ItemHookLimits`].
+
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use rhai::{Dynamic, Engine, EvalAltResult, Map, Scope, AST};
+use crate::error::AppError;
+use crate::modules::todos::todo_dto::{CreateItemDto, Patch, UpdateItemDto};
+use crate::modules::todos::todo_entity::{Item, Priority};
+
+/// Bounds every script call is held to. A script that exceeds one fails,
+/// and so does the operation it was called for.
+#[derive(Clone, Debug)]
+pub struct ItemHookLimits {
+    /// Wall-clock time one call may take.
+    pub timeout: Duration,
+    pub max_operations: u64,
+    /// Longest string, in bytes, a script may build.
+    pub max_string_size: usize,
+    /// Most elements in an array or map.
+    pub max_collection_size: usize,
+    pub max_call_levels: usize,
+}
+
+impl Default for ItemHookLimits {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_millis(50),
+            max_operations: 100_000,
+            max_string_size: 64 * 1024,
+            max_collection_size: 1_000,
+            max_call_levels: 32,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum HookEvent {
+    Create,
+    Update,
+    Delete,
+}
+
+impl HookEvent {
+    fn function(self) -> &'static str {
+        match self {
+            HookEvent::Create => "on_create",
+            HookEvent::Update => "on_update",
+            HookEvent::Delete => "on_delete",
+        }
+    }
+}
+
+struct Script {
+    name: String,
+    ast: AST,
+}
+
+/// The loaded scripts. Empty by default, in which case every hook is a
+/// no-op.
+#[derive(Default)]
+pub struct ItemHooks {
+    scripts: Vec<Script>,
+    limits: ItemHookLimits,
+}
+
+impl ItemHooks {
+    /// Compiles `(name, source)` pairs, run in the given order.
+    pub fn compile<'a>(
+        scripts: impl IntoIterator<Item = (&'a str, &'a str)>,
+        limits: ItemHookLimits,
+    ) -> Result<Self, String> {
+        let engine = engine(&limits, None);
+        let scripts = scripts
+            .into_iter()
+            .map(|(name, source)| {
+                let ast = engine.compile(source).map_err(|err| format!("{name}: {err}"))?;
+                Ok(Script { name: name.to_string(), ast })
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Self { scripts, limits })
+    }
+
+    /// Compiles every `*.rhai` file in `dir`, in file name order.
+    pub fn load_dir(dir: &Path, limits: ItemHookLimits) -> io::Result<Self> {
+        let mut paths = std::fs::read_dir(dir)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<io::Result<Vec<_>>>()?;
+        paths.retain(|path| path.extension().is_some_and(|extension| extension == "rhai"));
+        paths.sort();
+
+        let sources = paths
+            .iter()
+            .map(|path| {
+                let name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+                Ok((name, std::fs::read_to_string(path)?))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        Self::compile(sources.iter().map(|(name, source)| (name.as_str(), source.as_str())), limits)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scripts.is_empty()
+    }
+
+    fn handles(&self, event: HookEvent) -> bool {
+        self.scripts.iter().any(|script| defines(script, event))
+    }
+
+    /// The item to create, as changed by the scripts.
+    pub async fn on_create(self: &Arc<Self>, dto: CreateItemDto) -> Result<CreateItemDto, AppError> {
+        if !self.handles(HookEvent::Create) {
+            return Ok(dto);
+        }
+
+        let parent_id = dto.parent_id.clone();
+        let item = item_map(None, &dto.name, dto.description.as_deref(), dto.priority, parent_id.as_deref());
+        let item = self.run_blocking(HookEvent::Create, item, None).await?;
+        let (name, description, priority) = item_fields(&item)?;
+
+        Ok(CreateItemDto { name, description, priority, parent_id })
+    }
+
+    /// The update to apply to `before`, as changed by the scripts. Scripts
+    /// see the item as it would be after `dto`.
+    pub async fn on_update(self: &Arc<Self>, before: &Item, dto: UpdateItemDto) -> Result<UpdateItemDto, AppError> {
+        if !self.handles(HookEvent::Update) {
+            return Ok(dto);
+        }
+
+        let description = dto.description.apply(before.description.clone());
+        let priority = dto.priority.unwrap_or(before.priority);
+        let parent_id = dto.parent_id.clone().apply(before.parent_id.clone());
+        let item = item_map(
+            Some(&before.id),
+            dto.name.as_deref().unwrap_or(&before.name),
+            description.as_deref(),
+            priority,
+            parent_id.as_deref(),
+        );
+        let before_map = item_map(Some(&before.id), &before.name, before.description.as_deref(), before.priority, before.parent_id.as_deref());
+
+        let item = self.run_blocking(HookEvent::Update, item, Some(before_map)).await?;
+        let (name, description, priority) = item_fields(&item)?;
+
+        Ok(UpdateItemDto {
+            name: Some(name),
+            description: description.map_or(Patch::Null, Patch::Value),
+            priority: Some(priority),
+            parent_id: dto.parent_id,
+        })
+    }
+
+    /// Fails when a script vetoes deleting `item`.
+    pub async fn on_delete(self: &Arc<Self>, item: &Item) -> Result<(), AppError> {
+        if !self.handles(HookEvent::Delete) {
+            return Ok(());
+        }
+
+        let item = item_map(Some(&item.id), &item.name, item.description.as_deref(), item.priority, item.parent_id.as_deref());
+        self.run_blocking(HookEvent::Delete, item, None).await?;
+
+        Ok(())
+    }
+
+    /// Scripts are CPU-bound, so they run off the async workers.
+    async fn run_blocking(self: &Arc<Self>, event: HookEvent, item: Map, before: Option<Map>) -> Result<Map, AppError> {
+        let hooks = Arc::clone(self);
+
+        tokio::task::spawn_blocking(move || hooks.run(event, item, before))
+            .await
+            .map_err(|err| AppError::Internal(format!("Item hook panicked: {err}")))?
+    }
+
+    fn run(&self, event: HookEvent, mut item: Map, before: Option<Map>) -> Result<Map, AppError> {
+        for script in self.scripts.iter().filter(|script| defines(script, event)) {
+            let engine = engine(&self.limits, Some(Instant::now() + self.limits.timeout));
+            let mut scope = Scope::new();
+            let result = match &before {
+                Some(before) => engine.call_fn::<Dynamic>(&mut scope, &script.ast, event.function(), (item.clone(), before.clone())),
+                None => engine.call_fn::<Dynamic>(&mut scope, &script.ast, event.function(), (item.clone(),)),
+            };
+
+            match result {
+                Ok(value) if value.is_unit() => {}
+                Ok(value) => {
+                    item = value.try_cast::<Map>().ok_or_else(|| {
+                        AppError::Internal(format!("Item hook {} must return the item or nothing", script.name))
+                    })?;
+                }
+                Err(err) => return Err(hook_error(&script.name, *err)),
+            }
+        }
+
+        Ok(item)
+    }
+}
+
+fn defines(script: &Script, event: HookEvent) -> bool {
+    script.ast.iter_functions().any(|function| function.name == event.function())
+}
+
+/// An engine held to `limits`, that gives up once `deadline` passes.
+fn engine(limits: &ItemHookLimits, deadline: Option<Instant>) -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_max_operations(limits.max_operations)
+        .set_max_string_size(limits.max_string_size)
+        .set_max_array_size(limits.max_collection_size)
+        .set_max_map_size(limits.max_collection_size)
+        .set_max_call_levels(limits.max_call_levels)
+        .on_print(|text| tracing::info!(target: "item_hooks", "{text}"))
+        .on_debug(|text, _, _| tracing::debug!(target: "item_hooks", "{text}"));
+
+    if let Some(deadline) = deadline {
+        engine.on_progress(move |_| (Instant::now() > deadline).then(|| Dynamic::from("timeout")));
+    }
+
+    engine
+}
+
+fn hook_error(script: &str, err: EvalAltResult) -> AppError {
+    match err {
+        EvalAltResult::ErrorRuntime(reason, _) => AppError::Unprocessable(reason.to_string()),
+        EvalAltResult::ErrorTerminated(..) => AppError::Internal(format!("Item hook {script} timed out")),
+        err => AppError::Internal(format!("Item hook {script} failed: {err}")),
+    }
+}
+
+fn item_map(id: Option<&str>, name: &str, description: Option<&str>, priority: Priority, parent_id: Option<&str>) -> Map {
+    let optional = |value: Option<&str>| value.map_or(Dynamic::UNIT, |value| value.to_string().into());
+
+    let mut map = Map::new();
+    map.insert("id".into(), optional(id));
+    map.insert("name".into(), name.into());
+    map.insert("description".into(), optional(description));
+    map.insert("priority".into(), priority.as_str().into());
+    map.insert("parent_id".into(), optional(parent_id));
+    map
+}
+
+/// The writable fields of an item returned by a script.
+fn item_fields(item: &Map) -> Result<(String, Option<String>, Priority), AppError> {
+    let invalid = |field: &str| AppError::Internal(format!("Item hook returned an invalid {field}"));
+    let string = |field: &str| item.get(field).and_then(|value| value.clone().into_string().ok());
+
+    let name = string("name").ok_or_else(|| invalid("name"))?;
+    let description = match item.get("description") {
+        None => None,
+        Some(value) if value.is_unit() => None,
+        Some(_) => Some(string("description").ok_or_else(|| invalid("description"))?),
+    };
+    let priority = string("priority")
+        .and_then(|priority| priority.parse().ok())
+        .ok_or_else(|| invalid("priority"))?;
+
+    Ok((name, description.filter(|description| !description.is_empty()), priority))
+}
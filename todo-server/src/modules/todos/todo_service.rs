@@ -0,0 +1,430 @@
+use std::ops::{Deref, DerefMut};
+
+use async_trait::async_trait;
+use sqlx::pool::PoolConnection;
+use sqlx::sqlite::{Sqlite, SqliteConnection, SqlitePool};
+use sqlx::types::Json;
+use sqlx::Connection;
+use tokio::sync::{Mutex, MutexGuard};
+use todo_core::item_service::{self, ItemError};
+use todo_core::repository::{Change, ItemRepository, Placement};
+use crate::db_trace;
+use crate::modules::audit::audit_entity::AuditAction;
+use crate::modules::attachments::attachment_service;
+use crate::modules::audit::audit_service::{self, AuditContext};
+use crate::modules::comments::comment_service;
+use crate::modules::reminders::reminder_service;
+use crate::modules::todos::todo_dto::{CreateItemDto, ItemCursor, ItemNode, ItemSort, UpdateItemDto};
+use crate::modules::todos::todo_entity::Item;
+use crate::pagination::Paginated;
+
+// Changes go through the `todo-core` item service, which decides what they
+// do, over `SqliteItemRepository`, which stores them. Reads are plain
+// queries.
+
+/// Stores items in SQLite, recording every change in the audit log on the
+/// change's own transaction.
+pub struct SqliteItemRepository<'a> {
+    conn: Source<'a>,
+    audit: &'a AuditContext<'a>,
+}
+
+enum Source<'a> {
+    Pool(&'a SqlitePool),
+    /// A connection with a transaction already open; each change gets a
+    /// savepoint in it.
+    Transaction(Mutex<&'a mut SqliteConnection>),
+}
+
+impl<'a> SqliteItemRepository<'a> {
+    /// Changes are made on `pool` and recorded under `audit`.
+    pub fn new(pool: &'a SqlitePool, audit: &'a AuditContext<'a>) -> Self {
+        Self { conn: Source::Pool(pool), audit }
+    }
+
+    /// Changes are made inside the transaction open on `conn`, so several of
+    /// them can share one commit. A failed change is rolled back to its own
+    /// savepoint and leaves the transaction usable.
+    pub fn in_transaction(conn: &'a mut SqliteConnection, audit: &'a AuditContext<'a>) -> Self {
+        Self { conn: Source::Transaction(Mutex::new(conn)), audit }
+    }
+
+    async fn acquire(&self) -> Result<SourceConnection<'_, 'a>, sqlx::Error> {
+        match &self.conn {
+            Source::Pool(pool) => Ok(SourceConnection::Pool(pool.acquire().await?)),
+            Source::Transaction(conn) => Ok(SourceConnection::Transaction(conn.lock().await)),
+        }
+    }
+}
+
+enum SourceConnection<'r, 'a> {
+    Pool(PoolConnection<Sqlite>),
+    Transaction(MutexGuard<'r, &'a mut SqliteConnection>),
+}
+
+impl Deref for SourceConnection<'_, '_> {
+    type Target = SqliteConnection;
+
+    fn deref(&self) -> &SqliteConnection {
+        match self {
+            SourceConnection::Pool(conn) => conn,
+            SourceConnection::Transaction(conn) => conn,
+        }
+    }
+}
+
+impl DerefMut for SourceConnection<'_, '_> {
+    fn deref_mut(&mut self) -> &mut SqliteConnection {
+        match self {
+            SourceConnection::Pool(conn) => conn,
+            SourceConnection::Transaction(conn) => conn,
+        }
+    }
+}
+
+pub async fn create_item(pool: &SqlitePool, audit: &AuditContext<'_>, dto: CreateItemDto) -> Result<Item, sqlx::Error> {
+    item_service::create_item(&SqliteItemRepository::new(pool, audit), audit.ids, audit.now, dto).await
+}
+
+/// Creates every item of `dtos`, in order, in one transaction, so either all
+/// of them are created or none are. `progress` is told how many have been
+/// inserted after each one.
+pub async fn create_items(
+    pool: &SqlitePool,
+    audit: &AuditContext<'_>,
+    dtos: Vec<CreateItemDto>,
+    progress: &mut (dyn FnMut(usize) + Send),
+) -> Result<Vec<Item>, sqlx::Error> {
+    item_service::create_items(&SqliteItemRepository::new(pool, audit), audit.ids, audit.now, dtos, progress).await
+}
+
+async fn store_new_item(conn: &mut SqliteConnection, audit: &AuditContext<'_>, mut item: Item) -> Result<Item, sqlx::Error> {
+    // New items go to the end of the manual ordering.
+    let (position,): (i64,) = db_trace::query_as(
+        "INSERT INTO items (id, name, description, created_at, updated_at, priority, position, parent_id) \
+         VALUES (?, ?, COALESCE(?, ''), ?, ?, ?, (SELECT COALESCE(MAX(position), -1) + 1 FROM items), ?) \
+         RETURNING position",
+    )
+        .bind(&item.id)
+        .bind(&item.name)
+        .bind(&item.description)
+        .bind(item.created_at)
+        .bind(item.updated_at)
+        .bind(item.priority)
+        .bind(&item.parent_id)
+        .fetch_one(&mut *conn)
+        .await?;
+    item.position = position;
+
+    audit_service::record(conn, audit, audit_service::ITEM, &item.id, AuditAction::Create, None, Some(&item)).await?;
+
+    Ok(item)
+}
+
+/// One page of items in `sort` order, starting after `after`. With
+/// `roots_only`, subtasks are left out.
+///
+/// Uses keyset pagination (on `idx_items_created_at_id` and
+/// `idx_items_position_id` for the created and position sorts), so deep pages
+/// cost the same as the first one. `after` must come from the same sort.
+pub async fn list_items(pool: &SqlitePool, sort: ItemSort, roots_only: bool, limit: i64, after: Option<ItemCursor>) -> Result<Paginated<Item>, sqlx::Error> {
+    // Fetch one extra row to learn whether another page follows.
+    let query = match (sort, &after) {
+        (ItemSort::Created, None) => db_trace::query_as(
+            "SELECT id, name, NULLIF(description, '') AS description, created_at, updated_at, priority, position, parent_id FROM items \
+             WHERE (? OR parent_id IS NULL) \
+             ORDER BY created_at, id LIMIT ?",
+        )
+            .bind(!roots_only),
+        (ItemSort::Created, Some(cursor)) => db_trace::query_as(
+            "SELECT id, name, NULLIF(description, '') AS description, created_at, updated_at, priority, position, parent_id FROM items \
+             WHERE (? OR parent_id IS NULL) AND (created_at, id) > (?, ?) \
+             ORDER BY created_at, id LIMIT ?",
+        )
+            .bind(!roots_only)
+            .bind(cursor.created_at)
+            .bind(cursor.id.clone()),
+        (ItemSort::Position, None) => db_trace::query_as(
+            "SELECT id, name, NULLIF(description, '') AS description, created_at, updated_at, priority, position, parent_id FROM items \
+             WHERE (? OR parent_id IS NULL) \
+             ORDER BY position, id LIMIT ?",
+        )
+            .bind(!roots_only),
+        (ItemSort::Position, Some(cursor)) => db_trace::query_as(
+            "SELECT id, name, NULLIF(description, '') AS description, created_at, updated_at, priority, position, parent_id FROM items \
+             WHERE (? OR parent_id IS NULL) AND (position, id) > (?, ?) \
+             ORDER BY position, id LIMIT ?",
+        )
+            .bind(!roots_only)
+            .bind(cursor.position)
+            .bind(cursor.id.clone()),
+        (ItemSort::Priority, None) => db_trace::query_as(
+            "SELECT id, name, NULLIF(description, '') AS description, created_at, updated_at, priority, position, parent_id FROM items \
+             WHERE (? OR parent_id IS NULL) \
+             ORDER BY CASE priority WHEN 'high' THEN 0 WHEN 'medium' THEN 1 ELSE 2 END, position, id LIMIT ?",
+        )
+            .bind(!roots_only),
+        (ItemSort::Priority, Some(cursor)) => db_trace::query_as(
+            "SELECT id, name, NULLIF(description, '') AS description, created_at, updated_at, priority, position, parent_id FROM items \
+             WHERE (? OR parent_id IS NULL) AND (CASE priority WHEN 'high' THEN 0 WHEN 'medium' THEN 1 ELSE 2 END, position, id) > (?, ?, ?) \
+             ORDER BY CASE priority WHEN 'high' THEN 0 WHEN 'medium' THEN 1 ELSE 2 END, position, id LIMIT ?",
+        )
+            .bind(!roots_only)
+            .bind(cursor.priority_rank)
+            .bind(cursor.position)
+            .bind(cursor.id.clone()),
+    };
+    let items: Vec<Item> = query.bind(limit + 1).fetch_all(pool).await?;
+
+    Ok(Paginated::from_rows(items, limit, |item| ItemCursor::after(sort, item)))
+}
+
+/// One page of top-level items, each with all of its subtasks nested.
+pub async fn list_item_tree(pool: &SqlitePool, sort: ItemSort, limit: i64, after: Option<ItemCursor>) -> Result<Paginated<ItemNode>, sqlx::Error> {
+    let page = list_items(pool, sort, true, limit, after).await?;
+    let root_ids: Vec<String> = page.items.iter().map(|item| item.id.clone()).collect();
+    let descendants = list_descendants(pool, &root_ids).await?;
+
+    Ok(page.map_items(|roots| ItemNode::build_trees(roots, descendants)))
+}
+
+pub async fn get_item(pool: &SqlitePool, id: String) -> Result<Item, sqlx::Error> {
+    let item = db_trace::query_as("SELECT id, name, NULLIF(description, '') AS description, created_at, updated_at, priority, position, parent_id FROM items WHERE id = ?")
+        .bind(id)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(item)
+}
+
+/// The id, name and description of every item, for telling whether some
+/// content is already there.
+pub async fn list_contents(pool: &SqlitePool) -> Result<Vec<(String, String, Option<String>)>, sqlx::Error> {
+    db_trace::query_as("SELECT id, name, NULLIF(description, '') FROM items")
+        .fetch_all(pool)
+        .await
+}
+
+/// Direct subtasks of an item, in manual order.
+pub async fn list_subtasks(pool: &SqlitePool, parent_id: String) -> Result<Vec<Item>, sqlx::Error> {
+    let subtasks = db_trace::query_as(
+        "SELECT id, name, NULLIF(description, '') AS description, created_at, updated_at, priority, position, parent_id FROM items \
+         WHERE parent_id = ? ORDER BY position, id",
+    )
+        .bind(parent_id)
+        .fetch_all(pool)
+        .await?;
+
+    Ok(subtasks)
+}
+
+const DESCENDANTS: &str = "WITH RECURSIVE descendants (id) AS ( \
+         SELECT id FROM items WHERE parent_id IN (SELECT value FROM json_each(?)) \
+         UNION \
+         SELECT items.id FROM items JOIN descendants ON items.parent_id = descendants.id \
+     ) \
+     SELECT id, name, NULLIF(description, '') AS description, created_at, updated_at, priority, position, parent_id FROM items \
+     WHERE id IN (SELECT id FROM descendants) ORDER BY position, id";
+
+/// Every subtask below the given items, at any depth, in manual order.
+pub async fn list_descendants(pool: &SqlitePool, ids: &[String]) -> Result<Vec<Item>, sqlx::Error> {
+    let descendants = db_trace::query_as(DESCENDANTS)
+        .bind(Json(ids))
+        .fetch_all(pool)
+        .await?;
+
+    Ok(descendants)
+}
+
+/// [`list_descendants`] inside an open transaction.
+async fn fetch_descendants(conn: &mut SqliteConnection, ids: &[String]) -> Result<Vec<Item>, sqlx::Error> {
+    db_trace::query_as(DESCENDANTS)
+        .bind(Json(ids))
+        .fetch_all(conn)
+        .await
+}
+
+/// [`get_item`] inside an open transaction.
+async fn fetch_item(conn: &mut SqliteConnection, id: &str) -> Result<Item, sqlx::Error> {
+    db_trace::query_as("SELECT id, name, NULLIF(description, '') AS description, created_at, updated_at, priority, position, parent_id FROM items WHERE id = ?")
+        .bind(id)
+        .fetch_one(conn)
+        .await
+}
+
+pub async fn update_item(pool: &SqlitePool, audit: &AuditContext<'_>, id: String, dto: UpdateItemDto) -> Result<(), sqlx::Error> {
+    item_service::update_item(&SqliteItemRepository::new(pool, audit), audit.now, &id, dto)
+        .await
+        .map_err(from_item_error)?;
+
+    Ok(())
+}
+
+/// Moves an item to index `position` of the manual ordering, shifting the
+/// items in between by one place with a single range update, all in one
+/// transaction. The shifted items count as updated; only the moved one gets
+/// a move entry in the audit log.
+pub async fn move_item(pool: &SqlitePool, audit: &AuditContext<'_>, id: String, position: i64) -> Result<Item, sqlx::Error> {
+    item_service::move_item(&SqliteItemRepository::new(pool, audit), audit.now, &id, position)
+        .await
+        .map_err(from_item_error)
+}
+
+/// Deleting an item that does not exist is a no-op and records nothing.
+///
+/// The item's subtasks, and the comments, attachments and reminders of all of
+/// them, go with it through `ON DELETE CASCADE`; each gets its own delete
+/// entry in the audit log.
+pub async fn delete_item(pool: &SqlitePool, audit: &AuditContext<'_>, id: String) -> Result<(), sqlx::Error> {
+    item_service::delete_item(&SqliteItemRepository::new(pool, audit), &id).await
+}
+
+fn from_item_error(err: ItemError<sqlx::Error>) -> sqlx::Error {
+    match err {
+        ItemError::NotFound => sqlx::Error::RowNotFound,
+        ItemError::Repository(err) => err,
+    }
+}
+
+#[async_trait]
+impl ItemRepository for SqliteItemRepository<'_> {
+    type Error = sqlx::Error;
+
+    async fn get(&self, id: &str) -> Result<Option<Item>, sqlx::Error> {
+        match fetch_item(&mut *self.acquire().await?, id).await {
+            Ok(item) => Ok(Some(item)),
+            Err(sqlx::Error::RowNotFound) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn insert(&self, item: Item) -> Result<Item, sqlx::Error> {
+        let mut conn = self.acquire().await?;
+        let mut tx = conn.begin().await?;
+        let item = store_new_item(&mut tx, self.audit, item).await?;
+        tx.commit().await?;
+
+        Ok(item)
+    }
+
+    async fn insert_all(&self, items: Vec<Item>, progress: &mut (dyn FnMut(usize) + Send)) -> Result<Vec<Item>, sqlx::Error> {
+        let mut conn = self.acquire().await?;
+        let mut tx = conn.begin().await?;
+        let mut stored = Vec::with_capacity(items.len());
+        for item in items {
+            stored.push(store_new_item(&mut tx, self.audit, item).await?);
+            progress(stored.len());
+        }
+        tx.commit().await?;
+
+        Ok(stored)
+    }
+
+    async fn update(&self, id: &str, change: Change<'_>) -> Result<Option<Item>, sqlx::Error> {
+        let mut conn = self.acquire().await?;
+        let mut tx = conn.begin().await?;
+        let existing_item = match fetch_item(&mut tx, id).await {
+            Ok(item) => item,
+            Err(sqlx::Error::RowNotFound) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        let updated_item = change(&existing_item);
+
+        // The `items_no_parent_cycle` trigger rejects a parent that is the item
+        // itself or one of its subtasks.
+        db_trace::query("UPDATE items SET name = ?, description = COALESCE(?, ''), priority = ?, parent_id = ?, updated_at = ? WHERE id = ?")
+            .bind(&updated_item.name)
+            .bind(&updated_item.description)
+            .bind(updated_item.priority)
+            .bind(&updated_item.parent_id)
+            .bind(updated_item.updated_at)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        audit_service::record(&mut tx, self.audit, audit_service::ITEM, id, AuditAction::Update, Some(&existing_item), Some(&updated_item)).await?;
+        tx.commit().await?;
+
+        Ok(Some(updated_item))
+    }
+
+    async fn move_to(&self, id: &str, to: i64, place: Placement<'_>) -> Result<Option<Item>, sqlx::Error> {
+        let mut conn = self.acquire().await?;
+        let mut tx = conn.begin().await?;
+        let existing_item = match fetch_item(&mut tx, id).await {
+            Ok(item) => item,
+            Err(sqlx::Error::RowNotFound) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        // The position of the item at index `to`, or of the last one.
+        let (target,): (i64,) = db_trace::query_as(
+            "SELECT COALESCE((SELECT position FROM items ORDER BY position, id LIMIT 1 OFFSET ?), (SELECT MAX(position) FROM items))",
+        )
+            .bind(to)
+            .fetch_one(&mut *tx)
+            .await?;
+        let moved_item = place(&existing_item, target);
+
+        if let Some((range, by)) = item_service::shift(existing_item.position, target) {
+            db_trace::query("UPDATE items SET position = position + ?, updated_at = ? WHERE position BETWEEN ? AND ?")
+                .bind(by)
+                .bind(moved_item.updated_at)
+                .bind(range.start())
+                .bind(range.end())
+                .execute(&mut *tx)
+                .await?;
+        }
+        db_trace::query("UPDATE items SET position = ?, updated_at = ? WHERE id = ?")
+            .bind(moved_item.position)
+            .bind(moved_item.updated_at)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        // A move that leaves the item in place is still recorded.
+        audit_service::record(&mut tx, self.audit, audit_service::ITEM, id, AuditAction::Move, Some(&existing_item), Some(&moved_item)).await?;
+        tx.commit().await?;
+
+        Ok(Some(moved_item))
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, sqlx::Error> {
+        let mut conn = self.acquire().await?;
+        let mut tx = conn.begin().await?;
+        let existing_item = match fetch_item(&mut tx, id).await {
+            Ok(item) => item,
+            Err(sqlx::Error::RowNotFound) => return Ok(false),
+            Err(err) => return Err(err),
+        };
+        let descendants = fetch_descendants(&mut tx, &[id.to_string()]).await?;
+        let mut item_ids = vec![id.to_string()];
+        item_ids.extend(descendants.iter().map(|item| item.id.clone()));
+
+        let comments = comment_service::fetch_for_items(&mut tx, &item_ids).await?;
+        let attachments = attachment_service::fetch_for_items(&mut tx, &item_ids).await?;
+        let reminders = reminder_service::fetch_for_items(&mut tx, &item_ids).await?;
+
+        db_trace::query("DELETE FROM items WHERE id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        for comment in &comments {
+            comment_service::record(&mut tx, self.audit, AuditAction::Delete, Some(comment), None).await?;
+        }
+        for attachment in &attachments {
+            attachment_service::record(&mut tx, self.audit, AuditAction::Delete, Some(attachment), None).await?;
+        }
+        for reminder in &reminders {
+            reminder_service::record(&mut tx, self.audit, AuditAction::Delete, Some(reminder), None).await?;
+        }
+        for descendant in &descendants {
+            audit_service::record(&mut tx, self.audit, audit_service::ITEM, &descendant.id, AuditAction::Delete, Some(descendant), None).await?;
+        }
+        audit_service::record(&mut tx, self.audit, audit_service::ITEM, id, AuditAction::Delete, Some(&existing_item), None).await?;
+        tx.commit().await?;
+
+        Ok(true)
+    }
+}
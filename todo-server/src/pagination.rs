@@ -0,0 +1,128 @@
+//! Keyset pagination shared by every list endpoint.
+//!
+//! A page is fetched with one row more than asked for; the extra row only
+//! tells whether another page follows. The position after the last row is
+//! handed to the client as an opaque cursor and passed back to resume the
+//! scan from there, so deep pages cost the same as the first one.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::error::AppError;
+use crate::extract::{check_range, Validate};
+
+pub const DEFAULT_PAGE_SIZE: i64 = 50;
+pub const MAX_PAGE_SIZE: i64 = 200;
+
+/// One page of a collection.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Paginated<T> {
+    pub items: Vec<T>,
+    /// Pass back as `cursor` to fetch the next page; `None` on the last page.
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Paginated<T> {
+    /// The page for `rows`, which must have been fetched with a limit of
+    /// `limit + 1`.
+    pub fn from_rows<C: Cursor>(mut rows: Vec<T>, limit: i64, cursor_after: impl Fn(&T) -> C) -> Self {
+        let next_cursor = if rows.len() as i64 > limit {
+            rows.truncate(limit as usize);
+            rows.last().map(|row| cursor_after(row).encode())
+        } else {
+            None
+        };
+
+        Self { items: rows, next_cursor }
+    }
+
+    /// The same page with its items transformed, keeping the cursor.
+    pub fn map_items<U>(self, f: impl FnOnce(Vec<T>) -> Vec<U>) -> Paginated<U> {
+        Paginated { items: f(self.items), next_cursor: self.next_cursor }
+    }
+}
+
+/// `limit` and `cursor` query parameters of a list endpoint.
+#[derive(Serialize, Deserialize)]
+pub struct PageQuery {
+    #[serde(default = "default_page_size")]
+    pub limit: i64,
+    pub cursor: Option<String>,
+}
+
+pub fn default_page_size() -> i64 {
+    DEFAULT_PAGE_SIZE
+}
+
+impl Validate for PageQuery {
+    fn validate(&self) -> Result<(), String> {
+        check_range("limit", self.limit, 1..=MAX_PAGE_SIZE)
+    }
+}
+
+/// A position in some ordering.
+///
+/// Clients only see it as base64url over the fields joined with `|`. Only
+/// the last field may contain a `|`.
+pub trait Cursor: Sized {
+    const FIELDS: usize;
+
+    fn to_fields(&self) -> Vec<String>;
+
+    /// `None` when a field does not parse. Gets exactly [`Cursor::FIELDS`]
+    /// fields.
+    fn from_fields(fields: &[&str]) -> Option<Self>;
+
+    fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(self.to_fields().join("|"))
+    }
+
+    fn decode(cursor: &str) -> Option<Self> {
+        let bytes = URL_SAFE_NO_PAD.decode(cursor).ok()?;
+        let decoded = String::from_utf8(bytes).ok()?;
+        let fields: Vec<&str> = decoded.splitn(Self::FIELDS, '|').collect();
+        if fields.len() != Self::FIELDS {
+            return None;
+        }
+
+        Self::from_fields(&fields)
+    }
+}
+
+/// Decodes the `cursor` query parameter, rejecting malformed ones.
+pub fn parse_cursor<C: Cursor>(cursor: Option<&str>) -> Result<Option<C>, AppError> {
+    cursor.map(|cursor| C::decode(cursor).ok_or_else(invalid_cursor)).transpose()
+}
+
+pub fn invalid_cursor() -> AppError {
+    AppError::BadRequest("Invalid cursor".to_string())
+}
+
+/// Position in a `(created_at, id)` ordering, the order of comments and
+/// audit entries.
+pub struct CreatedAtCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: String,
+}
+
+impl CreatedAtCursor {
+    pub fn new(created_at: DateTime<Utc>, id: &str) -> Self {
+        Self { created_at, id: id.to_string() }
+    }
+}
+
+impl Cursor for CreatedAtCursor {
+    const FIELDS: usize = 2;
+
+    fn to_fields(&self) -> Vec<String> {
+        vec![self.created_at.to_rfc3339(), self.id.clone()]
+    }
+
+    fn from_fields(fields: &[&str]) -> Option<Self> {
+        Some(Self {
+            created_at: DateTime::parse_from_rfc3339(fields[0]).ok()?.with_timezone(&Utc),
+            id: fields[1].to_string(),
+        })
+    }
+}
@@ -0,0 +1,133 @@
+use axum::extract::Request;
+use axum::http::{HeaderName, HeaderValue};
+use axum::middleware::{self, Next};
+use axum::response::{Json, Response};
+use axum::routing::get;
+use axum::Router;
+use serde::Serialize;
+use crate::modules::actions::create_action_routes;
+use crate::modules::admin::create_admin_routes;
+use crate::modules::attachments::create_attachment_routes;
+use crate::modules::audit::create_audit_routes;
+use crate::modules::comments::create_comment_routes;
+use crate::modules::exports::create_export_routes;
+use crate::modules::imports::create_import_routes;
+use crate::modules::reminders::create_reminder_routes;
+use crate::modules::todos::create_item_routes;
+use crate::state::AppState;
+
+pub const API_VERSION_HEADER: HeaderName = HeaderName::from_static("api-version");
+pub const DEPRECATION_HEADER: HeaderName = HeaderName::from_static("deprecation");
+
+/// A version of the HTTP API, served under `/api/<name>`.
+pub struct ApiVersion {
+    pub name: &'static str,
+    pub status: VersionStatus,
+    pub routes: fn() -> Router<AppState>,
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionStatus {
+    Current,
+    Deprecated,
+}
+
+/// Every served API version, oldest first. Adding `/api/v2` means adding an
+/// entry here with its own route tree.
+pub const API_VERSIONS: &[ApiVersion] = &[
+    ApiVersion { name: "v1", status: VersionStatus::Current, routes: v1_routes },
+];
+
+/// The version that unversioned (legacy) paths resolve to.
+pub const LEGACY_VERSION: &str = "v1";
+
+pub fn v1_routes() -> Router<AppState> {
+    Router::new()
+        .nest(
+            "/items",
+            create_item_routes()
+                .merge(create_reminder_routes())
+                .merge(create_attachment_routes())
+                .merge(create_comment_routes())
+                .merge(create_audit_routes())
+                .merge(create_export_routes())
+                .merge(create_import_routes()),
+        )
+        .nest("/actions", create_action_routes())
+        .nest("/admin", create_admin_routes())
+}
+
+/// `/api/versions` plus one nested router per entry in [`API_VERSIONS`].
+pub fn create_api_routes() -> Router<AppState> {
+    API_VERSIONS.iter().fold(
+        Router::new().route("/versions", get(list_versions)),
+        |router, version| {
+            let name = version.name;
+            router.nest(
+                &format!("/{name}"),
+                (version.routes)().layer(middleware::map_response(move |response| tag_version(response, name))),
+            )
+        },
+    )
+}
+
+/// The routes served before versioning, the items and their reminders, kept
+/// as an alias of [`LEGACY_VERSION`] and flagged with a `Deprecation` header.
+/// Everything added since, such as `/admin` and `/actions`, is only served
+/// under `/api`.
+pub fn create_legacy_routes() -> Router<AppState> {
+    Router::new()
+        .nest("/items", create_item_routes().merge(create_reminder_routes()))
+        .layer(middleware::from_fn(deprecated))
+}
+
+#[derive(Serialize)]
+pub struct VersionInfo {
+    pub version: &'static str,
+    pub status: VersionStatus,
+    pub path: String,
+}
+
+#[derive(Serialize)]
+pub struct VersionsResponse {
+    pub current: &'static str,
+    pub versions: Vec<VersionInfo>,
+}
+
+pub async fn list_versions() -> Json<VersionsResponse> {
+    let current = API_VERSIONS
+        .iter()
+        .rev()
+        .find(|version| version.status == VersionStatus::Current)
+        .map_or(LEGACY_VERSION, |version| version.name);
+
+    let versions = API_VERSIONS
+        .iter()
+        .map(|version| VersionInfo {
+            version: version.name,
+            status: version.status,
+            path: format!("/api/{}", version.name),
+        })
+        .collect();
+
+    Json(VersionsResponse { current, versions })
+}
+
+async fn tag_version(mut response: Response, name: &'static str) -> Response {
+    response.headers_mut().insert(API_VERSION_HEADER, HeaderValue::from_static(name));
+    response
+}
+
+async fn deprecated(request: Request, next: Next) -> Response {
+    let successor = format!("</api/{LEGACY_VERSION}{}>; rel=\"successor-version\"", request.uri().path());
+    let mut response = tag_version(next.run(request).await, LEGACY_VERSION).await;
+
+    let headers = response.headers_mut();
+    headers.insert(DEPRECATION_HEADER, HeaderValue::from_static("true"));
+    if let Ok(link) = HeaderValue::from_str(&successor) {
+        headers.insert(axum::http::header::LINK, link);
+    }
+
+    response
+}
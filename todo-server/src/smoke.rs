@@ -0,0 +1,188 @@
+//! `smoke`: walks the core item journey through the real HTTP API and stops
+//! at the first response that isn't what the journey expects, so operators
+//! can verify a build or a deployment end to end.
+//!
+//! Without a URL the app is booted on a random local port over a scratch
+//! in-memory database. Against a running deployment the journey creates one
+//! item of its own and deletes it again at the end, or as soon as a later
+//! step fails.
+
+use std::fmt;
+
+use axum::http::{header, StatusCode};
+use axum::Router;
+use reqwest::{Client, Response};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::extract::Actor;
+
+/// Actor recorded in the audit log for changes made by the smoke test.
+pub const SMOKE_ACTOR: &str = "smoke";
+
+/// The step that went wrong and what was seen instead.
+#[derive(Debug)]
+pub struct SmokeFailure {
+    pub step: &'static str,
+    pub reason: String,
+}
+
+impl fmt::Display for SmokeFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Smoke test failed at {}: {}", self.step, self.reason)
+    }
+}
+
+impl std::error::Error for SmokeFailure {}
+
+/// Serves `app` on a random port of 127.0.0.1 in the background and returns
+/// its base URL. Must be called inside a Tokio runtime.
+pub async fn boot(app: Router) -> std::io::Result<String> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let address = listener.local_addr()?;
+    tokio::spawn(async move { axum::serve(listener, app).await });
+
+    Ok(format!("http://{address}"))
+}
+
+/// Runs the journey against the app at `base_url`, calling `passed` with the
+/// name of each step that succeeds.
+pub async fn run(base_url: &str, passed: &mut dyn FnMut(&'static str)) -> Result<(), SmokeFailure> {
+    let client = Client::new();
+    let base_url = base_url.trim_end_matches('/');
+    let api = format!("{base_url}/api/v1");
+    let name = format!("Smoke test {}", Uuid::new_v4().simple());
+
+    let step = "status";
+    let response = send(step, client.get(format!("{base_url}/status"))).await?;
+    let report = expect_json(step, response, StatusCode::OK).await?;
+    if report["status"] == json!("down") {
+        return Err(failure(step, format!("the app reports itself down: {report}")));
+    }
+    passed(step);
+
+    let step = "create";
+    let response = send(step, client.post(format!("{api}/items")).json(&json!({ "name": name, "priority": "high" }))).await?;
+    let item = expect_json(step, response, StatusCode::OK).await?;
+    expect_field(step, &item, "name", &json!(name))?;
+    expect_field(step, &item, "priority", &json!("high"))?;
+    let id = item["id"]
+        .as_str()
+        .ok_or_else(|| failure(step, format!("expected an id, got {item}")))?
+        .to_string();
+    let item_url = format!("{api}/items/{id}");
+    passed(step);
+
+    let result = journey(&client, &api, &item_url, &id, &name, passed).await;
+    if result.is_err() {
+        // Best effort, so a failed run doesn't leave its item behind.
+        let _ = send("cleanup", client.delete(&item_url)).await;
+    }
+
+    result
+}
+
+/// The steps after the item is created.
+async fn journey(client: &Client, api: &str, item_url: &str, id: &str, name: &str, passed: &mut dyn FnMut(&'static str)) -> Result<(), SmokeFailure> {
+    let step = "get";
+    let response = send(step, client.get(item_url)).await?;
+    let etag = response.headers().get(header::ETAG).cloned();
+    let fetched = expect_json(step, response, StatusCode::OK).await?;
+    expect_field(step, &fetched, "id", &json!(id))?;
+    let etag = etag.ok_or_else(|| failure(step, "expected an ETag".to_string()))?;
+    let response = send(step, client.get(item_url).header(header::IF_NONE_MATCH, etag)).await?;
+    expect_status(step, &response, StatusCode::NOT_MODIFIED)?;
+    passed(step);
+
+    let step = "update";
+    let renamed = format!("{name} (renamed)");
+    let response = send(step, client.patch(item_url).json(&json!({ "name": renamed }))).await?;
+    expect_status(step, &response, StatusCode::NO_CONTENT)?;
+    let fetched = expect_json(step, send(step, client.get(item_url)).await?, StatusCode::OK).await?;
+    expect_field(step, &fetched, "name", &json!(renamed))?;
+    passed(step);
+
+    let step = "move";
+    let response = send(step, client.patch(format!("{item_url}/move")).json(&json!({ "position": 0 }))).await?;
+    let moved = expect_json(step, response, StatusCode::OK).await?;
+    expect_field(step, &moved, "position", &json!(0))?;
+    passed(step);
+
+    let step = "list";
+    let response = send(step, client.get(format!("{api}/items?sort=position&limit=1"))).await?;
+    let page = expect_json(step, response, StatusCode::OK).await?;
+    if page["items"][0]["id"] != json!(id) {
+        return Err(failure(step, format!("expected the moved item first, got {page}")));
+    }
+    passed(step);
+
+    let step = "export";
+    let response = send(step, client.get(format!("{api}/items/export?format=json"))).await?;
+    let export = expect_json(step, response, StatusCode::OK).await?;
+    let exported = export
+        .as_array()
+        .is_some_and(|items| items.iter().any(|item| item["id"] == json!(id) && item["name"] == json!(renamed)));
+    if !exported {
+        return Err(failure(step, format!("expected item {id} in the export")));
+    }
+    passed(step);
+
+    let step = "delete";
+    let response = send(step, client.delete(item_url)).await?;
+    expect_status(step, &response, StatusCode::NO_CONTENT)?;
+    let response = send(step, client.get(item_url)).await?;
+    expect_status(step, &response, StatusCode::NOT_FOUND)?;
+    passed(step);
+
+    let step = "history";
+    let response = send(step, client.get(format!("{item_url}/history"))).await?;
+    let history = expect_json(step, response, StatusCode::OK).await?;
+    let actions: Vec<&str> = history["items"]
+        .as_array()
+        .map(|entries| entries.iter().filter_map(|entry| entry["action"].as_str()).collect())
+        .unwrap_or_default();
+    if actions != ["create", "update", "move", "delete"] {
+        return Err(failure(step, format!("expected create, update, move and delete, got {actions:?}")));
+    }
+    expect_field(step, &history["items"][0], "actor", &json!(SMOKE_ACTOR))?;
+    passed(step);
+
+    Ok(())
+}
+
+fn failure(step: &'static str, reason: String) -> SmokeFailure {
+    SmokeFailure { step, reason }
+}
+
+async fn send(step: &'static str, request: reqwest::RequestBuilder) -> Result<Response, SmokeFailure> {
+    request
+        .header(Actor::HEADER, SMOKE_ACTOR)
+        .send()
+        .await
+        .map_err(|err| failure(step, format!("request failed: {err}")))
+}
+
+fn expect_status(step: &'static str, response: &Response, expected: StatusCode) -> Result<(), SmokeFailure> {
+    if response.status() != expected {
+        return Err(failure(step, format!("expected {expected} from {}, got {}", response.url(), response.status())));
+    }
+
+    Ok(())
+}
+
+async fn expect_json(step: &'static str, response: Response, expected: StatusCode) -> Result<Value, SmokeFailure> {
+    expect_status(step, &response, expected)?;
+
+    response
+        .json()
+        .await
+        .map_err(|err| failure(step, format!("expected a JSON body: {err}")))
+}
+
+fn expect_field(step: &'static str, value: &Value, field: &str, expected: &Value) -> Result<(), SmokeFailure> {
+    if &value[field] != expected {
+        return Err(failure(step, format!("expected {field} to be {expected}, got {}", value[field])));
+    }
+
+    Ok(())
+}
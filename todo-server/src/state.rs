@@ -0,0 +1,132 @@
+use std::sync::Arc;
+
+use axum::extract::FromRef;
+use sqlx::SqlitePool;
+use crate::clock::{Clock, SystemClock};
+use crate::config::AppConfig;
+use crate::id_generator::{IdGenerator, UuidV4Generator};
+use crate::modules::actions::action_registry::ActionRegistry;
+use crate::modules::attachments::attachment_storage::{AttachmentStorage, LocalDiskStorage};
+use crate::modules::exports::exporter::ExporterRegistry;
+use crate::modules::imports::importer::ImporterRegistry;
+use crate::modules::status::status_metrics::RequestMetrics;
+use crate::modules::todos::todo_batcher::ItemBatcher;
+use crate::modules::todos::todo_cache::ItemCache;
+use crate::modules::todos::todo_hooks::ItemHooks;
+
+/// Shared state handed to every handler.
+///
+/// Handlers that only touch the database can keep extracting
+/// `State<SqlitePool>`; the pool is pulled out through [`FromRef`].
+#[derive(Clone)]
+pub struct AppState {
+    pub pool: SqlitePool,
+    /// Pool that writes go through. The same as `pool` unless a dedicated
+    /// writer pool is set with [`AppState::with_writer`].
+    pub writer: SqlitePool,
+    pub clock: Arc<dyn Clock>,
+    pub ids: Arc<dyn IdGenerator>,
+    pub config: AppConfig,
+    pub storage: Arc<dyn AttachmentStorage>,
+    /// When set, item inserts are coalesced into shared transactions.
+    pub item_batcher: Option<ItemBatcher>,
+    pub item_cache: Arc<ItemCache>,
+    /// Operator scripts run before items are created, updated or deleted.
+    pub item_hooks: Arc<ItemHooks>,
+    /// Formats offered by `GET /items/export`.
+    pub exporters: Arc<ExporterRegistry>,
+    /// Formats accepted by `POST /items/import`.
+    pub importers: Arc<ImporterRegistry>,
+    /// Actions offered by `/actions`.
+    pub actions: Arc<ActionRegistry>,
+    /// Counts behind `GET /status`.
+    pub request_metrics: Arc<RequestMetrics>,
+}
+
+impl AppState {
+    /// State backed by the system clock, random v4 UUIDs, the default
+    /// configuration and attachments on local disk.
+    pub fn new(pool: SqlitePool) -> Self {
+        let config = AppConfig::default();
+        let clock: Arc<dyn Clock> = Arc::new(SystemClock);
+
+        Self {
+            writer: pool.clone(),
+            pool,
+            request_metrics: Arc::new(RequestMetrics::new(clock.now())),
+            clock,
+            ids: Arc::new(UuidV4Generator),
+            storage: Arc::new(LocalDiskStorage::new(config.attachments_dir.clone())),
+            item_cache: Arc::new(ItemCache::from_config(&config)),
+            config,
+            item_batcher: None,
+            item_hooks: Arc::new(ItemHooks::default()),
+            exporters: Arc::new(ExporterRegistry::default()),
+            actions: Arc::new(ActionRegistry::default()),
+            importers: Arc::new(ImporterRegistry::default()),
+        }
+    }
+
+    /// Replaces the configuration and starts a fresh item cache sized by it.
+    /// The attachment storage is left alone; set it with
+    /// [`AppState::with_storage`].
+    pub fn with_config(mut self, config: AppConfig) -> Self {
+        self.item_cache = Arc::new(ItemCache::from_config(&config));
+        self.config = config;
+        self
+    }
+
+    pub fn with_writer(mut self, writer: SqlitePool) -> Self {
+        self.writer = writer;
+        self
+    }
+
+    pub fn with_storage(mut self, storage: Arc<dyn AttachmentStorage>) -> Self {
+        self.storage = storage;
+        self
+    }
+
+    /// Replaces the clock and restarts the request metrics, so uptime is
+    /// measured on the new clock.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.request_metrics = Arc::new(RequestMetrics::new(clock.now()));
+        self.clock = clock;
+        self
+    }
+
+    pub fn with_ids(mut self, ids: Arc<dyn IdGenerator>) -> Self {
+        self.ids = ids;
+        self
+    }
+
+    pub fn with_exporters(mut self, exporters: ExporterRegistry) -> Self {
+        self.exporters = Arc::new(exporters);
+        self
+    }
+
+    pub fn with_importers(mut self, importers: ImporterRegistry) -> Self {
+        self.importers = Arc::new(importers);
+        self
+    }
+
+    pub fn with_item_hooks(mut self, item_hooks: ItemHooks) -> Self {
+        self.item_hooks = Arc::new(item_hooks);
+        self
+    }
+
+    pub fn with_actions(mut self, actions: ActionRegistry) -> Self {
+        self.actions = Arc::new(actions);
+        self
+    }
+
+    pub fn with_item_batcher(mut self, item_batcher: ItemBatcher) -> Self {
+        self.item_batcher = Some(item_batcher);
+        self
+    }
+}
+
+impl FromRef<AppState> for SqlitePool {
+    fn from_ref(state: &AppState) -> Self {
+        state.pool.clone()
+    }
+}
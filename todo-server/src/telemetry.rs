@@ -0,0 +1,34 @@
+use axum::extract::Request;
+use tower_http::request_id::RequestId;
+use tracing::Span;
+use tracing_subscriber::EnvFilter;
+use crate::config::AppConfig;
+
+/// Installs the global log subscriber.
+///
+/// `RUST_LOG` picks the filter (default `info`). With `config.sql_debug` set,
+/// every SQL statement is logged as well; see [`crate::db_trace`].
+pub fn init(config: &AppConfig) {
+    let mut filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    if config.sql_debug {
+        filter = filter.add_directive("sql=debug".parse().expect("valid directive"));
+    }
+
+    tracing_subscriber::fmt().with_env_filter(filter).init();
+}
+
+/// The span every request is handled in, tagged with its `x-request-id`.
+pub fn request_span(request: &Request) -> Span {
+    let request_id = request
+        .extensions()
+        .get::<RequestId>()
+        .and_then(|id| id.header_value().to_str().ok())
+        .unwrap_or_default();
+
+    tracing::info_span!(
+        "request",
+        method = %request.method(),
+        uri = %request.uri(),
+        request_id,
+    )
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::http::StatusCode;
+use todo_server::create_app_with_state;
+use todo_server::error::AppError;
+use todo_server::extract::Actor;
+use todo_server::modules::actions::action_registry::{Action, ActionRegistry};
+use todo_server::state::AppState;
+use serde_json::{json, Value};
+
+use common::{request, setup, setup_state};
+
+#[tokio::test]
+async fn actions_are_listed_with_their_param_schemas() {
+    let app = setup().await;
+
+    let (status, actions) = request(&app, "GET", "/api/v1/actions", None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let names: Vec<_> = actions.as_array().unwrap().iter().map(|action| action["name"].as_str().unwrap()).collect();
+    assert_eq!(names, ["rename", "set_priority", "move", "delete"]);
+    assert_eq!(actions[1]["params"]["properties"]["priority"]["enum"], json!(["low", "medium", "high"]));
+    assert_eq!(actions[2]["params"]["required"], json!(["id", "position"]));
+}
+
+#[tokio::test]
+async fn actions_run_through_the_item_operations() {
+    let app = setup().await;
+    let (_, first) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "First" }))).await;
+    let (_, second) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Second" }))).await;
+    let id = first["id"].as_str().unwrap();
+
+    let (status, body) = request(
+        &app,
+        "POST",
+        "/api/v1/actions",
+        Some(json!({ "action": "set_priority", "params": { "id": id, "priority": "high" } })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["action"], "set_priority");
+    assert_eq!(body["result"]["priority"], "high");
+
+    let (_, body) = request(&app, "POST", "/api/v1/actions", Some(json!({ "action": "rename", "params": { "id": id, "name": "Renamed" } }))).await;
+    assert_eq!(body["result"]["name"], "Renamed");
+    assert_eq!(body["result"]["priority"], "high");
+
+    let (_, body) = request(&app, "POST", "/api/v1/actions", Some(json!({ "action": "move", "params": { "id": id, "position": 1 } }))).await;
+    assert_eq!(body["result"]["position"], 1);
+
+    let (status, body) = request(&app, "POST", "/api/v1/actions", Some(json!({ "action": "delete", "params": { "id": second["id"] } }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["result"], Value::Null);
+
+    let (_, page) = request(&app, "GET", "/api/v1/items", None).await;
+    assert_eq!(page["items"].as_array().unwrap().len(), 1);
+
+    let (_, history) = request(&app, "GET", &format!("/api/v1/items/{id}/history"), None).await;
+    assert_eq!(history["items"].as_array().unwrap().len(), 4);
+}
+
+#[tokio::test]
+async fn bad_actions_are_rejected() {
+    let app = setup().await;
+
+    let (status, body) = request(&app, "POST", "/api/v1/actions", Some(json!({ "action": "complete", "params": {} }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["message"], "Unknown action \"complete\"");
+
+    let (status, body) = request(&app, "POST", "/api/v1/actions", Some(json!({ "action": "move", "params": { "id": "x" } }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["message"], "Invalid params: missing field `position`");
+
+    let (status, _) = request(&app, "POST", "/api/v1/actions", Some(json!({ "action": "rename", "params": { "id": "missing", "name": "Nobody" } }))).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+struct EchoAction;
+
+#[async_trait]
+impl Action for EchoAction {
+    fn name(&self) -> &'static str {
+        "echo"
+    }
+
+    fn description(&self) -> &'static str {
+        "Say who asked"
+    }
+
+    fn params_schema(&self) -> Value {
+        json!({ "type": "object" })
+    }
+
+    async fn run(&self, _state: &AppState, actor: Actor, _params: Value) -> Result<Value, AppError> {
+        Ok(json!({ "actor": actor.name }))
+    }
+}
+
+#[tokio::test]
+async fn embedding_apps_can_register_actions() {
+    let (state, _) = setup_state().await;
+    let mut actions = ActionRegistry::default();
+    actions.register(Arc::new(EchoAction));
+    let app = create_app_with_state(state.with_actions(actions));
+
+    let (_, listed) = request(&app, "GET", "/api/v1/actions", None).await;
+    assert_eq!(listed[4]["name"], "echo");
+
+    let (_, body) = request(&app, "POST", "/api/v1/actions", Some(json!({ "action": "echo" }))).await;
+    assert_eq!(body["result"]["actor"], "anonymous");
+}
@@ -0,0 +1,29 @@
+mod common;
+
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{request, setup};
+
+#[tokio::test]
+async fn storage_report_covers_tables_and_attachments() {
+    let app = setup().await;
+    for name in ["a", "b", "c"] {
+        request(&app, "POST", "/api/v1/items", Some(json!({ "name": name }))).await;
+    }
+
+    let (status, report) = request(&app, "GET", "/api/v1/admin/storage", None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    assert!(report["database_bytes"].as_i64().unwrap() > 0);
+    // In-memory databases have no write-ahead log.
+    assert!(report["wal_bytes"].is_null());
+    assert_eq!(report["attachments"], json!({ "count": 0, "bytes": 0 }));
+
+    let tables = report["tables"].as_array().unwrap();
+    let items = tables.iter().find(|table| table["name"] == "items").unwrap();
+    assert_eq!(items["rows"], 3);
+    assert!(items["table_bytes"].as_i64().unwrap() > 0);
+    assert!(items["index_bytes"].as_i64().unwrap() > 0);
+    assert!(tables.iter().all(|table| !table["name"].as_str().unwrap().starts_with("sqlite_")));
+}
@@ -0,0 +1,202 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::body::{Body, Bytes};
+use axum::http::{header, Request, StatusCode};
+use axum::Router;
+use todo_server::config::AppConfig;
+use todo_server::create_app_with_state;
+use todo_server::modules::attachments::attachment_storage::{AttachmentStorage, InMemoryStorage, LocalDiskStorage};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use common::{request, send, setup_state};
+
+const BOUNDARY: &str = "X-TEST-BOUNDARY";
+
+fn multipart(field: &str, file_name: &str, content_type: &str, contents: &[u8]) -> Body {
+    let mut body = format!(
+        "--{BOUNDARY}\r\n\
+         Content-Disposition: form-data; name=\"{field}\"; filename=\"{file_name}\"\r\n\
+         Content-Type: {content_type}\r\n\r\n"
+    )
+    .into_bytes();
+    body.extend_from_slice(contents);
+    body.extend_from_slice(format!("\r\n--{BOUNDARY}--\r\n").as_bytes());
+
+    Body::from(body)
+}
+
+fn upload(item_id: &str, body: Body) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri(format!("/api/v1/items/{item_id}/attachments"))
+        .header(header::CONTENT_TYPE, format!("multipart/form-data; boundary={BOUNDARY}"))
+        .body(body)
+        .unwrap()
+}
+
+async fn setup_with(config: AppConfig) -> (Router, Arc<InMemoryStorage>, String) {
+    let storage = Arc::new(InMemoryStorage::default());
+    let (state, _) = setup_state().await;
+    let app = create_app_with_state(state.with_config(config).with_storage(storage.clone()));
+
+    let (_, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Taxes", "description": "" }))).await;
+    let item_id = item["id"].as_str().unwrap().to_string();
+
+    (app, storage, item_id)
+}
+
+#[tokio::test]
+async fn upload_download_and_delete() {
+    let (app, storage, item_id) = setup_with(AppConfig::default()).await;
+
+    let (status, attachment) = send(&app, upload(&item_id, multipart("file", "notes.txt", "text/plain; charset=utf-8", b"hello"))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(attachment["item_id"], item_id.as_str());
+    assert_eq!(attachment["file_name"], "notes.txt");
+    assert_eq!(attachment["content_type"], "text/plain");
+    assert_eq!(attachment["size_bytes"], 5);
+    let attachment_id = attachment["id"].as_str().unwrap();
+    assert!(storage.contains(attachment_id));
+
+    let (status, attachments) = request(&app, "GET", &format!("/api/v1/items/{item_id}/attachments"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(attachments, json!([attachment]));
+
+    let download = Request::builder()
+        .uri(format!("/api/v1/items/{item_id}/attachments/{attachment_id}"))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(download).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "text/plain");
+    assert_eq!(response.headers()[header::CONTENT_DISPOSITION], "attachment; filename=\"notes.txt\"");
+    assert_eq!(response.into_body().collect().await.unwrap().to_bytes(), Bytes::from_static(b"hello"));
+
+    let (status, _) = request(&app, "DELETE", &format!("/api/v1/items/{item_id}/attachments/{attachment_id}"), None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(!storage.contains(attachment_id));
+
+    let (status, _) = request(&app, "GET", &format!("/api/v1/items/{item_id}/attachments/{attachment_id}"), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn deleting_through_another_item_leaves_the_attachment_alone() {
+    let (app, storage, item_id) = setup_with(AppConfig::default()).await;
+    let (_, attachment) = send(&app, upload(&item_id, multipart("file", "notes.txt", "text/plain", b"hello"))).await;
+    let attachment_id = attachment["id"].as_str().unwrap();
+    let (_, other) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Other" }))).await;
+    let other_id = other["id"].as_str().unwrap();
+
+    let (status, _) = request(&app, "DELETE", &format!("/api/v1/items/{other_id}/attachments/{attachment_id}"), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(storage.contains(attachment_id));
+
+    let (status, _) = request(&app, "DELETE", &format!("/api/v1/items/{item_id}/attachments/missing"), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (_, attachments) = request(&app, "GET", &format!("/api/v1/items/{item_id}/attachments"), None).await;
+    assert_eq!(attachments, json!([attachment]));
+}
+
+#[tokio::test]
+async fn disallowed_mime_type_is_rejected() {
+    let (app, _, item_id) = setup_with(AppConfig::default()).await;
+
+    let (status, error) = send(&app, upload(&item_id, multipart("file", "run.sh", "application/x-sh", b"rm -rf /"))).await;
+
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_eq!(error["code"], "unsupported_media_type");
+}
+
+#[tokio::test]
+async fn oversized_attachment_is_rejected() {
+    let config = AppConfig { max_attachment_bytes: 4, ..AppConfig::default() };
+    let (app, storage, item_id) = setup_with(config).await;
+
+    let (status, error) = send(&app, upload(&item_id, multipart("file", "notes.txt", "text/plain", b"hello"))).await;
+
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(error["code"], "payload_too_large");
+    let (_, attachments) = request(&app, "GET", &format!("/api/v1/items/{item_id}/attachments"), None).await;
+    assert_eq!(attachments, json!([]));
+    assert!(!storage.contains("00000000-0000-0000-0000-000000000002"));
+}
+
+#[tokio::test]
+async fn attachments_may_exceed_the_json_body_limit() {
+    let config = AppConfig { max_body_bytes: 64, ..AppConfig::default() };
+    let (app, _, item_id) = setup_with(config).await;
+
+    let (status, attachment) = send(&app, upload(&item_id, multipart("file", "big.txt", "text/plain", &[b'x'; 1024]))).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(attachment["size_bytes"], 1024);
+}
+
+#[tokio::test]
+async fn upload_without_file_field_is_rejected() {
+    let (app, _, item_id) = setup_with(AppConfig::default()).await;
+
+    let (status, _) = send(&app, upload(&item_id, multipart("other", "notes.txt", "text/plain", b"hello"))).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn upload_that_is_not_multipart_is_rejected() {
+    let (app, _, item_id) = setup_with(AppConfig::default()).await;
+
+    let (status, _) = request(&app, "POST", &format!("/api/v1/items/{item_id}/attachments"), Some(json!({}))).await;
+
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
+
+#[tokio::test]
+async fn upload_to_unknown_item_is_not_found() {
+    let (app, _, _) = setup_with(AppConfig::default()).await;
+
+    let (status, _) = send(&app, upload("does-not-exist", multipart("file", "notes.txt", "text/plain", b"hello"))).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn deleting_item_removes_attachment_files() {
+    let (app, storage, item_id) = setup_with(AppConfig::default()).await;
+
+    let (_, attachment): (_, Value) = send(&app, upload(&item_id, multipart("file", "notes.txt", "text/plain", b"hello"))).await;
+    let attachment_id = attachment["id"].as_str().unwrap();
+
+    let (status, _) = request(&app, "DELETE", &format!("/api/v1/items/{item_id}"), None).await;
+
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(!storage.contains(attachment_id));
+}
+
+#[tokio::test]
+async fn local_disk_storage_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let storage = LocalDiskStorage::new(dir.path().join("files"));
+
+    storage.put("abc-123", Bytes::from_static(b"hello")).await.unwrap();
+    assert_eq!(storage.get("abc-123").await.unwrap(), Bytes::from_static(b"hello"));
+    assert!(dir.path().join("files/abc-123").exists());
+
+    storage.delete("abc-123").await.unwrap();
+    assert!(storage.get("abc-123").await.is_err());
+    storage.delete("abc-123").await.unwrap();
+}
+
+#[tokio::test]
+async fn local_disk_storage_rejects_path_traversal() {
+    let dir = tempfile::tempdir().unwrap();
+    let storage = LocalDiskStorage::new(dir.path());
+
+    assert!(storage.put("../escape", Bytes::from_static(b"x")).await.is_err());
+    assert!(storage.get("../../etc/passwd").await.is_err());
+}
@@ -0,0 +1,235 @@
+mod common;
+
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use axum::Router;
+use todo_server::create_app_with_state;
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+
+use common::{request, send, setup, setup_state};
+
+async fn create_as(app: &Router, actor: &str, body: Value) -> Value {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/v1/items")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header("x-actor", actor)
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let (status, item) = send(app, request).await;
+    assert_eq!(status, StatusCode::OK);
+
+    item
+}
+
+#[tokio::test]
+async fn history_records_each_mutation_in_order() {
+    let app = setup().await;
+    let item = create_as(&app, "alice", json!({ "name": "Buy milk", "description": "" })).await;
+    let id = item["id"].as_str().unwrap();
+
+    request(&app, "PUT", &format!("/api/v1/items/{id}"), Some(json!({ "name": "Buy oat milk" }))).await;
+    request(&app, "PATCH", &format!("/api/v1/items/{id}/move"), Some(json!({ "position": 0 }))).await;
+
+    let (status, history) = request(&app, "GET", &format!("/api/v1/items/{id}/history"), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let actions: Vec<&str> = history["items"].as_array().unwrap().iter().map(|entry| entry["action"].as_str().unwrap()).collect();
+    assert_eq!(actions, ["create", "update", "move"]);
+
+    assert_eq!(history["items"][0]["actor"], "alice");
+    assert_eq!(history["items"][0]["before"], Value::Null);
+    assert_eq!(history["items"][0]["after"]["name"], "Buy milk");
+
+    assert_eq!(history["items"][1]["actor"], "anonymous");
+    assert_eq!(history["items"][1]["before"]["name"], "Buy milk");
+    assert_eq!(history["items"][1]["after"]["name"], "Buy oat milk");
+}
+
+#[tokio::test]
+async fn history_survives_deletion() {
+    let app = setup().await;
+    let item = create_as(&app, "alice", json!({ "name": "Buy milk", "description": "" })).await;
+    let id = item["id"].as_str().unwrap();
+
+    request(&app, "DELETE", &format!("/api/v1/items/{id}"), None).await;
+
+    let (status, history) = request(&app, "GET", &format!("/api/v1/items/{id}/history"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(history["items"][1]["action"], "delete");
+    assert_eq!(history["items"][1]["before"]["name"], "Buy milk");
+    assert_eq!(history["items"][1]["after"], Value::Null);
+}
+
+#[tokio::test]
+async fn failed_mutations_are_not_recorded() {
+    let app = setup().await;
+    let item = create_as(&app, "alice", json!({ "name": "Buy milk", "description": "" })).await;
+    let id = item["id"].as_str().unwrap();
+
+    let (status, _) = request(&app, "PUT", "/api/v1/items/missing", Some(json!({ "name": "x" }))).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    request(&app, "DELETE", "/api/v1/items/missing", None).await;
+
+    let (_, history) = request(&app, "GET", &format!("/api/v1/items/{id}/history"), None).await;
+    assert_eq!(history["items"].as_array().unwrap().len(), 1);
+    let (status, _) = request(&app, "GET", "/api/v1/items/missing/history", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn entries_carry_the_request_id_of_the_change() {
+    let app = setup().await;
+    let create = Request::builder()
+        .method("POST")
+        .uri("/api/v1/items")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header("x-request-id", "req-create")
+        .body(Body::from(json!({ "name": "Buy milk" }).to_string()))
+        .unwrap();
+    let (_, item) = send(&app, create).await;
+    let id = item["id"].as_str().unwrap();
+    request(&app, "PATCH", &format!("/api/v1/items/{id}"), Some(json!({ "name": "Buy oat milk" }))).await;
+
+    let (_, history) = request(&app, "GET", &format!("/api/v1/items/{id}/history"), None).await;
+    assert_eq!(history["items"][0]["request_id"], "req-create");
+
+    // Requests without one get a generated id, which is recorded too.
+    let generated = history["items"][1]["request_id"].as_str().unwrap();
+    assert!(!generated.is_empty());
+    assert_ne!(generated, "req-create");
+}
+
+#[tokio::test]
+async fn blank_actor_header_is_rejected() {
+    let app = setup().await;
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/v1/items")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header("x-actor", "  ")
+        .body(Body::from(json!({ "name": "a", "description": "" }).to_string()))
+        .unwrap();
+
+    let (status, _) = send(&app, request).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn history_is_paginated_like_other_collections() {
+    let app = setup().await;
+    let (_, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "a" }))).await;
+    let id = item["id"].as_str().unwrap();
+    for name in ["b", "c"] {
+        request(&app, "PATCH", &format!("/api/v1/items/{id}"), Some(json!({ "name": name }))).await;
+    }
+
+    let (status, page) = request(&app, "GET", &format!("/api/v1/items/{id}/history?limit=2"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["items"].as_array().unwrap().len(), 2);
+    let cursor = page["next_cursor"].as_str().unwrap();
+
+    let (_, page) = request(&app, "GET", &format!("/api/v1/items/{id}/history?limit=2&cursor={cursor}"), None).await;
+    assert_eq!(page["items"].as_array().unwrap().len(), 1);
+    assert_eq!(page["items"][0]["after"]["name"], "c");
+    assert_eq!(page["next_cursor"], Value::Null);
+
+    let (status, _) = request(&app, "GET", &format!("/api/v1/items/{id}/history?cursor=garbage"), None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+async fn entries(pool: &SqlitePool, entity_type: &str) -> Vec<(String, String, String)> {
+    sqlx::query_as("SELECT entity_id, action, actor FROM audit_log WHERE entity_type = ? ORDER BY created_at, id")
+        .bind(entity_type)
+        .fetch_all(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn moving_records_only_the_moved_item() {
+    let app = setup().await;
+    let (_, first) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "first" }))).await;
+    let (_, second) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "second" }))).await;
+    let (first_id, second_id) = (first["id"].as_str().unwrap(), second["id"].as_str().unwrap());
+
+    request(&app, "PATCH", &format!("/api/v1/items/{second_id}/move"), Some(json!({ "position": 0 }))).await;
+
+    let (_, history) = request(&app, "GET", &format!("/api/v1/items/{second_id}/history"), None).await;
+    assert_eq!(history["items"][1]["action"], "move");
+    assert_eq!(history["items"][1]["before"]["position"], 1);
+    assert_eq!(history["items"][1]["after"]["position"], 0);
+
+    // The item it shifted keeps a history of its own changes only.
+    let (_, history) = request(&app, "GET", &format!("/api/v1/items/{first_id}/history"), None).await;
+    assert_eq!(history["items"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn deleting_records_everything_removed_with_the_item() {
+    let (state, _) = setup_state().await;
+    let pool = state.pool.clone();
+    let app = create_app_with_state(state);
+    let parent = create_as(&app, "alice", json!({ "name": "parent" })).await;
+    let parent_id = parent["id"].as_str().unwrap();
+    let child = create_as(&app, "alice", json!({ "name": "child", "parent_id": parent_id })).await;
+    let child_id = child["id"].as_str().unwrap();
+    request(&app, "POST", &format!("/api/v1/items/{child_id}/comments"), Some(json!({ "author": "alice", "body": "hi" }))).await;
+    request(
+        &app,
+        "POST",
+        &format!("/api/v1/items/{child_id}/reminders"),
+        Some(json!({ "remind_at": "2030-01-01T00:00:00Z", "webhook_url": "https://hooks.example.com/secret-token" })),
+    )
+    .await;
+
+    request(&app, "DELETE", &format!("/api/v1/items/{parent_id}"), None).await;
+
+    let (_, history) = request(&app, "GET", &format!("/api/v1/items/{child_id}/history"), None).await;
+    assert_eq!(history["items"][1]["action"], "delete");
+    assert_eq!(history["items"][1]["before"]["name"], "child");
+
+    let comments = entries(&pool, "comment").await;
+    assert_eq!(comments.iter().map(|(_, action, _)| action.as_str()).collect::<Vec<_>>(), ["create", "delete"]);
+    let reminders = entries(&pool, "reminder").await;
+    assert_eq!(reminders.iter().map(|(_, action, _)| action.as_str()).collect::<Vec<_>>(), ["create", "delete"]);
+
+    // Webhook URLs may carry credentials and are kept out of the log.
+    let (snapshots,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM audit_log WHERE before LIKE '%secret-token%' OR after LIKE '%secret-token%'")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(snapshots, 0);
+}
+
+#[tokio::test]
+async fn comment_changes_are_attributed() {
+    let (state, _) = setup_state().await;
+    let pool = state.pool.clone();
+    let app = create_app_with_state(state);
+    let item = create_as(&app, "alice", json!({ "name": "Buy milk" })).await;
+    let id = item["id"].as_str().unwrap();
+
+    let create = Request::builder()
+        .method("POST")
+        .uri(format!("/api/v1/items/{id}/comments"))
+        .header(header::CONTENT_TYPE, "application/json")
+        .header("x-actor", "bob")
+        .body(Body::from(json!({ "author": "bob", "body": "hi" }).to_string()))
+        .unwrap();
+    let (_, comment) = send(&app, create).await;
+    let comment_id = comment["id"].as_str().unwrap();
+    request(&app, "DELETE", &format!("/api/v1/items/{id}/comments/{comment_id}"), None).await;
+    request(&app, "DELETE", &format!("/api/v1/items/{id}/comments/{comment_id}"), None).await;
+
+    let comments = entries(&pool, "comment").await;
+    assert_eq!(
+        comments,
+        [
+            (comment_id.to_string(), "create".to_string(), "bob".to_string()),
+            (comment_id.to_string(), "delete".to_string(), "anonymous".to_string()),
+        ]
+    );
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use todo_server::config::AppConfig;
+use todo_server::create_app_with_state;
+use todo_server::modules::todos::todo_cache::CacheStats;
+use chrono::TimeDelta;
+use serde_json::json;
+
+use common::{request, setup_state};
+
+#[tokio::test]
+async fn repeated_reads_are_served_from_the_cache() {
+    let (state, _) = setup_state().await;
+    let cache = state.item_cache.clone();
+    let app = create_app_with_state(state);
+
+    let (_, created) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "a" }))).await;
+    let uri = format!("/api/v1/items/{}", created["id"].as_str().unwrap());
+
+    request(&app, "GET", &uri, None).await;
+    let (status, item) = request(&app, "GET", &uri, None).await;
+    request(&app, "GET", "/api/v1/items", None).await;
+    request(&app, "GET", "/api/v1/items", None).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(item, created);
+    assert_eq!(cache.stats(), CacheStats { hits: 2, misses: 2 });
+}
+
+#[tokio::test]
+async fn writes_invalidate_cached_reads() {
+    let (state, _) = setup_state().await;
+    let app = create_app_with_state(state);
+
+    let (_, created) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "a" }))).await;
+    let uri = format!("/api/v1/items/{}", created["id"].as_str().unwrap());
+    request(&app, "GET", &uri, None).await;
+    request(&app, "GET", "/api/v1/items", None).await;
+
+    request(&app, "PATCH", &uri, Some(json!({ "name": "b" }))).await;
+    let (_, item) = request(&app, "GET", &uri, None).await;
+    assert_eq!(item["name"], "b");
+
+    request(&app, "POST", "/api/v1/items", Some(json!({ "name": "c" }))).await;
+    let (_, page) = request(&app, "GET", "/api/v1/items", None).await;
+    assert_eq!(page["items"].as_array().unwrap().len(), 2);
+
+    request(&app, "DELETE", &uri, None).await;
+    let (status, _) = request(&app, "GET", &uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn entries_expire_after_the_ttl() {
+    let (state, clock) = setup_state().await;
+    let cache = state.item_cache.clone();
+    let app = create_app_with_state(state);
+
+    request(&app, "GET", "/api/v1/items", None).await;
+    clock.advance(TimeDelta::seconds(6));
+    request(&app, "GET", "/api/v1/items", None).await;
+
+    assert_eq!(cache.stats(), CacheStats { hits: 0, misses: 2 });
+}
+
+#[tokio::test]
+async fn zero_ttl_disables_the_cache() {
+    let (state, _) = setup_state().await;
+    let config = AppConfig {
+        item_cache_ttl: Duration::ZERO,
+        ..AppConfig::default()
+    };
+    let state = state.with_config(config);
+    let cache = state.item_cache.clone();
+    let app = create_app_with_state(state);
+
+    request(&app, "GET", "/api/v1/items", None).await;
+    request(&app, "GET", "/api/v1/items", None).await;
+
+    assert_eq!(cache.stats(), CacheStats { hits: 0, misses: 0 });
+}
+
+#[tokio::test]
+async fn cache_stats_are_reported_to_operators() {
+    let (state, _) = setup_state().await;
+    let app = create_app_with_state(state);
+
+    let (_, created) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "a" }))).await;
+    let uri = format!("/api/v1/items/{}", created["id"].as_str().unwrap());
+    request(&app, "GET", &uri, None).await;
+    request(&app, "GET", &uri, None).await;
+
+    let (status, stats) = request(&app, "GET", "/api/v1/admin/cache", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(stats, json!({ "hits": 1, "misses": 1 }));
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use axum::http::StatusCode;
+use axum::Router;
+use todo_server::create_app_with_state;
+use chrono::Duration;
+use serde_json::{json, Value};
+
+use common::{request, setup, setup_state};
+
+async fn create_item(app: &Router) -> String {
+    let (_, item) = request(app, "POST", "/api/v1/items", Some(json!({ "name": "Plan trip", "description": "" }))).await;
+    item["id"].as_str().unwrap().to_string()
+}
+
+async fn comment(app: &Router, item_id: &str, author: &str, body: &str) -> (StatusCode, Value) {
+    request(app, "POST", &format!("/api/v1/items/{item_id}/comments"), Some(json!({ "author": author, "body": body }))).await
+}
+
+fn bodies(page: &Value) -> Vec<&str> {
+    page["items"].as_array().unwrap().iter().map(|comment| comment["body"].as_str().unwrap()).collect()
+}
+
+#[tokio::test]
+async fn create_list_and_delete_comments() {
+    let app = setup().await;
+    let item_id = create_item(&app).await;
+
+    let (status, created) = comment(&app, &item_id, "ana", "Train or plane?").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(created["item_id"], item_id.as_str());
+    assert_eq!(created["author"], "ana");
+    assert_eq!(created["created_at"], "2024-06-24T08:00:00Z");
+
+    let (status, page) = request(&app, "GET", &format!("/api/v1/items/{item_id}/comments"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page, json!({ "items": [created], "next_cursor": null }));
+
+    let comment_id = created["id"].as_str().unwrap();
+    let (status, _) = request(&app, "DELETE", &format!("/api/v1/items/{item_id}/comments/{comment_id}"), None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (_, page) = request(&app, "GET", &format!("/api/v1/items/{item_id}/comments"), None).await;
+    assert_eq!(page["items"], json!([]));
+}
+
+#[tokio::test]
+async fn comments_are_paginated_oldest_first() {
+    let (state, clock) = setup_state().await;
+    let app = create_app_with_state(state);
+    let item_id = create_item(&app).await;
+
+    for body in ["one", "two", "three"] {
+        comment(&app, &item_id, "ana", body).await;
+        clock.advance(Duration::minutes(1));
+    }
+
+    let (_, page) = request(&app, "GET", &format!("/api/v1/items/{item_id}/comments?limit=2"), None).await;
+    assert_eq!(bodies(&page), ["one", "two"]);
+
+    let cursor = page["next_cursor"].as_str().unwrap();
+    let (_, page) = request(&app, "GET", &format!("/api/v1/items/{item_id}/comments?limit=2&cursor={cursor}"), None).await;
+    assert_eq!(bodies(&page), ["three"]);
+    assert!(page["next_cursor"].is_null());
+}
+
+#[tokio::test]
+async fn comments_are_scoped_to_their_item() {
+    let app = setup().await;
+    let first = create_item(&app).await;
+    let second = create_item(&app).await;
+
+    let (_, created) = comment(&app, &first, "ana", "Only on the first item").await;
+    let comment_id = created["id"].as_str().unwrap();
+
+    let (_, page) = request(&app, "GET", &format!("/api/v1/items/{second}/comments"), None).await;
+    assert_eq!(page["items"], json!([]));
+
+    // Deleting through the wrong item leaves the comment alone.
+    request(&app, "DELETE", &format!("/api/v1/items/{second}/comments/{comment_id}"), None).await;
+    let (_, page) = request(&app, "GET", &format!("/api/v1/items/{first}/comments"), None).await;
+    assert_eq!(bodies(&page), ["Only on the first item"]);
+}
+
+#[tokio::test]
+async fn empty_comment_is_rejected() {
+    let app = setup().await;
+    let item_id = create_item(&app).await;
+
+    let (status, _) = comment(&app, &item_id, "ana", "  ").await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let (status, _) = comment(&app, &item_id, "", "Hello").await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn comments_on_unknown_item_are_not_found() {
+    let app = setup().await;
+
+    let (status, _) = comment(&app, "does-not-exist", "ana", "Hello").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = request(&app, "GET", "/api/v1/items/does-not-exist/comments", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn deleting_item_deletes_its_comments() {
+    let (state, _) = setup_state().await;
+    let pool = state.pool.clone();
+    let app = create_app_with_state(state);
+    let item_id = create_item(&app).await;
+    comment(&app, &item_id, "ana", "Hello").await;
+
+    request(&app, "DELETE", &format!("/api/v1/items/{item_id}"), None).await;
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM comments").fetch_one(&pool).await.unwrap();
+    assert_eq!(count, 0);
+}
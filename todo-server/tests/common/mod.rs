@@ -0,0 +1,72 @@
+#![allow(dead_code)]
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use axum::Router;
+use todo_server::clock::FixedClock;
+use todo_server::create_app_with_state;
+use todo_server::db::init_scratch_db;
+use todo_server::id_generator::SequentialIdGenerator;
+use todo_server::modules::attachments::attachment_storage::InMemoryStorage;
+use todo_server::state::AppState;
+use chrono::{DateTime, TimeZone, Utc};
+use http_body_util::BodyExt;
+use serde_json::Value;
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+
+/// Opens a fresh in-memory database with all migrations applied.
+pub async fn setup_pool() -> SqlitePool {
+    init_scratch_db().await.expect("Failed to open in-memory database")
+}
+
+/// The instant every test clock starts at.
+pub fn start_time() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 6, 24, 8, 0, 0).unwrap()
+}
+
+/// App state with a fixed clock, sequential ids and in-memory attachment
+/// storage, so responses are reproducible and nothing touches the disk. The
+/// clock is returned so tests can move it forward.
+pub async fn setup_state() -> (AppState, Arc<FixedClock>) {
+    let clock = Arc::new(FixedClock::new(start_time()));
+    let state = AppState::new(setup_pool().await)
+        .with_clock(clock.clone())
+        .with_ids(Arc::new(SequentialIdGenerator::default()))
+        .with_storage(Arc::new(InMemoryStorage::default()));
+
+    (state, clock)
+}
+
+pub async fn setup() -> Router {
+    let (state, _) = setup_state().await;
+    create_app_with_state(state)
+}
+
+pub async fn send(app: &Router, request: Request<Body>) -> (StatusCode, Value) {
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body = if bytes.is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_slice(&bytes).unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&bytes).into_owned()))
+    };
+
+    (status, body)
+}
+
+pub async fn request(app: &Router, method: &str, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+    let builder = Request::builder().method(method).uri(uri);
+    let request = match body {
+        Some(body) => builder
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap(),
+        None => builder.body(Body::empty()).unwrap(),
+    };
+
+    send(app, request).await
+}
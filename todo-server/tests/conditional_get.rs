@@ -0,0 +1,152 @@
+mod common;
+
+use axum::body::Body;
+use axum::http::{header, HeaderMap, Request, StatusCode};
+use axum::Router;
+use todo_server::create_app_with_state;
+use chrono::Duration;
+use http_body_util::BodyExt;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use tower::ServiceExt;
+
+use common::{request, setup_state};
+
+async fn get(app: &Router, uri: &str, headers: &[(header::HeaderName, &str)]) -> (StatusCode, HeaderMap, Vec<u8>) {
+    let mut builder = Request::builder().uri(uri);
+    for (name, value) in headers {
+        builder = builder.header(name, *value);
+    }
+    let response = app.clone().oneshot(builder.body(Body::empty()).unwrap()).await.unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes().to_vec();
+
+    (status, headers, bytes)
+}
+
+#[tokio::test]
+async fn item_responses_carry_validators() {
+    let (state, _) = setup_state().await;
+    let app = create_app_with_state(state);
+    let (_, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Cached" }))).await;
+    assert_eq!(item["updated_at"], item["created_at"]);
+
+    let (status, headers, body) = get(&app, &format!("/api/v1/items/{}", item["id"].as_str().unwrap()), &[]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers[header::LAST_MODIFIED], "Mon, 24 Jun 2024 08:00:00 GMT");
+    assert_eq!(headers[header::CACHE_CONTROL], "no-cache");
+    assert_eq!(headers[header::CONTENT_TYPE], "application/json");
+    // Stable across processes: the SHA-256 of the body.
+    let digest: String = Sha256::digest(&body).iter().map(|byte| format!("{byte:02x}")).collect();
+    assert_eq!(headers[header::ETAG], format!("\"{digest}\"").as_str());
+}
+
+#[tokio::test]
+async fn matching_etag_gets_not_modified_until_the_item_changes() {
+    let (state, clock) = setup_state().await;
+    let app = create_app_with_state(state);
+    let (_, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Cached" }))).await;
+    let uri = format!("/api/v1/items/{}", item["id"].as_str().unwrap());
+
+    let (_, headers, _) = get(&app, &uri, &[]).await;
+    let etag = headers[header::ETAG].to_str().unwrap().to_string();
+
+    let (status, headers, body) = get(&app, &uri, &[(header::IF_NONE_MATCH, &etag)]).await;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+    assert_eq!(headers[header::ETAG], etag.as_str());
+    assert!(body.is_empty());
+
+    let (status, _, _) = get(&app, &uri, &[(header::IF_NONE_MATCH, &format!("\"other\", W/{etag}"))]).await;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+
+    clock.advance(Duration::minutes(5));
+    request(&app, "PATCH", &uri, Some(json!({ "name": "Changed" }))).await;
+
+    let (status, headers, _) = get(&app, &uri, &[(header::IF_NONE_MATCH, &etag)]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_ne!(headers[header::ETAG], etag.as_str());
+    assert_eq!(headers[header::LAST_MODIFIED], "Mon, 24 Jun 2024 08:05:00 GMT");
+}
+
+#[tokio::test]
+async fn if_modified_since_compares_against_updated_at() {
+    let (state, clock) = setup_state().await;
+    let app = create_app_with_state(state);
+    let (_, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Cached" }))).await;
+    let uri = format!("/api/v1/items/{}", item["id"].as_str().unwrap());
+
+    let (status, _, _) = get(&app, &uri, &[(header::IF_MODIFIED_SINCE, "Mon, 24 Jun 2024 08:00:00 GMT")]).await;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+
+    let (status, _, _) = get(&app, &uri, &[(header::IF_MODIFIED_SINCE, "Mon, 24 Jun 2024 07:59:59 GMT")]).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _, _) = get(&app, &uri, &[(header::IF_MODIFIED_SINCE, "not a date")]).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // If-None-Match takes precedence over If-Modified-Since.
+    let (status, _, _) = get(
+        &app,
+        &uri,
+        &[(header::IF_NONE_MATCH, "\"stale\""), (header::IF_MODIFIED_SINCE, "Mon, 24 Jun 2024 09:00:00 GMT")],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Moving an item reorders its neighbours too, and they count as updated.
+    clock.advance(Duration::hours(1));
+    let (_, other) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Other" }))).await;
+    request(&app, "PATCH", &format!("/api/v1/items/{}/move", other["id"].as_str().unwrap()), Some(json!({ "position": 0 }))).await;
+
+    let (status, _, _) = get(&app, &uri, &[(header::IF_MODIFIED_SINCE, "Mon, 24 Jun 2024 08:00:00 GMT")]).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn etag_describes_the_uncompressed_body_under_either_encoding() {
+    let (state, _) = setup_state().await;
+    let app = create_app_with_state(state);
+    let (_, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Cached" }))).await;
+    let uri = format!("/api/v1/items/{}", item["id"].as_str().unwrap());
+
+    let (status, plain, plain_body) = get(&app, &uri, &[]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(plain.get(header::CONTENT_ENCODING).is_none());
+    assert_eq!(serde_json::from_slice::<serde_json::Value>(&plain_body).unwrap(), item);
+
+    let (status, gzipped, gzipped_body) = get(&app, &uri, &[(header::ACCEPT_ENCODING, "gzip")]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(gzipped[header::CONTENT_ENCODING], "gzip");
+    assert_ne!(gzipped_body, plain_body);
+    assert_eq!(gzipped[header::ETAG], plain[header::ETAG]);
+
+    for headers in [&plain, &gzipped] {
+        let vary: Vec<_> = headers.get_all(header::VARY).iter().collect();
+        assert_eq!(vary, ["accept-encoding"]);
+    }
+
+    // A tag picked up from the gzipped response validates a plain copy too.
+    let etag = gzipped[header::ETAG].to_str().unwrap().to_string();
+    let (status, _, _) = get(&app, &uri, &[(header::IF_NONE_MATCH, &etag)]).await;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+}
+
+#[tokio::test]
+async fn not_modified_keeps_vary_and_skips_compression() {
+    let (state, _) = setup_state().await;
+    let app = create_app_with_state(state);
+    let (_, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Cached" }))).await;
+    let uri = format!("/api/v1/items/{}", item["id"].as_str().unwrap());
+
+    let (_, headers, _) = get(&app, &uri, &[]).await;
+    let etag = headers[header::ETAG].to_str().unwrap().to_string();
+
+    let (status, headers, body) =
+        get(&app, &uri, &[(header::IF_NONE_MATCH, &etag), (header::ACCEPT_ENCODING, "gzip")]).await;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+    assert!(body.is_empty());
+    assert!(headers.get(header::CONTENT_ENCODING).is_none());
+    assert_eq!(headers[header::ETAG], etag.as_str());
+    assert_eq!(headers.get_all(header::VARY).iter().collect::<Vec<_>>(), ["accept-encoding"]);
+}
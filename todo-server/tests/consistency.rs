@@ -0,0 +1,94 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use todo_server::create_app_with_state;
+use todo_server::modules::attachments::attachment_storage::{AttachmentStorage, InMemoryStorage};
+use serde_json::json;
+
+use common::{request, send, setup_state};
+
+const BOUNDARY: &str = "consistency-boundary";
+
+fn upload(item_id: &str) -> Request<Body> {
+    let body = format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"notes.txt\"\r\nContent-Type: text/plain\r\n\r\nhello\r\n--{BOUNDARY}--\r\n"
+    );
+
+    Request::builder()
+        .method("POST")
+        .uri(format!("/api/v1/items/{item_id}/attachments"))
+        .header(header::CONTENT_TYPE, format!("multipart/form-data; boundary={BOUNDARY}"))
+        .body(Body::from(body))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn consistent_database_reports_nothing() {
+    let (state, _) = setup_state().await;
+    let app = create_app_with_state(state);
+    let (_, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Fine" }))).await;
+    send(&app, upload(item["id"].as_str().unwrap())).await;
+
+    let (status, report) = request(&app, "GET", "/api/v1/admin/consistency", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report, json!({ "dangling_references": [], "missing_attachment_files": [], "repaired": false }));
+}
+
+#[tokio::test]
+async fn dangling_rows_and_missing_files_are_reported_and_repaired() {
+    let (state, _) = setup_state().await;
+    let storage = Arc::new(InMemoryStorage::default());
+    let state = state.with_storage(storage.clone());
+    let pool = state.pool.clone();
+    let app = create_app_with_state(state);
+
+    let (_, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Kept" }))).await;
+    let item_id = item["id"].as_str().unwrap();
+    let (_, attachment) = send(&app, upload(item_id)).await;
+    let attachment_id = attachment["id"].as_str().unwrap();
+    storage.delete(attachment_id).await.unwrap();
+
+    // Rows written while foreign keys were not enforced.
+    sqlx::query("PRAGMA foreign_keys = OFF").execute(&pool).await.unwrap();
+    sqlx::query("INSERT INTO items (id, name, description, parent_id) VALUES ('orphan', 'Orphan', '', 'gone')")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO comments (id, item_id, author, body, created_at) VALUES ('c1', 'gone', 'a', 'b', '2024-01-01T00:00:00+00:00')")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("PRAGMA foreign_keys = ON").execute(&pool).await.unwrap();
+
+    let expected = json!({
+        "dangling_references": [
+            { "table": "comments", "id": "c1", "references": "items" },
+            { "table": "items", "id": "orphan", "references": "items" },
+        ],
+        "missing_attachment_files": [attachment_id],
+    });
+
+    let (status, report) = request(&app, "GET", "/api/v1/admin/consistency", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["dangling_references"], expected["dangling_references"]);
+    assert_eq!(report["missing_attachment_files"], expected["missing_attachment_files"]);
+    assert_eq!(report["repaired"], false);
+
+    let (status, report) = request(&app, "POST", "/api/v1/admin/consistency/repair", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["dangling_references"], expected["dangling_references"]);
+    assert_eq!(report["repaired"], true);
+
+    // The orphaned subtask is kept as a top-level item; the rest is gone.
+    let (_, orphan) = request(&app, "GET", "/api/v1/items/orphan", None).await;
+    assert_eq!(orphan["parent_id"], json!(null));
+    let (_, attachments) = request(&app, "GET", &format!("/api/v1/items/{item_id}/attachments"), None).await;
+    assert_eq!(attachments, json!([]));
+
+    let (_, report) = request(&app, "GET", "/api/v1/admin/consistency", None).await;
+    assert_eq!(report["dangling_references"], json!([]));
+    assert_eq!(report["missing_attachment_files"], json!([]));
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use todo_server::config::AppConfig;
+use todo_server::db::{init_db, init_writer_pool, run_migrations};
+use todo_server::AppBuilder;
+use serde_json::json;
+use tempfile::TempDir;
+
+use common::request;
+
+fn file_config(dir: &TempDir) -> AppConfig {
+    AppConfig {
+        database_url: format!("sqlite:{}", dir.path().join("todos.db").display()),
+        db_max_connections: 4,
+        db_busy_timeout: Duration::from_millis(2500),
+        ..AppConfig::default()
+    }
+}
+
+#[tokio::test]
+async fn init_db_creates_the_file_and_sets_pragmas() {
+    let dir = TempDir::new().unwrap();
+    let config = file_config(&dir);
+
+    let pool = init_db(&config).await.unwrap();
+    assert!(dir.path().join("todos.db").exists());
+    assert_eq!(pool.options().get_max_connections(), 4);
+
+    let (journal_mode,): (String,) = sqlx::query_as("PRAGMA journal_mode").fetch_one(&pool).await.unwrap();
+    let (busy_timeout,): (i64,) = sqlx::query_as("PRAGMA busy_timeout").fetch_one(&pool).await.unwrap();
+    let (synchronous,): (i64,) = sqlx::query_as("PRAGMA synchronous").fetch_one(&pool).await.unwrap();
+    let (foreign_keys,): (i64,) = sqlx::query_as("PRAGMA foreign_keys").fetch_one(&pool).await.unwrap();
+
+    assert_eq!(journal_mode, "wal");
+    assert_eq!(busy_timeout, 2500);
+    assert_eq!(synchronous, 1);
+    assert_eq!(foreign_keys, 1);
+
+    // New files can hand free pages back through incremental vacuum.
+    let (auto_vacuum,): (i64,) = sqlx::query_as("PRAGMA auto_vacuum").fetch_one(&pool).await.unwrap();
+    assert_eq!(auto_vacuum, 2);
+}
+
+#[tokio::test]
+async fn concurrent_writes_through_the_writer_pool_all_succeed() {
+    let dir = TempDir::new().unwrap();
+    let config = file_config(&dir);
+    let pool = init_db(&config).await.unwrap();
+    run_migrations(&pool).await.unwrap();
+    let writer = init_writer_pool(&config).await.unwrap();
+    assert_eq!(writer.options().get_max_connections(), 1);
+
+    let app = AppBuilder::new(pool).with_writer_pool(writer).build().await.unwrap();
+
+    let requests = (0..20).map(|n| {
+        let app = app.clone();
+        tokio::spawn(async move { request(&app, "POST", "/api/v1/items", Some(json!({ "name": format!("Item {n}") }))).await })
+    });
+    for handle in requests {
+        let (status, _) = handle.await.unwrap();
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    let (status, page) = request(&app, "GET", "/api/v1/items", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["items"].as_array().unwrap().len(), 20);
+}
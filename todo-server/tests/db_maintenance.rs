@@ -0,0 +1,63 @@
+mod common;
+
+use todo_server::config::AppConfig;
+use todo_server::db::{init_db, run_migrations};
+use todo_server::db_maintenance::run_maintenance;
+use tempfile::TempDir;
+
+use common::setup_pool;
+
+#[tokio::test]
+async fn maintenance_reports_database_size() {
+    let pool = setup_pool().await;
+
+    let report = run_maintenance(&pool, false, 100).await.unwrap();
+
+    assert!(!report.analyzed);
+    assert!(report.page_count > 0);
+    assert_eq!(report.size_bytes(), report.page_size * report.page_count);
+}
+
+#[tokio::test]
+async fn full_analyze_collects_statistics() {
+    let pool = setup_pool().await;
+    sqlx::query("INSERT INTO items (id, name, description) VALUES ('1', 'a', '')")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let report = run_maintenance(&pool, true, 100).await.unwrap();
+    let (tables,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM sqlite_stat1 WHERE tbl = 'items'")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+    assert!(report.analyzed);
+    assert!(tables > 0);
+}
+
+#[tokio::test]
+async fn maintenance_releases_free_pages_of_a_new_database() {
+    let dir = TempDir::new().unwrap();
+    let config = AppConfig { database_url: format!("sqlite:{}", dir.path().join("todos.db").display()), ..AppConfig::default() };
+    let pool = init_db(&config).await.unwrap();
+    run_migrations(&pool).await.unwrap();
+
+    let padding = "x".repeat(4096);
+    for id in 0..200 {
+        sqlx::query("INSERT INTO items (id, name, description) VALUES (?, 'a', ?)")
+            .bind(id.to_string())
+            .bind(&padding)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+    sqlx::query("DELETE FROM items").execute(&pool).await.unwrap();
+    sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)").execute(&pool).await.unwrap();
+    let (free_before,): (i64,) = sqlx::query_as("PRAGMA freelist_count").fetch_one(&pool).await.unwrap();
+    assert!(free_before > 0);
+
+    let report = run_maintenance(&pool, false, 100_000).await.unwrap();
+
+    assert_eq!(report.freelist_count, 0);
+}
@@ -0,0 +1,151 @@
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use axum::Router;
+use todo_server::clock::FixedClock;
+use todo_server::config::AppConfig;
+use todo_server::demo::{Demo, DemoConfig, SESSION_COOKIE};
+use chrono::TimeDelta;
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use common::start_time;
+
+struct Reply {
+    status: StatusCode,
+    body: Value,
+    /// The `name=value` part of the session cookie sent back.
+    session: Option<String>,
+    /// Its `Max-Age`.
+    max_age: Option<String>,
+}
+
+async fn call(app: &Router, method: &str, uri: &str, session: Option<&str>, body: Option<Value>) -> Reply {
+    let mut builder = Request::builder().method(method).uri(uri);
+    if let Some(session) = session {
+        builder = builder.header(header::COOKIE, format!("theme=dark; {session}"));
+    }
+    let request = match body {
+        Some(body) => builder.header(header::CONTENT_TYPE, "application/json").body(Body::from(body.to_string())),
+        None => builder.body(Body::empty()),
+    };
+
+    let response = app.clone().oneshot(request.unwrap()).await.unwrap();
+    let status = response.status();
+    let cookie = response.headers().get(header::SET_COOKIE).map(|cookie| cookie.to_str().unwrap().to_string());
+    let session = cookie.as_ref().map(|cookie| cookie.split(';').next().unwrap().to_string());
+    let max_age = cookie.as_ref().and_then(|cookie| {
+        cookie.split(';').find_map(|attribute| attribute.trim().strip_prefix("Max-Age=")).map(str::to_string)
+    });
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+
+    Reply { status, body, session, max_age }
+}
+
+fn demo(config: DemoConfig) -> (Demo, Arc<FixedClock>) {
+    let clock = Arc::new(FixedClock::new(start_time()));
+
+    (Demo::with_clock(config, AppConfig::default(), clock.clone()), clock)
+}
+
+#[tokio::test]
+async fn each_session_gets_its_own_database() {
+    let (demo, _) = demo(DemoConfig::default());
+    let app = demo.router();
+
+    let first = call(&app, "POST", "/api/v1/items", None, Some(json!({ "name": "Mine" }))).await;
+    assert_eq!(first.status, StatusCode::OK);
+    let session = first.session.unwrap();
+    assert!(session.starts_with(&format!("{SESSION_COOKIE}=")));
+
+    let again = call(&app, "GET", "/api/v1/items", Some(&session), None).await;
+    assert_eq!(again.session, Some(session.clone()));
+    assert_eq!(again.body["items"][0]["name"], "Mine");
+
+    let stranger = call(&app, "GET", "/api/v1/items", None, None).await;
+    assert!(stranger.session.is_some_and(|other| other != session));
+    assert_eq!(stranger.body["items"], json!([]));
+    assert_eq!(demo.session_count(), 2);
+}
+
+#[tokio::test]
+async fn idle_sessions_expire_with_their_data() {
+    let (demo, clock) = demo(DemoConfig { session_ttl: Duration::from_secs(60), ..DemoConfig::default() });
+    let app = demo.router();
+
+    let session = call(&app, "POST", "/api/v1/items", None, Some(json!({ "name": "Gone soon" }))).await.session.unwrap();
+
+    // Requests keep a session alive, and renew its cookie.
+    for _ in 0..2 {
+        clock.advance(TimeDelta::seconds(45));
+        let reply = call(&app, "GET", "/api/v1/items", Some(&session), None).await;
+        assert_eq!(reply.session, Some(session.clone()));
+        assert_eq!(reply.max_age.as_deref(), Some("60"));
+        assert_eq!(reply.body["items"][0]["name"], "Gone soon");
+    }
+
+    clock.advance(TimeDelta::seconds(61));
+    demo.sweep();
+    assert_eq!(demo.session_count(), 0);
+
+    let reply = call(&app, "GET", "/api/v1/items", Some(&session), None).await;
+    assert!(reply.session.is_some_and(|new| new != session));
+    assert_eq!(reply.body["items"], json!([]));
+}
+
+#[tokio::test]
+async fn sessions_and_requests_are_rate_limited() {
+    let (demo, clock) = demo(DemoConfig {
+        sessions_per_client_per_hour: 2,
+        requests_per_session_per_minute: 3,
+        ..DemoConfig::default()
+    });
+    let app = demo.router();
+
+    let session = call(&app, "GET", "/api/v1/items", None, None).await.session.unwrap();
+    call(&app, "GET", "/api/v1/items", None, None).await;
+    let refused = call(&app, "GET", "/api/v1/items", None, None).await;
+    assert_eq!(refused.status, StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(refused.body["code"], "rate_limited");
+    assert_eq!(refused.session, None);
+
+    for _ in 0..2 {
+        assert_eq!(call(&app, "GET", "/api/v1/items", Some(&session), None).await.status, StatusCode::OK);
+    }
+    assert_eq!(call(&app, "GET", "/api/v1/items", Some(&session), None).await.status, StatusCode::TOO_MANY_REQUESTS);
+
+    clock.advance(TimeDelta::minutes(1));
+    assert_eq!(call(&app, "GET", "/api/v1/items", Some(&session), None).await.status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn the_demo_refuses_sessions_when_full() {
+    let (demo, _) = demo(DemoConfig { max_sessions: 1, ..DemoConfig::default() });
+    let app = demo.router();
+
+    call(&app, "GET", "/api/v1/items", None, None).await;
+    let refused = call(&app, "GET", "/api/v1/items", None, None).await;
+    assert_eq!(refused.status, StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(refused.body["message"], "The demo is full, try again later");
+}
+
+#[tokio::test]
+async fn sessions_started_at_once_cannot_overfill_the_demo() {
+    let (demo, _) = demo(DemoConfig { max_sessions: 1, ..DemoConfig::default() });
+    let app = demo.router();
+
+    let (first, second) = tokio::join!(
+        call(&app, "GET", "/api/v1/items", None, None),
+        call(&app, "GET", "/api/v1/items", None, None),
+    );
+    let mut statuses = [first.status, second.status];
+    statuses.sort();
+    assert_eq!(statuses, [StatusCode::OK, StatusCode::TOO_MANY_REQUESTS]);
+    assert_eq!(demo.session_count(), 1);
+}
@@ -0,0 +1,45 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::Router;
+use todo_server::id_generator::SequentialIdGenerator;
+use todo_server::modules::attachments::attachment_storage::InMemoryStorage;
+use todo_server::AppBuilder;
+use serde_json::json;
+use sqlx::sqlite::SqlitePoolOptions;
+
+use common::request;
+
+#[tokio::test]
+async fn builder_runs_migrations_and_nests_into_a_host_app() {
+    // Unmigrated, unlike the pool from `common::setup_pool`.
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .idle_timeout(None)
+        .max_lifetime(None)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    let todos = AppBuilder::new(pool)
+        .with_ids(Arc::new(SequentialIdGenerator::default()))
+        .with_storage(Arc::new(InMemoryStorage::default()))
+        .with_migrations()
+        .build()
+        .await
+        .unwrap();
+    let app = Router::new()
+        .route("/health", get(|| async { "ok" }))
+        .nest("/todos", todos);
+
+    let (status, item) = request(&app, "POST", "/todos/api/v1/items", Some(json!({ "name": "Embedded" }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(item["id"], "00000000-0000-0000-0000-000000000001");
+
+    let (status, body) = request(&app, "GET", "/health", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "ok");
+}
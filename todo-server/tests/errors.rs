@@ -0,0 +1,126 @@
+mod common;
+
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use todo_server::error::AppError;
+use serde_json::json;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::{Connection, SqliteConnection};
+
+use common::{request, setup, setup_pool};
+
+#[test]
+fn missing_row_is_not_found() {
+    let err = AppError::from(sqlx::Error::RowNotFound);
+
+    assert!(matches!(err, AppError::NotFound));
+    assert_eq!(err.status(), StatusCode::NOT_FOUND);
+    assert!(!err.retryable());
+}
+
+#[tokio::test]
+async fn duplicate_primary_key_is_a_conflict() {
+    let pool = setup_pool().await;
+    let insert = "INSERT INTO items (id, name, description, created_at) VALUES ('1', 'a', '', '2024-01-01T00:00:00+00:00')";
+    sqlx::query(insert).execute(&pool).await.unwrap();
+
+    let err = AppError::from(sqlx::query(insert).execute(&pool).await.unwrap_err());
+
+    assert!(matches!(err, AppError::Conflict(_)));
+    assert_eq!(err.status(), StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn dangling_foreign_key_is_a_conflict() {
+    let pool = setup_pool().await;
+
+    let err = sqlx::query("INSERT INTO reminders (id, item_id, remind_at, webhook_url, next_attempt_at) VALUES ('r', 'missing', '', '', '')")
+        .execute(&pool)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(AppError::from(err), AppError::Conflict(_)));
+}
+
+#[tokio::test]
+async fn not_null_violation_is_unprocessable() {
+    let pool = setup_pool().await;
+
+    let err = sqlx::query("INSERT INTO items (id, name, description) VALUES ('1', NULL, '')")
+        .execute(&pool)
+        .await
+        .unwrap_err();
+
+    let err = AppError::from(err);
+    assert!(matches!(err, AppError::Unprocessable(_)));
+    assert_eq!(err.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn locked_database_is_busy_and_retryable() {
+    let dir = tempfile::tempdir().unwrap();
+    let options = SqliteConnectOptions::new()
+        .filename(dir.path().join("busy.db"))
+        .create_if_missing(true)
+        .busy_timeout(Duration::ZERO);
+
+    let mut writer = SqliteConnection::connect_with(&options).await.unwrap();
+    sqlx::query("CREATE TABLE t (x INTEGER)").execute(&mut writer).await.unwrap();
+    sqlx::query("BEGIN IMMEDIATE").execute(&mut writer).await.unwrap();
+
+    let mut other = SqliteConnection::connect_with(&options).await.unwrap();
+    let err = sqlx::query("INSERT INTO t VALUES (1)").execute(&mut other).await.unwrap_err();
+
+    let err = AppError::from(err);
+    assert!(matches!(err, AppError::Busy));
+    assert_eq!(err.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert!(err.retryable());
+
+    let response = err.into_response();
+    assert_eq!(response.headers()["retry-after"], "1");
+}
+
+#[tokio::test]
+async fn exhausted_pool_is_busy() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_millis(10))
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    let _held = pool.acquire().await.unwrap();
+
+    let err = AppError::from(pool.acquire().await.unwrap_err());
+
+    assert!(matches!(err, AppError::Busy));
+}
+
+#[tokio::test]
+async fn file_that_is_not_a_database_is_corrupt() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("garbage.db");
+    std::fs::write(&path, vec![0x42; 4096]).unwrap();
+
+    let options = SqliteConnectOptions::new().filename(&path);
+    let err = match SqliteConnection::connect_with(&options).await {
+        Ok(mut conn) => sqlx::query("SELECT * FROM sqlite_master").execute(&mut conn).await.unwrap_err(),
+        Err(err) => err,
+    };
+
+    let err = AppError::from(err);
+    assert!(matches!(err, AppError::Corrupt(_)));
+    assert_eq!(err.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(!err.retryable());
+}
+
+#[tokio::test]
+async fn error_responses_have_a_json_body() {
+    let app = setup().await;
+
+    let (status, body) = request(&app, "PUT", "/api/v1/items/does-not-exist", Some(json!({ "name": "x" }))).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body, json!({ "code": "not_found", "message": "Not found", "retryable": false }));
+}
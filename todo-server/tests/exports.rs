@@ -0,0 +1,128 @@
+mod common;
+
+use std::io::{self, Write};
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use todo_server::create_app_with_state;
+use todo_server::modules::exports::exporter::{Exporter, ExporterRegistry};
+use todo_server::modules::todos::todo_entity::Item;
+use axum::Router;
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use common::{request, setup, setup_state};
+
+async fn download(app: &Router, uri: &str, accept: Option<&str>) -> (StatusCode, String, String) {
+    let mut builder = Request::builder().uri(uri);
+    if let Some(accept) = accept {
+        builder = builder.header(header::ACCEPT, accept);
+    }
+    let response = app.clone().oneshot(builder.body(Body::empty()).unwrap()).await.unwrap();
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .map(|value| value.to_str().unwrap().to_string())
+        .unwrap_or_default();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+
+    (status, content_type, String::from_utf8(bytes.to_vec()).unwrap())
+}
+
+async fn create_items(app: &Router) {
+    let (_, parent) = request(app, "POST", "/api/v1/items", Some(json!({ "name": "Plan, then \"ship\"", "priority": "high" }))).await;
+    request(
+        app,
+        "POST",
+        "/api/v1/items",
+        Some(json!({ "name": "Write notes", "description": "Two\nlines", "priority": "low", "parent_id": parent["id"] })),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn export_writes_csv_with_quoting() {
+    let app = setup().await;
+    create_items(&app).await;
+
+    let (status, content_type, body) = download(&app, "/api/v1/items/export?format=csv", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "text/csv");
+    assert_eq!(
+        body,
+        "id,name,description,priority,position,parent_id,created_at,updated_at\r\n\
+         00000000-0000-0000-0000-000000000001,\"Plan, then \"\"ship\"\"\",,high,0,,2024-06-24T08:00:00+00:00,2024-06-24T08:00:00+00:00\r\n\
+         00000000-0000-0000-0000-000000000003,Write notes,\"Two\nlines\",low,1,00000000-0000-0000-0000-000000000001,2024-06-24T08:00:00+00:00,2024-06-24T08:00:00+00:00\r\n"
+    );
+}
+
+#[tokio::test]
+async fn export_picks_the_format_from_accept() {
+    let app = setup().await;
+    create_items(&app).await;
+
+    let (status, content_type, body) = download(&app, "/api/v1/items/export", Some("application/pdf, text/plain;q=0.5")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "text/plain");
+    assert_eq!(
+        body,
+        "(A) 2024-06-24 Plan, then \"ship\" id:00000000-0000-0000-0000-000000000001\n\
+         (C) 2024-06-24 Write notes id:00000000-0000-0000-0000-000000000003 parent:00000000-0000-0000-0000-000000000001\n"
+    );
+
+    // Without a usable Accept the export is JSON.
+    let (status, content_type, body) = download(&app, "/api/v1/items/export", Some("*/*")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "application/json");
+    let items: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(items.as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn export_rejects_unknown_formats() {
+    let app = setup().await;
+
+    let (status, body) = request(&app, "GET", "/api/v1/items/export?format=xlsx", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["message"], "Unknown export format \"xlsx\"; expected one of json, csv, todotxt");
+}
+
+struct NamesExporter;
+
+impl Exporter for NamesExporter {
+    fn name(&self) -> &'static str {
+        "names"
+    }
+
+    fn media_type(&self) -> &'static str {
+        "text/x-names"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "names"
+    }
+
+    fn write(&self, items: &[Item], out: &mut dyn Write) -> io::Result<()> {
+        for item in items {
+            writeln!(out, "{}", item.name)?;
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn export_serves_formats_registered_by_the_embedding_app() {
+    let (state, _) = setup_state().await;
+    let mut exporters = ExporterRegistry::default();
+    exporters.register(Arc::new(NamesExporter));
+    let app = create_app_with_state(state.with_exporters(exporters));
+    create_items(&app).await;
+
+    let (status, content_type, body) = download(&app, "/api/v1/items/export", Some("text/x-names")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "text/x-names");
+    assert_eq!(body, "Plan, then \"ship\"\nWrite notes\n");
+}
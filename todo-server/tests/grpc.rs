@@ -0,0 +1,120 @@
+mod common;
+
+use axum::http::StatusCode;
+use axum::Router;
+use todo_server::create_app_with_state;
+use todo_server::grpc::grpc_routes;
+use todo_server::grpc::proto::todo_service_client::TodoServiceClient;
+use todo_server::grpc::proto::{
+    CreateItemRequest, DeleteItemRequest, GetItemRequest, ListItemsRequest, Priority, UpdateItemRequest,
+};
+use todo_server::state::AppState;
+use tonic::transport::Channel;
+use tonic::{Code, Request};
+
+use common::{request, setup_state};
+
+/// REST and gRPC on one router, as `main` serves them without `GRPC_PORT`.
+fn combined_app(state: AppState) -> Router {
+    create_app_with_state(state.clone()).merge(grpc_routes(state))
+}
+
+async fn serve(app: Router) -> TodoServiceClient<Channel> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    TodoServiceClient::connect(format!("http://{address}")).await.unwrap()
+}
+
+#[tokio::test]
+async fn grpc_covers_the_item_lifecycle() {
+    let (state, _) = setup_state().await;
+    let mut client = serve(combined_app(state)).await;
+
+    let item = client
+        .create_item(CreateItemRequest {
+            name: "Write proto".to_string(),
+            description: Some("Five RPCs".to_string()),
+            priority: Priority::High.into(),
+            parent_id: None,
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(item.id, "00000000-0000-0000-0000-000000000001");
+    assert_eq!(item.priority(), Priority::High);
+    assert_eq!(item.created_at, "2024-06-24T08:00:00+00:00");
+
+    let fetched = client.get_item(GetItemRequest { id: item.id.clone() }).await.unwrap().into_inner();
+    assert_eq!(fetched, item);
+
+    let updated = client
+        .update_item(UpdateItemRequest {
+            id: item.id.clone(),
+            name: Some("Write todo.proto".to_string()),
+            clear_description: true,
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(updated.name, "Write todo.proto");
+    assert_eq!(updated.description, None);
+    assert_eq!(updated.priority(), Priority::High);
+
+    let page = client.list_items(ListItemsRequest::default()).await.unwrap().into_inner();
+    assert_eq!(page.items, vec![updated]);
+    assert_eq!(page.next_cursor, None);
+
+    client.delete_item(DeleteItemRequest { id: item.id.clone() }).await.unwrap();
+    let status = client.get_item(GetItemRequest { id: item.id }).await.unwrap_err();
+    assert_eq!(status.code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn grpc_and_rest_share_the_same_items() {
+    let (state, _) = setup_state().await;
+    let app = combined_app(state);
+    let mut client = serve(app.clone()).await;
+
+    let mut create = Request::new(CreateItemRequest { name: "Over gRPC".to_string(), ..Default::default() });
+    create.metadata_mut().insert("x-actor", "alice".parse().unwrap());
+    create.metadata_mut().insert("x-request-id", "grpc-request-1".parse().unwrap());
+    let item = client.create_item(create).await.unwrap().into_inner();
+    assert_eq!(item.priority(), Priority::Medium);
+
+    let (status, body) = request(&app, "GET", &format!("/api/v1/items/{}", item.id), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["name"], "Over gRPC");
+
+    let (_, history) = request(&app, "GET", &format!("/api/v1/items/{}/history", item.id), None).await;
+    assert_eq!(history["items"][0]["actor"], "alice");
+    assert_eq!(history["items"][0]["request_id"], "grpc-request-1");
+
+    // REST paths the gRPC service does not own still get REST errors.
+    let (status, _) = request(&app, "GET", "/api/v1/missing", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn grpc_maps_validation_errors_to_invalid_argument() {
+    let (state, _) = setup_state().await;
+    let mut client = serve(combined_app(state)).await;
+
+    let status = client
+        .create_item(CreateItemRequest {
+            name: "Orphan".to_string(),
+            parent_id: Some("missing".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+
+    let status = client
+        .list_items(ListItemsRequest { cursor: Some("garbage".to_string()), ..Default::default() })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+}
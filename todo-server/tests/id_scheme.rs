@@ -0,0 +1,91 @@
+mod common;
+
+use std::thread;
+use std::time::Duration as StdDuration;
+
+use axum::http::StatusCode;
+use todo_server::config::IdScheme;
+use todo_server::create_app_with_state;
+use todo_server::id_generator::{IdGenerator, UuidV7Generator};
+use todo_server::id_migration::migrate_item_ids;
+use chrono::Duration;
+use serde_json::json;
+use uuid::Uuid;
+
+use common::{request, setup_state};
+
+#[test]
+fn v7_ids_sort_by_creation_time() {
+    let first = UuidV7Generator.next_id();
+    thread::sleep(StdDuration::from_millis(2));
+    let second = UuidV7Generator.next_id();
+
+    assert!(first < second);
+    assert_eq!(Uuid::parse_str(&first).unwrap().get_version_num(), 7);
+}
+
+#[test]
+fn id_scheme_parses_env_values() {
+    assert_eq!(IdScheme::parse("uuidv7"), Some(IdScheme::UuidV7));
+    assert_eq!(IdScheme::parse("UUIDv4"), Some(IdScheme::UuidV4));
+    assert_eq!(IdScheme::parse("ulid"), None);
+}
+
+#[tokio::test]
+async fn migration_rewrites_subtask_parents() {
+    let (state, clock) = setup_state().await;
+    let pool = state.pool.clone();
+    let app = create_app_with_state(state);
+
+    let (_, parent) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "parent" }))).await;
+    let parent_id = parent["id"].as_str().unwrap();
+    clock.advance(Duration::seconds(1));
+    let (_, child) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "child", "parent_id": parent_id }))).await;
+    assert_eq!(child["parent_id"], parent_id);
+
+    assert_eq!(migrate_item_ids(&pool).await.unwrap(), 2);
+
+    let (_, page) = request(&app, "GET", "/api/v1/items", None).await;
+    let new_parent_id = page["items"][0]["id"].as_str().unwrap();
+    assert_ne!(new_parent_id, parent_id);
+    assert_eq!(page["items"][1]["parent_id"], new_parent_id);
+
+    let (status, subtasks) = request(&app, "GET", &format!("/api/v1/items/{new_parent_id}/subtasks"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(subtasks[0]["name"], "child");
+}
+
+#[tokio::test]
+async fn migration_rewrites_item_ids_and_references() {
+    let (state, clock) = setup_state().await;
+    let pool = state.pool.clone();
+    let app = create_app_with_state(state);
+
+    let mut old_ids = Vec::new();
+    for name in ["a", "b", "c"] {
+        let (_, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": name, "description": "" }))).await;
+        old_ids.push(item["id"].as_str().unwrap().to_string());
+        clock.advance(Duration::seconds(1));
+    }
+    request(&app, "POST", &format!("/api/v1/items/{}/comments", old_ids[0]), Some(json!({ "author": "alice", "body": "hi" }))).await;
+
+    assert_eq!(migrate_item_ids(&pool).await.unwrap(), 3);
+
+    let (_, page) = request(&app, "GET", "/api/v1/items", None).await;
+    let new_ids: Vec<String> = page["items"].as_array().unwrap().iter().map(|item| item["id"].as_str().unwrap().to_string()).collect();
+    assert!(new_ids.iter().all(|id| Uuid::parse_str(id).unwrap().get_version_num() == 7));
+    assert!(new_ids.windows(2).all(|pair| pair[0] < pair[1]));
+
+    let (status, comments) = request(&app, "GET", &format!("/api/v1/items/{}/comments", new_ids[0]), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(comments["items"][0]["body"], "hi");
+
+    let (_, history) = request(&app, "GET", &format!("/api/v1/items/{}/history", new_ids[0]), None).await;
+    assert_eq!(history["items"][0]["action"], "create");
+
+    let (status, _) = request(&app, "GET", &format!("/api/v1/items/{}", old_ids[0]), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Already migrated ids are left alone.
+    assert_eq!(migrate_item_ids(&pool).await.unwrap(), 0);
+}
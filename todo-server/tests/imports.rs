@@ -0,0 +1,230 @@
+mod common;
+
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use axum::Router;
+use todo_server::create_app_with_state;
+use serde_json::{json, Value};
+
+use common::{request, send, setup, setup_state};
+
+async fn upload(app: &Router, uri: &str, content_type: &str, body: &str) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header(header::CONTENT_TYPE, content_type)
+        .body(Body::from(body.to_string()))
+        .unwrap();
+
+    send(app, request).await
+}
+
+async fn item_names(app: &Router) -> Vec<(String, String)> {
+    let (_, page) = request(app, "GET", "/api/v1/items", None).await;
+    page["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| (item["name"].as_str().unwrap().to_string(), item["priority"].as_str().unwrap().to_string()))
+        .collect()
+}
+
+#[tokio::test]
+async fn import_reads_csv_quoting_and_ignores_unknown_columns() {
+    let app = setup().await;
+    let csv = "id,name,description,priority\r\n\
+               old-1,\"Plan, then \"\"ship\"\"\",,high\r\n\
+               old-2,Write notes,\"Two\nlines\",low\r\n";
+
+    let (status, report) = upload(&app, "/api/v1/items/import", "text/csv; charset=utf-8", csv).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report, json!({ "format": "csv", "dry_run": false, "rows": 2, "created": 2, "duplicates": [], "errors": [] }));
+
+    let (_, page) = request(&app, "GET", "/api/v1/items", None).await;
+    assert_eq!(page["items"][0]["name"], "Plan, then \"ship\"");
+    assert_eq!(page["items"][0]["description"], Value::Null);
+    assert_eq!(page["items"][1]["description"], "Two\nlines");
+    assert_eq!(page["items"][1]["priority"], "low");
+}
+
+#[tokio::test]
+async fn import_round_trips_the_todotxt_export() {
+    let app = setup().await;
+    let todo_txt = "(A) 2024-06-20 Call the bank id:123 +finance\n\
+                    \n\
+                    x 2024-06-21 2024-06-20 Pay rent\n\
+                    Read https://example.com later\n";
+
+    let (status, report) = upload(&app, "/api/v1/items/import?format=todotxt", "application/octet-stream", todo_txt).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["created"], 3);
+
+    assert_eq!(
+        item_names(&app).await,
+        [
+            ("Call the bank +finance".to_string(), "high".to_string()),
+            ("Pay rent".to_string(), "medium".to_string()),
+            ("Read https://example.com later".to_string(), "medium".to_string()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn import_reads_the_tasks_of_a_todoist_export() {
+    let app = setup().await;
+    let csv = include_str!("fixtures/todoist.csv");
+
+    let (status, report) = upload(&app, "/api/v1/items/import?format=todoist", "text/csv", csv).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report, json!({ "format": "todoist", "dry_run": false, "rows": 4, "created": 4, "duplicates": [], "errors": [] }));
+
+    assert_eq!(
+        item_names(&app).await,
+        [
+            ("Book the dentist".to_string(), "high".to_string()),
+            ("Buy stamps @errands".to_string(), "medium".to_string()),
+            ("Pick up the 'big' parcel".to_string(), "medium".to_string()),
+            ("Return library books".to_string(), "low".to_string()),
+        ]
+    );
+    let (_, page) = request(&app, "GET", "/api/v1/items", None).await;
+    assert_eq!(page["items"][0]["description"], "Ask about Tuesday, or else Friday");
+}
+
+#[tokio::test]
+async fn import_reads_the_open_cards_of_a_trello_board() {
+    let app = setup().await;
+    let board = include_str!("fixtures/trello.json");
+
+    let (status, report) = upload(&app, "/api/v1/items/import?format=trello", "application/json", board).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report, json!({ "format": "trello", "dry_run": false, "rows": 2, "created": 2, "duplicates": [], "errors": [] }));
+
+    assert_eq!(
+        item_names(&app).await,
+        [
+            ("Fix the fence".to_string(), "high".to_string()),
+            ("Paint the shed".to_string(), "medium".to_string()),
+        ]
+    );
+    let (_, page) = request(&app, "GET", "/api/v1/items", None).await;
+    assert_eq!(page["items"][0]["description"], "The panel by the gate is loose.");
+    assert_eq!(page["items"][1]["description"], Value::Null);
+
+    let (status, body) = upload(&app, "/api/v1/items/import?format=trello", "application/json", "[]").await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(body["errors"][0]["message"].as_str().unwrap().starts_with("Invalid Trello board"));
+}
+
+#[tokio::test]
+async fn dry_run_validates_without_creating() {
+    let app = setup().await;
+
+    let (status, report) = upload(
+        &app,
+        "/api/v1/items/import?dry_run=true",
+        "application/json",
+        r#"[{ "name": "One" }, { "name": "Two", "priority": "high" }]"#,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report, json!({ "format": "json", "dry_run": true, "rows": 2, "created": 0, "duplicates": [], "errors": [] }));
+    assert!(item_names(&app).await.is_empty());
+}
+
+#[tokio::test]
+async fn importing_again_skips_the_rows_already_there() {
+    let app = setup().await;
+    let (_, existing) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Water plants", "description": "" }))).await;
+    let csv = "name,description\nWater plants,\nPay rent,Before Friday\nPay rent,Before Friday\nPay rent,\n";
+
+    let (status, report) = upload(&app, "/api/v1/items/import?format=csv", "text/csv", csv).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["created"], 2);
+    assert_eq!(
+        report["duplicates"],
+        json!([
+            { "row": 1, "item_id": existing["id"] },
+            { "row": 3, "item_id": null },
+        ])
+    );
+
+    let (_, report) = upload(&app, "/api/v1/items/import?format=csv", "text/csv", csv).await;
+    assert_eq!(report["created"], 0);
+    assert_eq!(report["duplicates"].as_array().unwrap().len(), 4);
+    assert_eq!(item_names(&app).await.len(), 3);
+}
+
+#[tokio::test]
+async fn invalid_rows_are_reported_and_nothing_is_created() {
+    let app = setup().await;
+    let csv = "name,priority\nFine,low\n  ,high\nAlso fine,urgent\n";
+
+    let (status, report) = upload(&app, "/api/v1/items/import?format=csv", "text/plain", csv).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(report["rows"], 3);
+    assert_eq!(report["created"], 0);
+    assert_eq!(
+        report["errors"],
+        json!([
+            { "row": 2, "message": "name must not be empty" },
+            { "row": 3, "message": "Unknown priority \"urgent\"" },
+        ])
+    );
+    assert!(item_names(&app).await.is_empty());
+}
+
+#[tokio::test]
+async fn a_failed_import_creates_nothing() {
+    let (state, _) = setup_state().await;
+    let app = create_app_with_state(state.clone());
+    sqlx::query("CREATE TRIGGER refuse_second BEFORE INSERT ON items WHEN NEW.name = 'Second' BEGIN SELECT RAISE(ABORT, 'refused'); END")
+        .execute(&state.pool)
+        .await
+        .unwrap();
+
+    let json = json!([{ "name": "First" }, { "name": "Second" }, { "name": "Third" }]).to_string();
+    let (status, _) = upload(&app, "/api/v1/items/import", "application/json", &json).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(item_names(&app).await.is_empty());
+
+    let (entries,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM audit_log").fetch_one(&state.pool).await.unwrap();
+    assert_eq!(entries, 0);
+}
+
+#[tokio::test]
+async fn import_needs_a_known_format() {
+    let app = setup().await;
+
+    let (status, body) = upload(&app, "/api/v1/items/import", "application/pdf", "%PDF").await;
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_eq!(body["message"], "Cannot import \"application/pdf\"; pass ?format= with one of json, csv, todotxt, todoist, trello");
+
+    let (status, _) = upload(&app, "/api/v1/items/import?format=asana", "application/json", "[]").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = upload(&app, "/api/v1/items/import?format=csv", "text/csv", "title\nNo name column\n").await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["errors"][0]["message"], "The header has no name column");
+}
+
+#[tokio::test]
+async fn json_rows_are_read_until_the_array_breaks() {
+    let app = setup().await;
+
+    let (status, report) = upload(&app, "/api/v1/items/import", "application/json", r#"[{ "name": "One, [two]" }, { "name": 2 } { "name": "Three" }]"#).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(report["rows"], 3);
+    assert!(report["errors"][0]["message"].as_str().unwrap().starts_with("invalid type"));
+    assert!(report["errors"][1]["message"].as_str().unwrap().starts_with("Invalid JSON array"));
+}
+
+#[tokio::test]
+async fn an_import_past_the_body_limit_is_refused() {
+    let app = setup().await;
+    let rows: Vec<_> = (0..80_000).map(|n| json!({ "name": format!("Item {n}") })).collect();
+
+    let (status, _) = upload(&app, "/api/v1/items/import", "application/json", &Value::from(rows).to_string()).await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert!(item_names(&app).await.is_empty());
+}
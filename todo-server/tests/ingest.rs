@@ -0,0 +1,73 @@
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use todo_server::create_app_with_state;
+use todo_server::id_generator::IdGenerator;
+use todo_server::modules::todos::todo_batcher::{ItemBatcher, ItemBatcherConfig};
+use serde_json::json;
+use tokio::task::JoinSet;
+
+use common::{request, setup_state};
+
+/// Hands out the same id every time, so the second insert of an item hits the
+/// primary key.
+struct ConstantIdGenerator;
+
+impl IdGenerator for ConstantIdGenerator {
+    fn next_id(&self) -> String {
+        "same".to_string()
+    }
+}
+
+#[tokio::test]
+async fn concurrent_creates_are_all_acknowledged() {
+    let (state, _) = setup_state().await;
+    let batcher = ItemBatcher::spawn(&state, ItemBatcherConfig::default());
+    let app = create_app_with_state(state.with_item_batcher(batcher));
+
+    let mut requests = JoinSet::new();
+    for n in 0..20 {
+        let app = app.clone();
+        requests.spawn(async move {
+            request(&app, "POST", "/api/v1/items", Some(json!({ "name": format!("item {n}"), "description": "" }))).await
+        });
+    }
+
+    let mut positions = Vec::new();
+    while let Some(result) = requests.join_next().await {
+        let (status, item) = result.unwrap();
+        assert_eq!(status, StatusCode::OK);
+        positions.push(item["position"].as_i64().unwrap());
+    }
+    positions.sort();
+    assert_eq!(positions, (0..20).collect::<Vec<_>>());
+
+    let (_, page) = request(&app, "GET", "/api/v1/items", None).await;
+    assert_eq!(page["items"].as_array().unwrap().len(), 20);
+}
+
+#[tokio::test]
+async fn a_failing_insert_does_not_fail_its_batch() {
+    let (state, _) = setup_state().await;
+    let state = state.with_ids(Arc::new(ConstantIdGenerator));
+    let config = ItemBatcherConfig {
+        max_delay: Duration::from_millis(200),
+        ..ItemBatcherConfig::default()
+    };
+    let batcher = ItemBatcher::spawn(&state, config);
+    let app = create_app_with_state(state.with_item_batcher(batcher));
+
+    let first = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "a", "description": "" })));
+    let second = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "b", "description": "" })));
+    let ((first, _), (second, _)) = tokio::join!(first, second);
+
+    let mut statuses = [first, second];
+    statuses.sort();
+    assert_eq!(statuses, [StatusCode::OK, StatusCode::CONFLICT]);
+
+    let (status, _) = request(&app, "GET", "/api/v1/items/same", None).await;
+    assert_eq!(status, StatusCode::OK);
+}
@@ -0,0 +1,127 @@
+mod common;
+
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use axum::Router;
+use todo_server::create_app_with_state;
+use todo_server::modules::todos::todo_hooks::{ItemHookLimits, ItemHooks};
+use serde_json::json;
+
+use common::{request, setup_state};
+
+async fn app_with_hooks(scripts: &[(&str, &str)]) -> Router {
+    let (state, _) = setup_state().await;
+    let hooks = ItemHooks::compile(scripts.iter().copied(), ItemHookLimits::default()).unwrap();
+
+    create_app_with_state(state.with_item_hooks(hooks))
+}
+
+#[tokio::test]
+async fn create_hooks_can_change_the_new_item_in_order() {
+    let app = app_with_hooks(&[
+        ("10-tidy.rhai", r#"
+            fn on_create(item) {
+                item.name.trim();
+                item
+            }
+        "#),
+        ("20-urgent.rhai", r#"
+            fn on_create(item) {
+                if item.name.starts_with("URGENT") {
+                    item.priority = "high";
+                    item.description = "Flagged by a hook";
+                }
+                item
+            }
+        "#),
+    ])
+    .await;
+
+    let (status, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "  URGENT: renew cert " }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(item["name"], "URGENT: renew cert");
+    assert_eq!(item["priority"], "high");
+    assert_eq!(item["description"], "Flagged by a hook");
+
+    // A hook returning nothing leaves the item as it is.
+    let app = app_with_hooks(&[("noop.rhai", "fn on_create(item) { }")]).await;
+    let (_, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "As sent" }))).await;
+    assert_eq!(item["name"], "As sent");
+
+    // One that breaks the item fails the request rather than writing it.
+    let app = app_with_hooks(&[("broken.rhai", "fn on_create(item) { item.name = (); item }")]).await;
+    let (status, _) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Broken" }))).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+}
+
+#[tokio::test]
+async fn hooks_can_veto_updates_and_deletes() {
+    let app = app_with_hooks(&[("guard.rhai", r#"
+        fn on_update(item, before) {
+            if before.priority == "high" && item.priority != "high" {
+                throw "High priority items cannot be downgraded";
+            }
+        }
+
+        fn on_delete(item) {
+            if item.name.contains("keep") {
+                throw `"${item.name}" is protected`;
+            }
+        }
+    "#)])
+    .await;
+
+    let (_, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "keep me", "priority": "high" }))).await;
+    let uri = format!("/api/v1/items/{}", item["id"].as_str().unwrap());
+
+    let (status, body) = request(&app, "PATCH", &uri, Some(json!({ "priority": "low" }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["message"], "High priority items cannot be downgraded");
+
+    let (status, _) = request(&app, "PATCH", &uri, Some(json!({ "description": "Still high" }))).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, body) = request(&app, "DELETE", &uri, None).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["message"], "\"keep me\" is protected");
+
+    let (_, item) = request(&app, "GET", &uri, None).await;
+    assert_eq!(item["priority"], "high");
+    assert_eq!(item["description"], "Still high");
+
+    let (status, _) = request(&app, "DELETE", "/api/v1/items/missing", None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn runaway_scripts_are_stopped_and_fail_the_operation() {
+    let (state, _) = setup_state().await;
+    let limits = ItemHookLimits { timeout: Duration::from_millis(20), max_operations: u64::MAX, ..ItemHookLimits::default() };
+    let hooks = ItemHooks::compile([("spin.rhai", "fn on_create(item) { loop { } }")], limits).unwrap();
+    let app = create_app_with_state(state.with_item_hooks(hooks));
+
+    let (status, _) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Never" }))).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+
+    let (_, page) = request(&app, "GET", "/api/v1/items", None).await;
+    assert_eq!(page["items"], json!([]));
+}
+
+#[tokio::test]
+async fn hooks_load_from_a_directory_in_name_order() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("b.rhai"), r#"fn on_create(item) { item.name += " b"; item }"#).unwrap();
+    std::fs::write(dir.path().join("a.rhai"), r#"fn on_create(item) { item.name += " a"; item }"#).unwrap();
+    std::fs::write(dir.path().join("notes.txt"), "not a script").unwrap();
+
+    let (state, _) = setup_state().await;
+    let hooks = ItemHooks::load_dir(dir.path(), ItemHookLimits::default()).unwrap();
+    let app = create_app_with_state(state.with_item_hooks(hooks));
+
+    let (_, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Order:" }))).await;
+    assert_eq!(item["name"], "Order: a b");
+
+    std::fs::write(dir.path().join("c.rhai"), "fn on_create(item) {").unwrap();
+    assert!(ItemHooks::load_dir(dir.path(), ItemHookLimits::default()).is_err());
+}
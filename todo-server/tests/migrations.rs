@@ -0,0 +1,49 @@
+mod common;
+
+use todo_server::create_app;
+use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::SqlitePool;
+
+use common::request;
+
+/// A database with only the original items table, holding rows inserted in
+/// an order their random ids don't share.
+async fn legacy_pool() -> SqlitePool {
+    // One connection for good, as every new one would see an empty database
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .idle_timeout(None)
+        .max_lifetime(None)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    let mut migrator = sqlx::migrate!("./migrations");
+    migrator.migrations = migrator.migrations[..1].to_vec().into();
+    migrator.run(&pool).await.unwrap();
+
+    for (id, name) in [("c", "first"), ("a", "second"), ("b", "third")] {
+        sqlx::query("INSERT INTO items (id, name, description) VALUES (?, ?, '')")
+            .bind(id)
+            .bind(name)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+
+    pool
+}
+
+async fn list_names(pool: &SqlitePool, uri: &str) -> Vec<String> {
+    let (_, page) = request(&create_app(pool.clone()), "GET", uri, None).await;
+
+    page["items"].as_array().unwrap().iter().map(|item| item["name"].as_str().unwrap().to_string()).collect()
+}
+
+#[tokio::test]
+async fn legacy_items_keep_their_insertion_order() {
+    let pool = legacy_pool().await;
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    assert_eq!(list_names(&pool, "/api/v1/items").await, ["first", "second", "third"]);
+    assert_eq!(list_names(&pool, "/api/v1/items?sort=position").await, ["first", "second", "third"]);
+}
@@ -0,0 +1,194 @@
+mod common;
+
+use axum::http::StatusCode;
+use axum::Router;
+use serde_json::{json, Value};
+
+use common::{request, setup};
+
+async fn create(app: &Router, name: &str, priority: &str) -> String {
+    let (status, item) = request(
+        app,
+        "POST",
+        "/api/v1/items",
+        Some(json!({ "name": name, "description": "", "priority": priority })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    item["id"].as_str().unwrap().to_string()
+}
+
+async fn list_names(app: &Router, uri: &str) -> (Vec<String>, Value) {
+    let (status, page) = request(app, "GET", uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let names = page["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["name"].as_str().unwrap().to_string())
+        .collect();
+
+    (names, page["next_cursor"].clone())
+}
+
+#[tokio::test]
+async fn new_items_are_appended_with_medium_priority_by_default() {
+    let app = setup().await;
+
+    create(&app, "a", "high").await;
+    let (_, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "b", "description": "" }))).await;
+
+    assert_eq!(item["priority"], "medium");
+    assert_eq!(item["position"], 1);
+}
+
+#[tokio::test]
+async fn unknown_priority_is_rejected() {
+    let app = setup().await;
+
+    let (status, _) = request(
+        &app,
+        "POST",
+        "/api/v1/items",
+        Some(json!({ "name": "a", "description": "", "priority": "urgent" })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn priority_can_be_updated() {
+    let app = setup().await;
+    let id = create(&app, "a", "low").await;
+
+    let (status, _) = request(&app, "PUT", &format!("/api/v1/items/{id}"), Some(json!({ "priority": "high" }))).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (_, item) = request(&app, "GET", &format!("/api/v1/items/{id}"), None).await;
+    assert_eq!(item["priority"], "high");
+}
+
+#[tokio::test]
+async fn sort_by_priority_pages_most_urgent_first() {
+    let app = setup().await;
+
+    create(&app, "a", "low").await;
+    create(&app, "b", "high").await;
+    create(&app, "c", "medium").await;
+    create(&app, "d", "high").await;
+
+    let (names, cursor) = list_names(&app, "/api/v1/items?sort=priority&limit=3").await;
+    assert_eq!(names, ["b", "d", "c"]);
+
+    let (names, cursor) = list_names(
+        &app,
+        &format!("/api/v1/items?sort=priority&limit=3&cursor={}", cursor.as_str().unwrap()),
+    )
+    .await;
+    assert_eq!(names, ["a"]);
+    assert!(cursor.is_null());
+}
+
+#[tokio::test]
+async fn cursor_from_another_sort_is_rejected() {
+    let app = setup().await;
+
+    create(&app, "a", "low").await;
+    create(&app, "b", "low").await;
+
+    let (_, cursor) = list_names(&app, "/api/v1/items?limit=1").await;
+    let (status, _) = request(
+        &app,
+        "GET",
+        &format!("/api/v1/items?sort=priority&cursor={}", cursor.as_str().unwrap()),
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn unknown_sort_is_bad_request() {
+    let app = setup().await;
+
+    let (status, _) = request(&app, "GET", "/api/v1/items?sort=name", None).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn move_reorders_siblings() {
+    let app = setup().await;
+
+    create(&app, "a", "medium").await;
+    create(&app, "b", "medium").await;
+    create(&app, "c", "medium").await;
+    let d = create(&app, "d", "medium").await;
+
+    let (status, item) = request(&app, "PATCH", &format!("/api/v1/items/{d}/move"), Some(json!({ "position": 1 }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(item["position"], 1);
+
+    let (names, _) = list_names(&app, "/api/v1/items?sort=position").await;
+    assert_eq!(names, ["a", "d", "b", "c"]);
+
+    let (_, page) = request(&app, "GET", "/api/v1/items?sort=position", None).await;
+    let positions: Vec<i64> = page["items"].as_array().unwrap().iter().map(|item| item["position"].as_i64().unwrap()).collect();
+    assert_eq!(positions, [0, 1, 2, 3]);
+}
+
+#[tokio::test]
+async fn move_past_the_end_moves_to_the_end() {
+    let app = setup().await;
+
+    let a = create(&app, "a", "medium").await;
+    create(&app, "b", "medium").await;
+    create(&app, "c", "medium").await;
+
+    let (status, item) = request(&app, "PATCH", &format!("/api/v1/items/{a}/move"), Some(json!({ "position": 99 }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(item["position"], 2);
+
+    let (names, _) = list_names(&app, "/api/v1/items?sort=position").await;
+    assert_eq!(names, ["b", "c", "a"]);
+}
+
+#[tokio::test]
+async fn move_counts_places_across_the_gaps_left_by_deletes() {
+    let app = setup().await;
+
+    create(&app, "a", "medium").await;
+    let b = create(&app, "b", "medium").await;
+    create(&app, "c", "medium").await;
+    let d = create(&app, "d", "medium").await;
+    request(&app, "DELETE", &format!("/api/v1/items/{b}"), None).await;
+
+    let (status, _) = request(&app, "PATCH", &format!("/api/v1/items/{d}/move"), Some(json!({ "position": 1 }))).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (names, _) = list_names(&app, "/api/v1/items?sort=position").await;
+    assert_eq!(names, ["a", "d", "c"]);
+}
+
+#[tokio::test]
+async fn move_rejects_negative_position() {
+    let app = setup().await;
+    let a = create(&app, "a", "medium").await;
+
+    let (status, _) = request(&app, "PATCH", &format!("/api/v1/items/{a}/move"), Some(json!({ "position": -1 }))).await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn move_unknown_item_is_not_found() {
+    let app = setup().await;
+
+    let (status, _) = request(&app, "PATCH", "/api/v1/items/missing/move", Some(json!({ "position": 0 }))).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use axum::http::StatusCode;
+use axum::Router;
+use todo_server::create_app_with_state;
+use chrono::Duration;
+use serde_json::{json, Value};
+
+use common::{request, setup, setup_state};
+
+async fn list_names(app: &Router, uri: &str) -> (Vec<String>, Value) {
+    let (status, page) = request(app, "GET", uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let names = page["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["name"].as_str().unwrap().to_string())
+        .collect();
+
+    (names, page["next_cursor"].clone())
+}
+
+#[tokio::test]
+async fn cursor_walks_items_in_creation_order() {
+    let (state, clock) = setup_state().await;
+    let app = create_app_with_state(state);
+
+    for name in ["a", "b", "c", "d", "e"] {
+        request(&app, "POST", "/api/v1/items", Some(json!({ "name": name, "description": "" }))).await;
+        clock.advance(Duration::seconds(1));
+    }
+
+    let (names, cursor) = list_names(&app, "/api/v1/items?limit=2").await;
+    assert_eq!(names, ["a", "b"]);
+
+    let (names, cursor) = list_names(&app, &format!("/api/v1/items?limit=2&cursor={}", cursor.as_str().unwrap())).await;
+    assert_eq!(names, ["c", "d"]);
+
+    let (names, cursor) = list_names(&app, &format!("/api/v1/items?limit=2&cursor={}", cursor.as_str().unwrap())).await;
+    assert_eq!(names, ["e"]);
+    assert!(cursor.is_null());
+}
+
+#[tokio::test]
+async fn items_created_at_the_same_instant_are_ordered_by_id() {
+    let app = setup().await;
+
+    for name in ["a", "b", "c"] {
+        request(&app, "POST", "/api/v1/items", Some(json!({ "name": name, "description": "" }))).await;
+    }
+
+    let (names, cursor) = list_names(&app, "/api/v1/items?limit=2").await;
+    assert_eq!(names, ["a", "b"]);
+
+    let (names, cursor) = list_names(&app, &format!("/api/v1/items?limit=2&cursor={}", cursor.as_str().unwrap())).await;
+    assert_eq!(names, ["c"]);
+    assert!(cursor.is_null());
+}
+
+#[tokio::test]
+async fn exact_last_page_has_no_next_cursor() {
+    let app = setup().await;
+
+    for name in ["a", "b"] {
+        request(&app, "POST", "/api/v1/items", Some(json!({ "name": name, "description": "" }))).await;
+    }
+
+    let (names, cursor) = list_names(&app, "/api/v1/items?limit=2").await;
+    assert_eq!(names, ["a", "b"]);
+    assert!(cursor.is_null());
+}
+
+#[tokio::test]
+async fn malformed_cursor_is_rejected() {
+    let app = setup().await;
+
+    let (status, _) = request(&app, "GET", "/api/v1/items?cursor=not-a-cursor", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn out_of_range_limit_is_rejected() {
+    let app = setup().await;
+    let (_, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "a" }))).await;
+    let item_id = item["id"].as_str().unwrap();
+
+    for uri in [
+        "/api/v1/items?limit=0".to_string(),
+        "/api/v1/items?limit=201".to_string(),
+        format!("/api/v1/items/{item_id}/comments?limit=201"),
+    ] {
+        let (status, body) = request(&app, "GET", &uri, None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{uri}");
+        assert_eq!(body["code"], "bad_request");
+        assert_eq!(body["message"], "limit must be between 1 and 200");
+    }
+
+    let (status, body) = request(&app, "GET", "/api/v1/items?limit=many", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], "bad_request");
+
+    let (status, _) = request(&app, "GET", "/api/v1/items?limit=200", None).await;
+    assert_eq!(status, StatusCode::OK);
+}
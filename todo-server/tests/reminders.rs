@@ -0,0 +1,262 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::routing::post;
+use axum::{Json, Router};
+use todo_server::clock::FixedClock;
+use todo_server::config::AppConfig;
+use todo_server::create_app_with_state;
+use todo_server::modules::reminders::reminder_webhook::{self, WebhookPolicy};
+use todo_server::modules::reminders::reminder_worker::{deliver_due_reminders, ReminderWorkerConfig};
+use todo_server::state::AppState;
+use chrono::Duration;
+use reqwest::Client;
+use serde_json::{json, Value};
+
+use common::{request, setup, setup_state, start_time};
+
+type Received = Arc<Mutex<Vec<Value>>>;
+
+/// Starts a local webhook receiver that answers every POST with `status`.
+async fn spawn_receiver(status: StatusCode) -> (String, Received) {
+    let received = Received::default();
+    let app = Router::new()
+        .route("/hook", post(move |State(received): State<Received>, Json(body): Json<Value>| async move {
+            received.lock().unwrap().push(body);
+            status
+        }))
+        .with_state(received.clone());
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    (format!("http://{addr}/hook"), received)
+}
+
+/// State whose webhooks may reach the local receivers.
+async fn setup_receiver_state() -> (AppState, Arc<FixedClock>) {
+    let (state, clock) = setup_state().await;
+    let config = AppConfig { webhook_private_hosts: vec!["127.0.0.1".to_string()], ..AppConfig::default() };
+
+    (state.with_config(config), clock)
+}
+
+#[tokio::test]
+async fn due_reminder_is_delivered_once() {
+    let (state, _) = setup_receiver_state().await;
+    let app = create_app_with_state(state.clone());
+    let (webhook_url, received) = spawn_receiver(StatusCode::OK).await;
+
+    let (_, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Call mum", "description": "Sunday" }))).await;
+    let id = item["id"].as_str().unwrap();
+    let (status, reminder) = request(&app, "POST", &format!("/api/v1/items/{id}/reminders"), Some(json!({
+        "remind_at": start_time() - Duration::minutes(1),
+        "webhook_url": webhook_url,
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let config = ReminderWorkerConfig::default();
+    let delivered = deliver_due_reminders(&state, &Client::new(), &config).await.unwrap();
+    assert_eq!(delivered, 1);
+
+    let payloads = received.lock().unwrap().clone();
+    assert_eq!(payloads.len(), 1);
+    assert_eq!(payloads[0]["reminder_id"], reminder["id"]);
+    assert_eq!(payloads[0]["item_id"], id);
+    assert_eq!(payloads[0]["item_name"], "Call mum");
+
+    let (_, reminders) = request(&app, "GET", &format!("/api/v1/items/{id}/reminders"), None).await;
+    assert_eq!(reminders[0]["attempts"], 1);
+    assert!(!reminders[0]["delivered_at"].is_null());
+
+    let delivered = deliver_due_reminders(&state, &Client::new(), &config).await.unwrap();
+    assert_eq!(delivered, 0);
+    assert_eq!(received.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn failed_delivery_is_rescheduled_with_backoff() {
+    let (state, clock) = setup_receiver_state().await;
+    let app = create_app_with_state(state.clone());
+    let (webhook_url, received) = spawn_receiver(StatusCode::SERVICE_UNAVAILABLE).await;
+
+    let (_, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Call mum", "description": "" }))).await;
+    let id = item["id"].as_str().unwrap();
+    request(&app, "POST", &format!("/api/v1/items/{id}/reminders"), Some(json!({
+        "remind_at": start_time() - Duration::minutes(1),
+        "webhook_url": webhook_url,
+    }))).await;
+
+    let config = ReminderWorkerConfig::default();
+    let delivered = deliver_due_reminders(&state, &Client::new(), &config).await.unwrap();
+    assert_eq!(delivered, 0);
+    assert_eq!(received.lock().unwrap().len(), 1);
+
+    let (_, reminders) = request(&app, "GET", &format!("/api/v1/items/{id}/reminders"), None).await;
+    assert_eq!(reminders[0]["attempts"], 1);
+    assert!(reminders[0]["delivered_at"].is_null());
+    assert!(reminders[0]["last_error"].as_str().unwrap().contains("503"));
+    assert!(!reminders[0]["last_error"].as_str().unwrap().contains(&webhook_url));
+
+    // The retry is scheduled after the backoff, so an immediate poll skips it.
+    deliver_due_reminders(&state, &Client::new(), &config).await.unwrap();
+    assert_eq!(received.lock().unwrap().len(), 1);
+
+    clock.advance(Duration::from_std(config.base_backoff).unwrap());
+    deliver_due_reminders(&state, &Client::new(), &config).await.unwrap();
+    assert_eq!(received.lock().unwrap().len(), 2);
+
+    let (_, reminders) = request(&app, "GET", &format!("/api/v1/items/{id}/reminders"), None).await;
+    assert_eq!(reminders[0]["attempts"], 2);
+    assert_eq!(reminders[0]["next_attempt_at"], json!(start_time() + Duration::seconds(30 + 60)));
+}
+
+#[tokio::test]
+async fn future_reminder_is_not_delivered_yet() {
+    let (state, clock) = setup_receiver_state().await;
+    let app = create_app_with_state(state.clone());
+    let (webhook_url, received) = spawn_receiver(StatusCode::OK).await;
+
+    let (_, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Call mum", "description": "" }))).await;
+    let id = item["id"].as_str().unwrap();
+    request(&app, "POST", &format!("/api/v1/items/{id}/reminders"), Some(json!({
+        "remind_at": start_time() + Duration::hours(1),
+        "webhook_url": webhook_url,
+    }))).await;
+
+    let config = ReminderWorkerConfig::default();
+    let delivered = deliver_due_reminders(&state, &Client::new(), &config).await.unwrap();
+    assert_eq!(delivered, 0);
+    assert!(received.lock().unwrap().is_empty());
+
+    clock.advance(Duration::hours(1));
+    let delivered = deliver_due_reminders(&state, &Client::new(), &config).await.unwrap();
+    assert_eq!(delivered, 1);
+}
+
+#[tokio::test]
+async fn reminder_for_unknown_item_returns_not_found() {
+    let app = setup().await;
+
+    let (status, _) = request(&app, "POST", "/api/v1/items/does-not-exist/reminders", Some(json!({
+        "remind_at": start_time(),
+        "webhook_url": "https://example.com/hook",
+    }))).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn reminder_with_invalid_webhook_url_is_rejected() {
+    let app = setup().await;
+
+    let (_, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Call mum", "description": "" }))).await;
+    let id = item["id"].as_str().unwrap();
+
+    for webhook_url in ["not a url", "ftp://example.com/hook"] {
+        let (status, _) = request(&app, "POST", &format!("/api/v1/items/{id}/reminders"), Some(json!({
+            "remind_at": start_time(),
+            "webhook_url": webhook_url,
+        }))).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
+
+#[tokio::test]
+async fn reminder_with_private_webhook_is_rejected() {
+    let app = setup().await;
+
+    let (_, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Call mum", "description": "" }))).await;
+    let id = item["id"].as_str().unwrap();
+
+    for webhook_url in [
+        "http://127.0.0.1:8080/hook",
+        "http://localhost/hook",
+        "http://10.0.0.7/hook",
+        "http://192.168.1.1/hook",
+        "http://169.254.169.254/latest/meta-data",
+        "http://[::1]/hook",
+        "http://[::ffff:127.0.0.1]/hook",
+    ] {
+        let (status, body) = request(&app, "POST", &format!("/api/v1/items/{id}/reminders"), Some(json!({
+            "remind_at": start_time(),
+            "webhook_url": webhook_url,
+        }))).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{webhook_url}");
+        assert!(body["message"].as_str().unwrap().contains("private"), "{webhook_url}: {body}");
+    }
+}
+
+#[tokio::test]
+async fn delivery_to_a_private_address_is_refused() {
+    let (state, _) = setup_receiver_state().await;
+    let app = create_app_with_state(state.clone());
+    let (webhook_url, received) = spawn_receiver(StatusCode::OK).await;
+
+    let (_, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Call mum", "description": "" }))).await;
+    let id = item["id"].as_str().unwrap();
+    request(&app, "POST", &format!("/api/v1/items/{id}/reminders"), Some(json!({
+        "remind_at": start_time() - Duration::minutes(1),
+        "webhook_url": webhook_url,
+    }))).await;
+
+    // The host is no longer allowed by the time the reminder is due
+    let state = state.with_config(AppConfig::default());
+    let delivered = deliver_due_reminders(&state, &Client::new(), &ReminderWorkerConfig::default()).await.unwrap();
+    assert_eq!(delivered, 0);
+    assert!(received.lock().unwrap().is_empty());
+
+    let (_, reminders) = request(&app, "GET", &format!("/api/v1/items/{id}/reminders"), None).await;
+    assert!(reminders[0]["last_error"].as_str().unwrap().contains("private"));
+}
+
+#[tokio::test]
+async fn webhook_redirects_are_not_followed() {
+    let (state, _) = setup_receiver_state().await;
+    let app = create_app_with_state(state.clone());
+    let (target_url, received) = spawn_receiver(StatusCode::OK).await;
+
+    let redirect = Router::new().route("/hook", post(move || async move {
+        (StatusCode::TEMPORARY_REDIRECT, [(header::LOCATION, target_url)])
+    }));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, redirect).await.unwrap() });
+
+    let (_, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Call mum", "description": "" }))).await;
+    let id = item["id"].as_str().unwrap();
+    request(&app, "POST", &format!("/api/v1/items/{id}/reminders"), Some(json!({
+        "remind_at": start_time() - Duration::minutes(1),
+        "webhook_url": format!("http://{addr}/hook"),
+    }))).await;
+
+    let config = ReminderWorkerConfig::default();
+    let client = reminder_webhook::client(WebhookPolicy::from_config(&state.config), config.request_timeout);
+    let delivered = deliver_due_reminders(&state, &client, &config).await.unwrap();
+    assert_eq!(delivered, 0);
+    assert!(received.lock().unwrap().is_empty());
+
+    let (_, reminders) = request(&app, "GET", &format!("/api/v1/items/{id}/reminders"), None).await;
+    assert!(reminders[0]["last_error"].as_str().unwrap().contains("307"));
+}
+
+#[tokio::test]
+async fn deleting_item_removes_its_reminders() {
+    let (state, _) = setup_state().await;
+    let app = create_app_with_state(state.clone());
+
+    let (_, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Call mum", "description": "" }))).await;
+    let id = item["id"].as_str().unwrap();
+    request(&app, "POST", &format!("/api/v1/items/{id}/reminders"), Some(json!({
+        "remind_at": start_time(),
+        "webhook_url": "https://example.com/hook",
+    }))).await;
+
+    request(&app, "DELETE", &format!("/api/v1/items/{id}"), None).await;
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM reminders").fetch_one(&state.pool).await.unwrap();
+    assert_eq!(count, 0);
+}
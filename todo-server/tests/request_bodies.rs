@@ -0,0 +1,84 @@
+mod common;
+
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use todo_server::config::AppConfig;
+use todo_server::create_app_with_state;
+use serde_json::json;
+
+use common::{send, setup, setup_state};
+
+fn post_items(content_type: Option<&str>, body: impl Into<Body>) -> Request<Body> {
+    let mut builder = Request::builder().method("POST").uri("/api/v1/items");
+    if let Some(content_type) = content_type {
+        builder = builder.header(header::CONTENT_TYPE, content_type);
+    }
+
+    builder.body(body.into()).unwrap()
+}
+
+#[tokio::test]
+async fn body_over_the_limit_is_rejected() {
+    let (state, _) = setup_state().await;
+    let app = create_app_with_state(state.with_config(AppConfig { max_body_bytes: 64, ..AppConfig::default() }));
+
+    let body = json!({ "name": "x".repeat(100), "description": "" }).to_string();
+    let (status, error) = send(&app, post_items(Some("application/json"), body)).await;
+
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(error["code"], "payload_too_large");
+}
+
+#[tokio::test]
+async fn body_under_the_limit_is_accepted() {
+    let (state, _) = setup_state().await;
+    let app = create_app_with_state(state.with_config(AppConfig { max_body_bytes: 64, ..AppConfig::default() }));
+
+    let body = json!({ "name": "x", "description": "" }).to_string();
+    let (status, _) = send(&app, post_items(Some("application/json"), body)).await;
+
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn wrong_content_type_is_unsupported_media_type() {
+    let app = setup().await;
+
+    let (status, error) = send(&app, post_items(Some("text/plain"), "{}")).await;
+
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_eq!(error["code"], "unsupported_media_type");
+    assert_eq!(error["retryable"], false);
+}
+
+#[tokio::test]
+async fn syntax_error_is_bad_request() {
+    let app = setup().await;
+
+    let (status, error) = send(&app, post_items(Some("application/json"), "{\"name\": ")).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["code"], "bad_request");
+    assert!(error["message"].as_str().unwrap().contains("EOF while parsing"));
+}
+
+#[tokio::test]
+async fn missing_field_is_unprocessable() {
+    let app = setup().await;
+
+    let (status, error) = send(&app, post_items(Some("application/json"), "{\"description\": \"x\"}")).await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(error["code"], "unprocessable");
+    assert!(error["message"].as_str().unwrap().contains("missing field `name`"));
+}
+
+#[tokio::test]
+async fn wrong_field_type_is_unprocessable() {
+    let app = setup().await;
+
+    let (status, error) = send(&app, post_items(Some("application/json"), "{\"name\": 1, \"description\": \"\"}")).await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(error["code"], "unprocessable");
+}
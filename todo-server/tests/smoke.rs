@@ -0,0 +1,64 @@
+mod common;
+
+use axum::extract::Request;
+use axum::http::StatusCode;
+use axum::middleware::{self, Next};
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use todo_server::create_app_with_state;
+use todo_server::smoke;
+use serde_json::json;
+
+use common::{request, setup_state};
+
+#[tokio::test]
+async fn journey_passes_against_the_app_and_cleans_up() {
+    let (state, _) = setup_state().await;
+    let app = create_app_with_state(state);
+    let url = smoke::boot(app.clone()).await.unwrap();
+
+    let mut steps = Vec::new();
+    smoke::run(&format!("{url}/"), &mut |step| steps.push(step)).await.unwrap();
+    assert_eq!(steps, ["status", "create", "get", "update", "move", "list", "export", "delete", "history"]);
+
+    let (status, page) = request(&app, "GET", "/api/v1/items", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["items"], json!([]));
+}
+
+#[tokio::test]
+async fn journey_stops_at_the_first_unexpected_response() {
+    let broken = Router::new().route("/status", get(|| async { Json(json!({ "status": "ok" })) }));
+    let url = smoke::boot(broken).await.unwrap();
+
+    let mut steps = Vec::new();
+    let failure = smoke::run(&url, &mut |step| steps.push(step)).await.unwrap_err();
+    assert_eq!(steps, ["status"]);
+    assert_eq!(failure.step, "create");
+    assert!(failure.reason.contains("404"), "{}", failure.reason);
+
+    let down = Router::new().route("/status", get(|| async { Json(json!({ "status": "down" })) }));
+    let url = smoke::boot(down).await.unwrap();
+    let failure = smoke::run(&url, &mut |_| {}).await.unwrap_err();
+    assert_eq!(failure.step, "status");
+}
+
+#[tokio::test]
+async fn a_failed_journey_deletes_its_item() {
+    let (state, _) = setup_state().await;
+    let app = create_app_with_state(state);
+    let broken_export = app.clone().layer(middleware::from_fn(|request: Request, next: Next| async move {
+        if request.uri().path().ends_with("/export") {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+        next.run(request).await
+    }));
+    let url = smoke::boot(broken_export).await.unwrap();
+
+    let failure = smoke::run(&url, &mut |_| {}).await.unwrap_err();
+    assert_eq!(failure.step, "export");
+
+    let (_, page) = request(&app, "GET", "/api/v1/items", None).await;
+    assert_eq!(page["items"], json!([]));
+}
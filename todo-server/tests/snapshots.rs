@@ -0,0 +1,109 @@
+mod common;
+
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{request, setup};
+
+#[tokio::test]
+async fn create_item_response() {
+    let app = setup().await;
+
+    let (status, body) = request(&app, "POST", "/api/v1/items", Some(json!({
+        "name": "Buy milk",
+        "description": "Two litres",
+    }))).await;
+
+    assert_eq!(status, StatusCode::OK);
+    insta::assert_json_snapshot!(body);
+}
+
+#[tokio::test]
+async fn list_items_response() {
+    let app = setup().await;
+
+    request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Buy milk", "description": "Two litres" }))).await;
+    request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Walk dog", "description": "" }))).await;
+
+    let (status, body) = request(&app, "GET", "/api/v1/items", None).await;
+
+    assert_eq!(status, StatusCode::OK);
+    insta::assert_json_snapshot!(body);
+}
+
+#[tokio::test]
+async fn get_item_response() {
+    let app = setup().await;
+
+    let (_, created) = request(&app, "POST", "/api/v1/items", Some(json!({
+        "name": "Buy milk",
+        "description": "Two litres",
+    }))).await;
+    let id = created["id"].as_str().unwrap();
+
+    let (status, body) = request(&app, "GET", &format!("/api/v1/items/{id}"), None).await;
+
+    assert_eq!(status, StatusCode::OK);
+    insta::assert_json_snapshot!(body);
+}
+
+#[tokio::test]
+async fn get_missing_item_response() {
+    let app = setup().await;
+
+    let (status, body) = request(&app, "GET", "/api/v1/items/does-not-exist", None).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    insta::assert_json_snapshot!(body);
+}
+
+#[tokio::test]
+async fn update_item_response() {
+    let app = setup().await;
+
+    let (_, created) = request(&app, "POST", "/api/v1/items", Some(json!({
+        "name": "Buy milk",
+        "description": "Two litres",
+    }))).await;
+    let id = created["id"].as_str().unwrap();
+
+    let (status, body) = request(&app, "PUT", &format!("/api/v1/items/{id}"), Some(json!({ "name": "Buy oat milk" }))).await;
+
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    insta::assert_json_snapshot!(body);
+}
+
+#[tokio::test]
+async fn delete_item_response() {
+    let app = setup().await;
+
+    let (_, created) = request(&app, "POST", "/api/v1/items", Some(json!({
+        "name": "Buy milk",
+        "description": "Two litres",
+    }))).await;
+    let id = created["id"].as_str().unwrap();
+
+    let (status, body) = request(&app, "DELETE", &format!("/api/v1/items/{id}"), None).await;
+
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    insta::assert_json_snapshot!(body);
+}
+
+#[tokio::test]
+async fn create_reminder_response() {
+    let app = setup().await;
+
+    let (_, created) = request(&app, "POST", "/api/v1/items", Some(json!({
+        "name": "Buy milk",
+        "description": "Two litres",
+    }))).await;
+    let id = created["id"].as_str().unwrap();
+
+    let (status, body) = request(&app, "POST", &format!("/api/v1/items/{id}/reminders"), Some(json!({
+        "remind_at": "2024-06-24T09:00:00Z",
+        "webhook_url": "https://example.com/hook",
+    }))).await;
+
+    assert_eq!(status, StatusCode::OK);
+    insta::assert_json_snapshot!(body);
+}
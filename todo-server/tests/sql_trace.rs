@@ -0,0 +1,99 @@
+mod common;
+
+use std::io;
+use std::sync::{Arc, Mutex};
+
+use axum::http::StatusCode;
+use serde_json::json;
+use tracing::Level;
+use tracing_subscriber::fmt::MakeWriter;
+
+use common::{request, setup};
+
+/// Collects everything the subscriber writes.
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+
+impl Captured {
+    fn contents(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+impl io::Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for Captured {
+    type Writer = Captured;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+fn subscriber(captured: &Captured, level: Level) -> impl tracing::Subscriber {
+    tracing_subscriber::fmt()
+        .with_writer(captured.clone())
+        .with_max_level(level)
+        .with_ansi(false)
+        .finish()
+}
+
+#[tokio::test]
+async fn statements_are_logged_with_params_and_row_counts() {
+    let app = setup().await;
+    let captured = Captured::default();
+    let _guard = tracing::subscriber::set_default(subscriber(&captured, Level::DEBUG));
+
+    let (_, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Traced", "description": "" }))).await;
+    let id = item["id"].as_str().unwrap();
+    request(&app, "GET", &format!("/api/v1/items/{id}"), None).await;
+
+    let logs = captured.contents();
+    assert!(logs.contains("statement=\"INSERT INTO items"));
+    assert!(logs.contains("\\\"Traced\\\""));
+    assert!(logs.contains("statement=\"SELECT id, name, NULLIF(description, '') AS description, created_at, updated_at, priority, position, parent_id FROM items WHERE id = ?\""));
+    assert!(logs.contains("rows=1"));
+    assert!(logs.contains("elapsed_ms="));
+    // Statements are logged inside the request span.
+    assert!(logs.contains("request{method=GET uri=/api/v1/items/"));
+}
+
+#[tokio::test]
+async fn sensitive_params_are_redacted() {
+    let app = setup().await;
+    let captured = Captured::default();
+    let _guard = tracing::subscriber::set_default(subscriber(&captured, Level::DEBUG));
+
+    let (_, item) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Traced", "description": "" }))).await;
+    let id = item["id"].as_str().unwrap();
+    let (status, _) = request(&app, "POST", &format!("/api/v1/items/{id}/reminders"), Some(json!({
+        "remind_at": "2024-06-24T09:00:00Z",
+        "webhook_url": "https://example.com/hook?token=secret",
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let logs = captured.contents();
+    assert!(logs.contains("statement=\"INSERT INTO reminders"));
+    assert!(logs.contains("[redacted]"));
+    assert!(!logs.contains("token=secret"));
+}
+
+#[tokio::test]
+async fn nothing_is_logged_unless_sql_debug_is_enabled() {
+    let app = setup().await;
+    let captured = Captured::default();
+    let _guard = tracing::subscriber::set_default(subscriber(&captured, Level::INFO));
+
+    request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Traced", "description": "" }))).await;
+
+    assert!(!captured.contents().contains("statement="));
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use todo_server::create_app_with_state;
+use todo_server::modules::status::status_service::UNAVAILABLE;
+use chrono::TimeDelta;
+use serde_json::json;
+
+use common::{request, send, setup_state};
+
+#[tokio::test]
+async fn status_reports_uptime_and_healthy_dependencies() {
+    let (state, clock) = setup_state().await;
+    let app = create_app_with_state(state);
+
+    request(&app, "POST", "/api/v1/items", Some(json!({ "name": "Counted" }))).await;
+    clock.advance(TimeDelta::seconds(90));
+
+    let (status, body) = request(&app, "GET", "/status", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ok");
+    assert_eq!(body["started_at"], "2024-06-24T08:00:00Z");
+    assert_eq!(body["uptime_seconds"], 90);
+    assert_eq!(body["window_minutes"], 60);
+    assert_eq!(body["requests"], 1);
+    assert_eq!(body["server_errors"], 0);
+    assert_eq!(body["error_rate"], 0.0);
+
+    let dependencies = body["dependencies"].as_array().unwrap();
+    assert_eq!(dependencies.len(), 2);
+    assert_eq!(dependencies[0]["name"], "database");
+    assert_eq!(dependencies[1]["name"], "attachment_storage");
+    assert!(dependencies.iter().all(|dependency| dependency["healthy"] == true));
+}
+
+#[tokio::test]
+async fn status_has_no_error_rate_before_the_first_request() {
+    let (state, _) = setup_state().await;
+    let app = create_app_with_state(state);
+
+    let (_, body) = request(&app, "GET", "/status", None).await;
+    assert_eq!(body["requests"], 0);
+    assert_eq!(body["error_rate"], serde_json::Value::Null);
+}
+
+#[tokio::test]
+async fn status_only_counts_requests_inside_the_window() {
+    let (state, clock) = setup_state().await;
+    let app = create_app_with_state(state);
+
+    request(&app, "GET", "/api/v1/items", None).await;
+    clock.advance(TimeDelta::minutes(30));
+    request(&app, "GET", "/api/v1/items", None).await;
+
+    let (_, body) = request(&app, "GET", "/status", None).await;
+    assert_eq!(body["requests"], 2);
+
+    clock.advance(TimeDelta::minutes(31));
+    let (_, body) = request(&app, "GET", "/status", None).await;
+    // The status request from before and the second list are still in.
+    assert_eq!(body["requests"], 2);
+}
+
+#[tokio::test]
+async fn status_is_down_when_the_database_is_unreachable() {
+    let (state, _) = setup_state().await;
+    let app = create_app_with_state(state.clone());
+
+    state.pool.close().await;
+    let (status, _) = request(&app, "GET", "/api/v1/items", None).await;
+    assert!(status.is_server_error());
+
+    let (status, body) = request(&app, "GET", "/status", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "down");
+    assert_eq!(body["server_errors"], 1);
+    assert_eq!(body["error_rate"], 1.0);
+    assert_eq!(body["dependencies"][0]["healthy"], false);
+    assert_eq!(body["dependencies"][0]["error"], UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn status_renders_html_for_browsers() {
+    let (state, _) = setup_state().await;
+    let app = create_app_with_state(state);
+
+    let request = Request::builder()
+        .uri("/status")
+        .header(header::ACCEPT, "text/html,application/xhtml+xml")
+        .body(Body::empty())
+        .unwrap();
+    let (status, body) = send(&app, request).await;
+
+    assert_eq!(status, StatusCode::OK);
+    let html = body.as_str().unwrap();
+    assert!(html.contains("<h1>All systems operational</h1>"));
+    assert!(html.contains("<td>database</td><td>healthy</td>"));
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use axum::http::StatusCode;
+use axum::Router;
+use serde_json::{json, Value};
+
+use common::{request, setup};
+
+async fn create(app: &Router, name: &str, parent_id: Option<&str>) -> String {
+    let (status, item) = request(app, "POST", "/api/v1/items", Some(json!({ "name": name, "parent_id": parent_id }))).await;
+    assert_eq!(status, StatusCode::OK);
+
+    item["id"].as_str().unwrap().to_string()
+}
+
+fn names(items: &Value) -> Vec<&str> {
+    items.as_array().unwrap().iter().map(|item| item["name"].as_str().unwrap()).collect()
+}
+
+#[tokio::test]
+async fn subtasks_are_listed_under_their_parent() {
+    let app = setup().await;
+    let parent = create(&app, "Plan trip", None).await;
+    create(&app, "Book flights", Some(&parent)).await;
+    create(&app, "Book hotel", Some(&parent)).await;
+    create(&app, "Unrelated", None).await;
+
+    let (status, subtasks) = request(&app, "GET", &format!("/api/v1/items/{parent}/subtasks"), None).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(names(&subtasks), ["Book flights", "Book hotel"]);
+    assert_eq!(subtasks[0]["parent_id"], parent.as_str());
+}
+
+#[tokio::test]
+async fn subtasks_of_unknown_item_is_not_found() {
+    let app = setup().await;
+
+    let (status, _) = request(&app, "GET", "/api/v1/items/missing/subtasks", None).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn unknown_parent_is_rejected() {
+    let app = setup().await;
+
+    let (status, _) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "a", "parent_id": "missing" }))).await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn reparenting_under_a_descendant_is_rejected() {
+    let app = setup().await;
+    let a = create(&app, "a", None).await;
+    let b = create(&app, "b", Some(&a)).await;
+    let c = create(&app, "c", Some(&b)).await;
+
+    let (status, error) = request(&app, "PATCH", &format!("/api/v1/items/{a}"), Some(json!({ "parent_id": c }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(error["message"].as_str().unwrap().contains("own subtasks"));
+
+    let (status, _) = request(&app, "PATCH", &format!("/api/v1/items/{a}"), Some(json!({ "parent_id": a }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    // Detaching and moving elsewhere is fine.
+    let (status, _) = request(&app, "PATCH", &format!("/api/v1/items/{c}"), Some(json!({ "parent_id": null }))).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = request(&app, "PATCH", &format!("/api/v1/items/{a}"), Some(json!({ "parent_id": c }))).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn tree_listing_nests_subtasks_under_top_level_items() {
+    let app = setup().await;
+    let a = create(&app, "a", None).await;
+    let b = create(&app, "b", Some(&a)).await;
+    create(&app, "c", Some(&b)).await;
+    create(&app, "d", None).await;
+
+    let (status, page) = request(&app, "GET", "/api/v1/items?tree=true&sort=position&limit=1", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(names(&page["items"]), ["a"]);
+    assert_eq!(names(&page["items"][0]["subtasks"]), ["b"]);
+    assert_eq!(names(&page["items"][0]["subtasks"][0]["subtasks"]), ["c"]);
+
+    let cursor = page["next_cursor"].as_str().unwrap();
+    let (_, page) = request(&app, "GET", &format!("/api/v1/items?tree=true&sort=position&limit=1&cursor={cursor}"), None).await;
+    assert_eq!(names(&page["items"]), ["d"]);
+    assert_eq!(page["items"][0]["subtasks"], json!([]));
+    assert!(page["next_cursor"].is_null());
+
+    let (_, flat) = request(&app, "GET", "/api/v1/items", None).await;
+    assert_eq!(flat["items"].as_array().unwrap().len(), 4);
+}
+
+#[tokio::test]
+async fn deleting_a_parent_deletes_its_subtasks() {
+    let app = setup().await;
+    let a = create(&app, "a", None).await;
+    let b = create(&app, "b", Some(&a)).await;
+
+    request(&app, "GET", &format!("/api/v1/items/{b}"), None).await;
+    let (status, _) = request(&app, "DELETE", &format!("/api/v1/items/{a}"), None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, _) = request(&app, "GET", &format!("/api/v1/items/{b}"), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,149 @@
+mod common;
+
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use serde_json::json;
+
+use common::{request, send, setup};
+
+#[tokio::test]
+async fn crud_lifecycle() {
+    let app = setup().await;
+
+    let (status, created) = request(&app, "POST", "/api/v1/items", Some(json!({
+        "name": "Buy milk",
+        "description": "Two litres",
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(created["name"], "Buy milk");
+    assert_eq!(created["description"], "Two litres");
+    let id = created["id"].as_str().unwrap().to_string();
+
+    let (status, items) = request(&app, "GET", "/api/v1/items", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(items, json!({ "items": [created], "next_cursor": null }));
+
+    let (status, item) = request(&app, "GET", &format!("/api/v1/items/{id}"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(item, created);
+
+    let (status, _) = request(&app, "PUT", &format!("/api/v1/items/{id}"), Some(json!({
+        "name": "Buy oat milk",
+    }))).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (_, item) = request(&app, "GET", &format!("/api/v1/items/{id}"), None).await;
+    assert_eq!(item["name"], "Buy oat milk");
+    assert_eq!(item["description"], "Two litres");
+
+    let (status, _) = request(&app, "DELETE", &format!("/api/v1/items/{id}"), None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, _) = request(&app, "GET", &format!("/api/v1/items/{id}"), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (_, items) = request(&app, "GET", "/api/v1/items", None).await;
+    assert_eq!(items, json!({ "items": [], "next_cursor": null }));
+}
+
+#[tokio::test]
+async fn list_returns_every_item() {
+    let app = setup().await;
+
+    for name in ["first", "second", "third"] {
+        request(&app, "POST", "/api/v1/items", Some(json!({ "name": name, "description": "" }))).await;
+    }
+
+    let (status, items) = request(&app, "GET", "/api/v1/items", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(items["items"].as_array().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn get_unknown_item_returns_not_found() {
+    let app = setup().await;
+
+    let (status, _) = request(&app, "GET", "/api/v1/items/does-not-exist", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn create_with_missing_field_is_rejected() {
+    let app = setup().await;
+
+    let (status, _) = request(&app, "POST", "/api/v1/items", Some(json!({ "description": "No name" }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn description_is_optional() {
+    let app = setup().await;
+
+    let (status, created) = request(&app, "POST", "/api/v1/items", Some(json!({ "name": "No description" }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(created["description"], json!(null));
+
+    let (_, item) = request(&app, "GET", &format!("/api/v1/items/{}", created["id"].as_str().unwrap()), None).await;
+    assert_eq!(item, created);
+}
+
+#[tokio::test]
+async fn patch_distinguishes_null_from_missing() {
+    let app = setup().await;
+    let (_, created) = request(&app, "POST", "/api/v1/items", Some(json!({
+        "name": "Buy milk",
+        "description": "Two litres",
+    }))).await;
+    let uri = format!("/api/v1/items/{}", created["id"].as_str().unwrap());
+
+    let (status, _) = request(&app, "PATCH", &uri, Some(json!({ "name": "Buy oat milk" }))).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (_, item) = request(&app, "GET", &uri, None).await;
+    assert_eq!(item["description"], "Two litres");
+
+    let (status, _) = request(&app, "PATCH", &uri, Some(json!({ "description": null }))).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (_, item) = request(&app, "GET", &uri, None).await;
+    assert_eq!(item["name"], "Buy oat milk");
+    assert_eq!(item["description"], json!(null));
+
+    let (status, _) = request(&app, "PATCH", &uri, Some(json!({ "description": "One litre" }))).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (_, item) = request(&app, "GET", &uri, None).await;
+    assert_eq!(item["description"], "One litre");
+}
+
+#[tokio::test]
+async fn create_with_malformed_json_is_rejected() {
+    let app = setup().await;
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/v1/items")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from("{\"name\": "))
+        .unwrap();
+    let (status, _) = send(&app, request).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn create_without_json_content_type_is_rejected() {
+    let app = setup().await;
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/v1/items")
+        .body(Body::from(json!({ "name": "a", "description": "b" }).to_string()))
+        .unwrap();
+    let (status, _) = send(&app, request).await;
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
+
+#[tokio::test]
+async fn delete_unknown_item_is_a_no_op() {
+    let app = setup().await;
+
+    let (status, _) = request(&app, "DELETE", "/api/v1/items/does-not-exist", None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+}
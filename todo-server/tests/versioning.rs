@@ -0,0 +1,69 @@
+mod common;
+
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use serde_json::json;
+use tower::ServiceExt;
+
+use common::{request, setup};
+
+#[tokio::test]
+async fn versions_endpoint_lists_v1_as_current() {
+    let app = setup().await;
+
+    let (status, body) = request(&app, "GET", "/api/versions", None).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({
+        "current": "v1",
+        "versions": [{ "version": "v1", "status": "current", "path": "/api/v1" }],
+    }));
+}
+
+#[tokio::test]
+async fn versioned_routes_report_their_version() {
+    let app = setup().await;
+
+    let request = Request::builder().uri("/api/v1/items").body(Body::empty()).unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["api-version"], "v1");
+    assert!(response.headers().get("deprecation").is_none());
+}
+
+#[tokio::test]
+async fn legacy_routes_are_a_deprecated_alias_of_v1() {
+    let app = setup().await;
+
+    let (_, created) = request(&app, "POST", "/items", Some(json!({ "name": "Legacy", "description": "" }))).await;
+    let id = created["id"].as_str().unwrap();
+
+    let legacy = Request::builder().uri(format!("/items/{id}")).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(legacy).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["deprecation"], "true");
+    assert_eq!(response.headers()[header::LINK], format!("</api/v1/items/{id}>; rel=\"successor-version\""));
+
+    let (status, item) = request(&app, "GET", &format!("/api/v1/items/{id}"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(item, created);
+}
+
+#[tokio::test]
+async fn the_legacy_alias_only_covers_pre_versioning_routes() {
+    let app = setup().await;
+
+    let (_, created) = request(&app, "POST", "/items", Some(json!({ "name": "Legacy", "description": "" }))).await;
+    let id = created["id"].as_str().unwrap();
+    let (status, _) = request(&app, "GET", &format!("/items/{id}/reminders"), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    for uri in ["/actions".to_string(), "/admin/storage".to_string(), format!("/items/{id}/comments")] {
+        let (status, _) = request(&app, "GET", &uri, None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{uri}");
+        let (status, _) = request(&app, "GET", &format!("/api/v1{uri}"), None).await;
+        assert_eq!(status, StatusCode::OK, "/api/v1{uri}");
+    }
+}